@@ -0,0 +1,34 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use decaf377::Fq;
+use penumbra_keys::{
+    keys::{Bip44Path, SeedPhrase, SpendKey},
+    PayloadKey,
+};
+use penumbra_tct::StateCommitment;
+use rand_core::OsRng;
+
+fn bench(c: &mut Criterion) {
+    let sk = SpendKey::from_seed_phrase_bip44(SeedPhrase::generate(OsRng), &Bip44Path::new(0));
+    let ovk = sk.full_viewing_key().outgoing();
+
+    let mut group = c.benchmark_group("swap-kdf");
+    for count in [64u64, 512] {
+        let cms: Vec<StateCommitment> = (0..count).map(|i| StateCommitment(Fq::from(i))).collect();
+        group.throughput(Throughput::Elements(count));
+
+        group.bench_with_input(BenchmarkId::new("scalar", count), &cms, |b, cms| {
+            b.iter(|| {
+                cms.iter()
+                    .map(|cm| PayloadKey::derive_swap(ovk, *cm))
+                    .collect::<Vec<_>>()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("many", count), &cms, |b, cms| {
+            b.iter(|| PayloadKey::derive_swap_many(ovk, cms))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
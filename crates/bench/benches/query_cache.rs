@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use penumbra_view::QueryCache;
+
+/// Stands in for a storage query: a blocking read that builds a fresh response.
+async fn fetch_from_storage() -> anyhow::Result<Vec<String>> {
+    let assets = tokio::task::spawn_blocking(|| {
+        (0..256)
+            .map(|i| format!("asset-{i}"))
+            .collect::<Vec<String>>()
+    })
+    .await?;
+    Ok(assets)
+}
+
+fn query_cache_hit_vs_miss(c: &mut Criterion) {
+    let rt = tokio::runtime::Runtime::new().expect("can build tokio runtime");
+
+    let hits = QueryCache::<(), Vec<String>>::new(Duration::from_secs(3600));
+    // Warm the cache so that every lookup below is a hit.
+    rt.block_on(hits.get_or_fetch((), fetch_from_storage()))
+        .expect("can warm cache");
+
+    // A zero TTL never retains entries, so every lookup is a miss.
+    let misses = QueryCache::<(), Vec<String>>::new(Duration::ZERO);
+
+    c.bench_function("query cache: hit", |b| {
+        b.iter(|| {
+            rt.block_on(hits.get_or_fetch(black_box(()), fetch_from_storage()))
+                .expect("can read from cache")
+        })
+    });
+
+    c.bench_function("query cache: miss", |b| {
+        b.iter(|| {
+            rt.block_on(misses.get_or_fetch(black_box(()), fetch_from_storage()))
+                .expect("can read from storage")
+        })
+    });
+}
+
+criterion_group!(benches, query_cache_hit_vs_miss);
+criterion_main!(benches);
@@ -0,0 +1,60 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use penumbra_asset::asset;
+use penumbra_dex::{
+    swap::{SwapPayload, SwapPlaintext, SwapScanner},
+    TradingPair,
+};
+use penumbra_fee::Fee;
+use penumbra_keys::{
+    keys::{Bip44Path, SeedPhrase, SpendKey},
+    FullViewingKey,
+};
+use rand_core::OsRng;
+
+fn random_fvk() -> FullViewingKey {
+    SpendKey::from_seed_phrase_bip44(SeedPhrase::generate(OsRng), &Bip44Path::new(0))
+        .full_viewing_key()
+        .clone()
+}
+
+/// Build 1000 swap payloads, 10% of which belong to `fvk`.
+fn payloads(fvk: &FullViewingKey) -> Vec<SwapPayload> {
+    let other_fvk = random_fvk();
+    let gm = asset::Cache::with_known_assets().get_unit("gm").unwrap();
+    let gn = asset::Cache::with_known_assets().get_unit("gn").unwrap();
+    let trading_pair = TradingPair::new(gm.id(), gn.id());
+
+    (0..1000u64)
+        .map(|i| {
+            let sender = if i % 10 == 0 { fvk } else { &other_fvk };
+            let (claim_address, _dtk_d) = sender.incoming().payment_address(0u32.into());
+            SwapPlaintext::new(
+                &mut OsRng,
+                trading_pair,
+                (i + 1).into(),
+                0u64.into(),
+                Fee::default(),
+                claim_address,
+            )
+            .encrypt(sender.outgoing())
+        })
+        .collect()
+}
+
+fn swap_scanning(c: &mut Criterion) {
+    let fvk = random_fvk();
+    let payloads = payloads(&fvk);
+    let scanner = SwapScanner::new(fvk);
+
+    c.bench_function("swap scanning: 1000 payloads, sequential", |b| {
+        b.iter(|| scanner.scan_batch_sequential(black_box(&payloads)))
+    });
+
+    #[cfg(feature = "parallel")]
+    c.bench_function("swap scanning: 1000 payloads, parallel", |b| {
+        b.iter(|| scanner.scan_batch_parallel(black_box(&payloads)))
+    });
+}
+
+criterion_group!(benches, swap_scanning);
+criterion_main!(benches);
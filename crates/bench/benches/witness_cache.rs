@@ -0,0 +1,63 @@
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use decaf377::Fq;
+use penumbra_tct::{StateCommitment, Tree, Witness, WitnessCache};
+
+const LEAVES: u64 = 10_000;
+const NOTES: u64 = 1_000;
+
+fn commitment(i: u64) -> StateCommitment {
+    StateCommitment(Fq::from(i + 1))
+}
+
+/// A tree of [`LEAVES`] commitments, of which every tenth is witnessed, for [`NOTES`] in total.
+fn tree() -> Tree {
+    let mut tree = Tree::new();
+    for i in 0..LEAVES {
+        let witness = if i % (LEAVES / NOTES) == 0 {
+            Witness::Keep
+        } else {
+            Witness::Forget
+        };
+        tree.insert(witness, commitment(i))
+            .expect("tree has room for all the leaves");
+    }
+    tree
+}
+
+fn notes() -> impl Iterator<Item = StateCommitment> {
+    (0..LEAVES)
+        .step_by((LEAVES / NOTES) as usize)
+        .map(commitment)
+}
+
+fn bench(c: &mut Criterion) {
+    let tree = tree();
+
+    let mut group = c.benchmark_group("witness-cache");
+    group.throughput(Throughput::Elements(NOTES));
+
+    group.bench_function("generate_witnesses", |b| {
+        b.iter(|| {
+            for note in notes() {
+                tree.witness(note).expect("note is witnessed");
+            }
+        })
+    });
+
+    let mut cache = WitnessCache::from_tree(tree.clone(), NOTES as usize);
+    for note in notes() {
+        cache.get_or_generate(note).expect("note is witnessed");
+    }
+    group.bench_function("cached_witnesses", |b| {
+        b.iter(|| {
+            for note in notes() {
+                cache.get_or_generate(note).expect("note is witnessed");
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
@@ -157,7 +157,11 @@ impl ValidatorCmd {
                         "".into(),
                         "".into(),
                         "".into(),
-                        v.validator.name.to_string().bright_green().to_string(),
+                        format!(
+                            "{} ({})",
+                            v.validator.name.to_string().bright_green(),
+                            v.validator.identity_key.fingerprint(),
+                        ),
                     ]);
                     if *detailed {
                         table.add_row(vec![
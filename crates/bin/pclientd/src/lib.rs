@@ -27,6 +27,7 @@ use std::fs;
 use std::fs::File;
 use std::io::{self, BufRead, Write};
 use std::str::FromStr;
+use std::time::Duration;
 use tonic::transport::Server;
 use url::Url;
 
@@ -109,7 +110,12 @@ pub enum Command {
         bind_addr: SocketAddr,
     },
     /// Start running `pclientd`.
-    Start {},
+    Start {
+        /// How long, in seconds, to cache the results of frequently-repeated view service
+        /// queries, such as asset listings and app parameters. Set to 0 to disable caching.
+        #[clap(long, display_order = 100, default_value = "0")]
+        query_cache_ttl_secs: u64,
+    },
     /// Delete `pclientd` storage to reset local state.
     Reset {},
 }
@@ -288,7 +294,9 @@ impl Opt {
 
                 Ok(())
             }
-            Command::Start {} => {
+            Command::Start {
+                query_cache_ttl_secs,
+            } => {
                 let config = PclientdConfig::load(opt.config_path()).context(
                     "Failed to load pclientd config file. Have you run `pclientd init` with a FVK?",
                 )?;
@@ -316,8 +324,11 @@ impl Opt {
                 let compact_block_query_proxy = CompactBlockQueryProxy(proxy_channel.clone());
                 let tendermint_proxy_proxy = TendermintProxyProxy(proxy_channel.clone());
 
-                let view_service =
-                    ViewServiceServer::new(ViewServer::new(storage, config.grpc_url).await?);
+                let view_service = ViewServiceServer::new(
+                    ViewServer::new(storage, config.grpc_url)
+                        .await?
+                        .with_query_cache_ttl(Duration::from_secs(*query_cache_ttl_secs)),
+                );
                 let custody_service = config.kms_config.as_ref().map(|kms_config| {
                     CustodyServiceServer::new(SoftKms::new(kms_config.spend_key.clone().into()))
                 });
@@ -165,12 +165,14 @@ async fn main() -> anyhow::Result<()> {
             };
 
             // Configure a Prometheus recorder and exporter.
+            use penumbra_compact_block::component::metrics::PrometheusBuilderExt as _;
             use penumbra_dex::component::metrics::PrometheusBuilderExt;
             let (recorder, exporter) = PrometheusBuilder::new()
                 .with_http_listener(metrics_bind)
                 // Set explicit buckets so that Prometheus endpoint emits true histograms, rather
                 // than the default distribution type summaries, for time-series data.
                 .set_buckets_for_dex_metrics()?
+                .set_buckets_for_compact_block_metrics()?
                 .build()
                 .map_err(|e| {
                     let msg = format!(
@@ -0,0 +1,645 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+
+use anyhow::anyhow;
+use penumbra_asset::Value;
+use penumbra_dex::{BatchSwapOutputData, TradingPair};
+use penumbra_keys::FullViewingKey;
+use penumbra_num::{fixpoint::U128x128, Amount};
+use penumbra_proto::{
+    core::component::{dex::v1 as dex_pb, sct::v1 as sct_pb},
+    event::ProtoEvent,
+    DomainType,
+};
+use penumbra_sct::Nullifier;
+use penumbra_tct as tct;
+use penumbra_transaction::Transaction;
+use prost::Message as _;
+use sqlx::{
+    types::chrono::{DateTime, Utc},
+    PgPool,
+};
+use tendermint::abci;
+use tendermint_proto::v0_37::abci::TxResult;
+
+const CSV_HEADER: &str = "kind,height,timestamp,commitment,nullifier,asset_1,amount_1,asset_2,amount_2,output_1,output_2,settlement_price";
+
+/// A note received by the audited key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NoteDetail {
+    pub height: u64,
+    pub timestamp: Option<DateTime<Utc>>,
+    pub commitment: tct::StateCommitment,
+    pub value: Value,
+}
+
+/// A note or swap of the audited key being spent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpendDetail {
+    pub height: u64,
+    pub nullifier: Nullifier,
+}
+
+/// A swap made by the audited key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SwapDetail {
+    pub height: u64,
+    pub commitment: tct::StateCommitment,
+    pub trading_pair: TradingPair,
+    pub delta_1_i: Amount,
+    pub delta_2_i: Amount,
+    /// The amounts of each asset the swap is entitled to claim, if its batch was executed.
+    pub output: Option<(Amount, Amount)>,
+    /// The price the swap settled at, in units of `asset_2` per unit of `asset_1`.
+    pub settlement_price: Option<U128x128>,
+}
+
+/// The activity of a single key over a range of blocks.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AuditReport {
+    pub notes: Vec<NoteDetail>,
+    pub spends: Vec<SpendDetail>,
+    pub swaps: Vec<SwapDetail>,
+}
+
+impl AuditReport {
+    /// Render the report as a single CSV table, with one row per note, spend, and swap.
+    ///
+    /// Columns that don't apply to a row's kind are left empty.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::new();
+        out.push_str(CSV_HEADER);
+        out.push('\n');
+        for note in &self.notes {
+            let _ = writeln!(
+                out,
+                "note,{},{},{},,{},{},,,,,",
+                note.height,
+                note.timestamp.map(|t| t.to_rfc3339()).unwrap_or_default(),
+                hex::encode(note.commitment.0.to_bytes()),
+                hex::encode(note.value.asset_id.to_bytes()),
+                note.value.amount,
+            );
+        }
+        for spend in &self.spends {
+            let _ = writeln!(
+                out,
+                "spend,{},,,{},,,,,,,",
+                spend.height,
+                hex::encode(spend.nullifier.to_bytes()),
+            );
+        }
+        for swap in &self.swaps {
+            let (output_1, output_2) = match swap.output {
+                Some((output_1, output_2)) => (output_1.to_string(), output_2.to_string()),
+                None => Default::default(),
+            };
+            let _ = writeln!(
+                out,
+                "swap,{},,{},,{},{},{},{},{},{},{}",
+                swap.height,
+                hex::encode(swap.commitment.0.to_bytes()),
+                hex::encode(swap.trading_pair.asset_1().to_bytes()),
+                swap.delta_1_i,
+                hex::encode(swap.trading_pair.asset_2().to_bytes()),
+                swap.delta_2_i,
+                output_1,
+                output_2,
+                swap.settlement_price
+                    .map(|price| price.to_string())
+                    .unwrap_or_default(),
+            );
+        }
+        out
+    }
+
+    /// Render the report as a JSON object with `notes`, `spends` and `swaps` arrays.
+    ///
+    /// Amounts are rendered as strings, since they may not fit in a JSON number.
+    pub fn to_json(&self) -> String {
+        let notes: Vec<_> = self
+            .notes
+            .iter()
+            .map(|note| {
+                serde_json::json!({
+                    "height": note.height,
+                    "timestamp": note.timestamp.map(|t| t.to_rfc3339()),
+                    "commitment": hex::encode(note.commitment.0.to_bytes()),
+                    "asset_id": hex::encode(note.value.asset_id.to_bytes()),
+                    "amount": note.value.amount.to_string(),
+                })
+            })
+            .collect();
+        let spends: Vec<_> = self
+            .spends
+            .iter()
+            .map(|spend| {
+                serde_json::json!({
+                    "height": spend.height,
+                    "nullifier": hex::encode(spend.nullifier.to_bytes()),
+                })
+            })
+            .collect();
+        let swaps: Vec<_> = self
+            .swaps
+            .iter()
+            .map(|swap| {
+                serde_json::json!({
+                    "height": swap.height,
+                    "commitment": hex::encode(swap.commitment.0.to_bytes()),
+                    "asset_1": hex::encode(swap.trading_pair.asset_1().to_bytes()),
+                    "amount_1": swap.delta_1_i.to_string(),
+                    "asset_2": hex::encode(swap.trading_pair.asset_2().to_bytes()),
+                    "amount_2": swap.delta_2_i.to_string(),
+                    "output_1": swap.output.map(|(output_1, _)| output_1.to_string()),
+                    "output_2": swap.output.map(|(_, output_2)| output_2.to_string()),
+                    "settlement_price": swap.settlement_price.map(|price| price.to_string()),
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "notes": notes,
+            "spends": spends,
+            "swaps": swaps,
+        })
+        .to_string()
+    }
+}
+
+/// The price a swap settled at, in units of `asset_2` per unit of `asset_1`.
+pub(crate) fn settlement_price(
+    (delta_1_i, delta_2_i): (Amount, Amount),
+    (lambda_1_i, lambda_2_i): (Amount, Amount),
+) -> Option<U128x128> {
+    if delta_1_i != Amount::zero() {
+        U128x128::ratio(lambda_2_i, delta_1_i).ok()
+    } else {
+        U128x128::ratio(delta_2_i, lambda_1_i).ok()
+    }
+}
+
+/// Read every event of `kind` from blocks in `start_height..=end_height`, in order.
+async fn events_in_range(
+    src_db: &PgPool,
+    kind: &str,
+    start_height: u64,
+    end_height: u64,
+) -> anyhow::Result<Vec<abci::Event>> {
+    let rows: Vec<(serde_json::Value,)> = sqlx::query_as(
+        "SELECT jsonb_object_agg(attributes.key, attributes.value)
+         FROM events
+         JOIN blocks ON blocks.rowid = events.block_id
+         JOIN attributes ON attributes.event_id = events.rowid
+         WHERE events.type = $1 AND blocks.height BETWEEN $2 AND $3
+         GROUP BY events.rowid
+         ORDER BY events.rowid",
+    )
+    .bind(kind)
+    .bind(i64::try_from(start_height)?)
+    .bind(i64::try_from(end_height)?)
+    .fetch_all(src_db)
+    .await?;
+
+    rows.into_iter()
+        .map(|(attrs,)| {
+            let serde_json::Value::Object(attrs) = attrs else {
+                return Err(anyhow!("expected event attributes to be a JSON object"));
+            };
+            let attributes = attrs
+                .into_iter()
+                .filter_map(|(k, v)| match v {
+                    serde_json::Value::String(s) => Some((k, s).into()),
+                    _ => None,
+                })
+                .collect();
+            Ok(abci::Event {
+                kind: kind.to_string(),
+                attributes,
+            })
+        })
+        .collect()
+}
+
+/// Exports the notes, spends and swaps of a single full viewing key from the raw event database.
+///
+/// Only notes created by outputs are found; notes minted by the chain, such as swap claim outputs,
+/// are not part of any transaction's payloads and so are not reported.
+#[derive(Debug)]
+pub struct AuditExporter {
+    fvk: FullViewingKey,
+    /// The nullifiers of every note and swap found in the ranges exported so far.
+    nullifiers: BTreeSet<Nullifier>,
+}
+
+impl AuditExporter {
+    pub fn new(fvk: &FullViewingKey) -> Self {
+        Self {
+            fvk: fvk.clone(),
+            nullifiers: BTreeSet::new(),
+        }
+    }
+
+    /// Export the activity of this key in blocks `start_height..=end_height`.
+    ///
+    /// Only those blocks are read. Recognizing a spend requires having found the note or swap it
+    /// spends, so spends of notes received before `start_height` are only reported if the range
+    /// they were received in was exported first, by this exporter.
+    pub async fn export_range(
+        &mut self,
+        start_height: u64,
+        end_height: u64,
+        src_db: &PgPool,
+    ) -> anyhow::Result<AuditReport> {
+        let mut positions = BTreeMap::new();
+        for event in events_in_range(
+            src_db,
+            "penumbra.core.component.sct.v1.EventCommitment",
+            start_height,
+            end_height,
+        )
+        .await?
+        {
+            let pe = sct_pb::EventCommitment::from_event(&event)?;
+            let commitment: tct::StateCommitment = pe
+                .commitment
+                .ok_or_else(|| anyhow!("missing commitment"))?
+                .try_into()?;
+            positions.insert(commitment, tct::Position::from(pe.position));
+        }
+
+        let mut timestamps = BTreeMap::new();
+        for event in events_in_range(
+            src_db,
+            "penumbra.core.component.sct.v1.EventBlockRoot",
+            start_height,
+            end_height,
+        )
+        .await?
+        {
+            let pe = sct_pb::EventBlockRoot::from_event(&event)?;
+            let timestamp = pe.timestamp.unwrap_or_default();
+            timestamps.insert(
+                pe.height,
+                DateTime::from_timestamp(timestamp.seconds, u32::try_from(timestamp.nanos)?),
+            );
+        }
+
+        let mut batch_swaps = BTreeMap::new();
+        for event in events_in_range(
+            src_db,
+            "penumbra.core.component.dex.v1.EventBatchSwap",
+            start_height,
+            end_height,
+        )
+        .await?
+        {
+            let pe = dex_pb::EventBatchSwap::from_event(&event)?;
+            let bsod: BatchSwapOutputData = pe
+                .batch_swap_output_data
+                .ok_or_else(|| anyhow!("missing batch_swap_output_data"))?
+                .try_into()?;
+            batch_swaps.insert((bsod.height, bsod.trading_pair), bsod);
+        }
+
+        let rows: Vec<(i64, Vec<u8>)> = sqlx::query_as(
+            "SELECT blocks.height, tx_results.tx_result
+             FROM tx_results
+             JOIN blocks ON blocks.rowid = tx_results.block_id
+             WHERE blocks.height BETWEEN $1 AND $2
+             ORDER BY blocks.height, tx_results.index",
+        )
+        .bind(i64::try_from(start_height)?)
+        .bind(i64::try_from(end_height)?)
+        .fetch_all(src_db)
+        .await?;
+
+        let nk = self.fvk.nullifier_key();
+        let mut report = AuditReport::default();
+        for (height, tx_result) in rows {
+            let height = u64::try_from(height)?;
+            let tx_result = TxResult::decode(tx_result.as_slice())?;
+            // A failed transaction's effects were discarded, so its outputs never entered the tree.
+            if tx_result.result.is_some_and(|result| result.code != 0) {
+                continue;
+            }
+            let transaction = Transaction::decode(tx_result.tx)?;
+
+            for spent in transaction.spent_nullifiers() {
+                if self.nullifiers.contains(&spent) {
+                    report.spends.push(SpendDetail {
+                        height,
+                        nullifier: spent,
+                    });
+                }
+            }
+
+            for output in transaction.outputs() {
+                let Some(note) = output.body.note_payload.trial_decrypt(&self.fvk) else {
+                    continue;
+                };
+                let commitment = note.commit();
+                if let Some(position) = positions.get(&commitment) {
+                    self.nullifiers
+                        .insert(Nullifier::derive(nk, *position, &commitment));
+                }
+                report.notes.push(NoteDetail {
+                    height,
+                    timestamp: timestamps.get(&height).copied().flatten(),
+                    commitment,
+                    value: note.value(),
+                });
+            }
+
+            for swap in transaction.swaps() {
+                let Some(plaintext) = swap.body.payload.trial_decrypt(&self.fvk) else {
+                    continue;
+                };
+                let commitment = plaintext.swap_commitment();
+                if let Some(position) = positions.get(&commitment) {
+                    self.nullifiers
+                        .insert(Nullifier::derive(nk, *position, &commitment));
+                }
+                let deltas = (plaintext.delta_1_i, plaintext.delta_2_i);
+                let output = batch_swaps
+                    .get(&(height, plaintext.trading_pair))
+                    .map(|bsod| bsod.pro_rata_outputs(deltas));
+                report.swaps.push(SwapDetail {
+                    height,
+                    commitment,
+                    trading_pair: plaintext.trading_pair,
+                    delta_1_i: plaintext.delta_1_i,
+                    delta_2_i: plaintext.delta_2_i,
+                    output,
+                    settlement_price: output.and_then(|output| settlement_price(deltas, output)),
+                });
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use decaf377::{Element, Fq};
+    use penumbra_asset::{asset, balance, STAKING_TOKEN_ASSET_ID};
+    use penumbra_keys::{
+        symmetric::{
+            OvkWrappedKey, WrappedMemoKey, MEMOKEY_WRAPPED_LEN_BYTES, OVK_WRAPPED_LEN_BYTES,
+        },
+        test_keys, Address,
+    };
+    use penumbra_proto::core::component::shielded_pool::v1 as shielded_pool_pb;
+    use penumbra_shielded_pool::{output, spend, Note, Output, OutputProof, Spend, SpendProof};
+    use penumbra_transaction::Action;
+    use prost::Message;
+    use rand_core::OsRng;
+    use tendermint_proto::v0_37::abci::ResponseDeliverTx;
+
+    use super::*;
+    use crate::testing;
+
+    fn asset_id(n: u64) -> asset::Id {
+        asset::Id(Fq::from(n))
+    }
+
+    fn timestamp(height: u64) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp(1_700_000_000 + 5 * i64::try_from(height).unwrap(), 0)
+    }
+
+    /// The activity of a key over five blocks: two notes received, a swap, and two spends.
+    fn five_block_report() -> AuditReport {
+        let swap_deltas = (100u64.into(), 0u64.into());
+        let swap_output = (0u64.into(), 200u64.into());
+        AuditReport {
+            notes: vec![
+                NoteDetail {
+                    height: 1,
+                    timestamp: timestamp(1),
+                    commitment: tct::StateCommitment(Fq::from(1u64)),
+                    value: Value {
+                        amount: 1_000u64.into(),
+                        asset_id: asset_id(10),
+                    },
+                },
+                NoteDetail {
+                    height: 2,
+                    timestamp: timestamp(2),
+                    commitment: tct::StateCommitment(Fq::from(2u64)),
+                    value: Value {
+                        amount: 250u64.into(),
+                        asset_id: asset_id(11),
+                    },
+                },
+            ],
+            spends: vec![
+                SpendDetail {
+                    height: 3,
+                    nullifier: Nullifier(Fq::from(3u64)),
+                },
+                SpendDetail {
+                    height: 5,
+                    nullifier: Nullifier(Fq::from(5u64)),
+                },
+            ],
+            swaps: vec![SwapDetail {
+                height: 3,
+                commitment: tct::StateCommitment(Fq::from(4u64)),
+                trading_pair: TradingPair::new(asset_id(10), asset_id(11)),
+                delta_1_i: swap_deltas.0,
+                delta_2_i: swap_deltas.1,
+                output: Some(swap_output),
+                settlement_price: settlement_price(swap_deltas, swap_output),
+            }],
+        }
+    }
+
+    #[test]
+    fn csv_matches_golden_fixture() {
+        assert_eq!(
+            five_block_report().to_csv(),
+            include_str!("audit/test/five_blocks.csv")
+        );
+    }
+
+    #[test]
+    fn json_contains_every_record() {
+        let json: serde_json::Value = serde_json::from_str(&five_block_report().to_json()).unwrap();
+        assert_eq!(json["notes"].as_array().unwrap().len(), 2);
+        assert_eq!(json["spends"].as_array().unwrap().len(), 2);
+        assert_eq!(json["swaps"].as_array().unwrap().len(), 1);
+        assert_eq!(json["notes"][0]["amount"], "1000");
+        assert_eq!(json["swaps"][0]["settlement_price"], "2");
+    }
+
+    #[test]
+    fn settlement_price_is_quoted_in_asset_2() {
+        let price = |deltas: (u64, u64), lambdas: (u64, u64)| {
+            settlement_price(
+                (deltas.0.into(), deltas.1.into()),
+                (lambdas.0.into(), lambdas.1.into()),
+            )
+            .map(f64::from)
+        };
+        assert_eq!(price((100, 0), (0, 200)), Some(2.0));
+        assert_eq!(price((0, 200), (100, 0)), Some(2.0));
+        assert_eq!(price((0, 200), (0, 0)), None);
+    }
+
+    /// An encoded `TxResult` for a transaction with outputs of `notes` and spends of `nullifiers`.
+    ///
+    /// Proofs, signatures and balance commitments are placeholders, since the exporter doesn't
+    /// check them.
+    fn tx_result(notes: &[Note], nullifiers: &[Nullifier]) -> Vec<u8> {
+        let spends = nullifiers.iter().map(|nullifier| {
+            Action::Spend(Spend {
+                body: spend::Body {
+                    balance_commitment: balance::Commitment(Element::GENERATOR),
+                    nullifier: *nullifier,
+                    rk: Element::GENERATOR.vartime_compress().0.try_into().unwrap(),
+                },
+                auth_sig: [0u8; 64].into(),
+                proof: SpendProof::try_from(shielded_pool_pb::ZkSpendProof {
+                    inner: vec![0; 192],
+                })
+                .unwrap(),
+            })
+        });
+        let outputs = notes.iter().map(|note| {
+            Action::Output(Output {
+                body: output::Body {
+                    note_payload: note.payload(),
+                    balance_commitment: balance::Commitment(Element::GENERATOR),
+                    ovk_wrapped_key: OvkWrappedKey([0; OVK_WRAPPED_LEN_BYTES]),
+                    wrapped_memo_key: WrappedMemoKey([0; MEMOKEY_WRAPPED_LEN_BYTES]),
+                },
+                proof: OutputProof::try_from(shielded_pool_pb::ZkOutputProof {
+                    inner: vec![0; 192],
+                })
+                .unwrap(),
+            })
+        });
+        let mut transaction = Transaction::default();
+        transaction.transaction_body.actions = spends.chain(outputs).collect();
+        TxResult {
+            tx: transaction.encode_to_vec().into(),
+            ..Default::default()
+        }
+        .encode_to_vec()
+    }
+
+    /// `tx_result`, marked as having failed.
+    fn failed(tx_result: Vec<u8>) -> Vec<u8> {
+        TxResult {
+            result: Some(ResponseDeliverTx {
+                code: 1,
+                ..Default::default()
+            }),
+            ..TxResult::decode(tx_result.as_slice()).unwrap()
+        }
+        .encode_to_vec()
+    }
+
+    fn commitment_event(note: &Note, position: u64) -> abci::Event {
+        sct_pb::EventCommitment {
+            commitment: Some(note.commit().into()),
+            position,
+            source: None,
+        }
+        .into_event()
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a scratch database in PINDEXER_TEST_DATABASE_URL"]
+    async fn spends_are_recognized_from_earlier_ranges() -> anyhow::Result<()> {
+        let pool = testing::database().await?;
+        testing::install_raw_schema(&pool).await?;
+        let fvk = &*test_keys::FULL_VIEWING_KEY;
+        let value = Value {
+            amount: 1_000u64.into(),
+            asset_id: *STAKING_TOKEN_ASSET_ID,
+        };
+        let received = Note::generate(&mut OsRng, &test_keys::ADDRESS_0, value);
+        let elsewhere = Note::generate(&mut OsRng, &Address::dummy(&mut OsRng), value);
+        let nullifier = Nullifier::derive(
+            fvk.nullifier_key(),
+            tct::Position::from(0u64),
+            &received.commit(),
+        );
+        testing::insert_raw_block(
+            &pool,
+            1,
+            &[
+                commitment_event(&received, 0),
+                commitment_event(&elsewhere, 1),
+            ],
+            &[tx_result(&[received.clone(), elsewhere], &[])],
+        )
+        .await?;
+        testing::insert_raw_block(&pool, 3, &[], &[tx_result(&[], &[nullifier])]).await?;
+
+        // Only the exported range is read, so on its own the later range has nothing of the key's.
+        let report = AuditExporter::new(fvk).export_range(2, 3, &pool).await?;
+        assert_eq!(report, AuditReport::default());
+
+        let mut exporter = AuditExporter::new(fvk);
+        let report = exporter.export_range(1, 2, &pool).await?;
+        assert_eq!(
+            report.notes,
+            vec![NoteDetail {
+                height: 1,
+                timestamp: None,
+                commitment: received.commit(),
+                value,
+            }]
+        );
+        assert!(report.spends.is_empty());
+
+        let report = exporter.export_range(3, 3, &pool).await?;
+        assert!(report.notes.is_empty());
+        assert_eq!(
+            report.spends,
+            vec![SpendDetail {
+                height: 3,
+                nullifier,
+            }]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a scratch database in PINDEXER_TEST_DATABASE_URL"]
+    async fn failed_transactions_are_not_exported() -> anyhow::Result<()> {
+        let pool = testing::database().await?;
+        testing::install_raw_schema(&pool).await?;
+        let fvk = &*test_keys::FULL_VIEWING_KEY;
+        let value = Value {
+            amount: 1_000u64.into(),
+            asset_id: *STAKING_TOKEN_ASSET_ID,
+        };
+        let received = Note::generate(&mut OsRng, &test_keys::ADDRESS_0, value);
+        let discarded = Note::generate(&mut OsRng, &test_keys::ADDRESS_0, value);
+        testing::insert_raw_block(
+            &pool,
+            1,
+            &[commitment_event(&received, 0)],
+            &[
+                tx_result(&[received.clone()], &[]),
+                failed(tx_result(&[discarded], &[])),
+            ],
+        )
+        .await?;
+
+        let report = AuditExporter::new(fvk).export_range(1, 1, &pool).await?;
+        assert_eq!(
+            report.notes,
+            vec![NoteDetail {
+                height: 1,
+                timestamp: None,
+                commitment: received.commit(),
+                value,
+            }]
+        );
+        Ok(())
+    }
+}
@@ -13,6 +13,13 @@ use sqlx::{PgPool, Postgres, Transaction};
 use crate::sql::Sql;
 use crate::{AppView, ContextualizedEvent, PgTransaction};
 
+mod receipt;
+mod settlement;
+mod volume;
+pub use receipt::{SwapReceipt, SwapReceiptView};
+pub use settlement::SwapSettlementView;
+pub use volume::{VolumeView, DEFAULT_BLOCK_TIME_SECS, WINDOW_HOURS};
+
 /// Insert a swap execution into the database.
 ///
 /// This returns the start and end indices of its trace.
@@ -0,0 +1,517 @@
+use anyhow::{anyhow, Context as _};
+use cometindex::async_trait;
+use penumbra_asset::{asset, Value};
+use penumbra_dex::{swap::SwapPlaintext, BatchSwapOutputData, TradingPair};
+use penumbra_fee::Fee;
+use penumbra_num::{fixpoint::U128x128, Amount};
+use penumbra_proto::{
+    core::component::dex::v1 as pb, crypto::tct::v1 as pb_tct, event::ProtoEvent, DomainType,
+};
+use penumbra_tct::StateCommitment;
+use sqlx::PgPool;
+
+use crate::audit::settlement_price;
+use crate::sql::Sql;
+use crate::{AppView, ContextualizedEvent, PgTransaction};
+
+/// The outcome of a single swap, once its batch has executed and it has been claimed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SwapReceipt {
+    pub swap_commitment: StateCommitment,
+    pub pair: TradingPair,
+    pub input_1: Amount,
+    pub input_2: Amount,
+    pub output_1: Amount,
+    pub output_2: Amount,
+    /// The price the swap cleared at, in units of `asset_2` per unit of `asset_1`.
+    pub clearing_price: Option<U128x128>,
+    /// The fee paid to claim the swap.
+    pub fee_paid: Fee,
+    /// The height of the block in which the swap's batch was executed.
+    pub execution_height: u64,
+}
+
+impl SwapReceipt {
+    /// Build the receipt for `swap` from the batch swap and swap claim events of its pair.
+    ///
+    /// The batch that executed the swap is identified by checking which one produces the output
+    /// notes recorded by one of the swap claims, so `events` may contain batches and claims for
+    /// other swaps in the same pair.
+    pub fn from_claim_events(
+        swap: &SwapPlaintext,
+        events: &[ContextualizedEvent],
+    ) -> anyhow::Result<Self> {
+        let mut batches = Vec::new();
+        let mut claims = Vec::new();
+        for event in events {
+            if event.event.kind == pb::EventBatchSwap::full_name() {
+                let pe = pb::EventBatchSwap::from_event(event.as_ref())?;
+                let bsod: BatchSwapOutputData = pe
+                    .batch_swap_output_data
+                    .ok_or_else(|| anyhow!("event missing batch_swap_output_data"))?
+                    .try_into()?;
+                if bsod.trading_pair == swap.trading_pair {
+                    batches.push(bsod);
+                }
+            } else if event.event.kind == pb::EventSwapClaim::full_name() {
+                let pe = pb::EventSwapClaim::from_event(event.as_ref())?;
+                let output_1: StateCommitment = pe
+                    .output_1_commitment
+                    .ok_or_else(|| anyhow!("event missing output_1_commitment"))?
+                    .try_into()?;
+                let output_2: StateCommitment = pe
+                    .output_2_commitment
+                    .ok_or_else(|| anyhow!("event missing output_2_commitment"))?
+                    .try_into()?;
+                claims.push((output_1, output_2));
+            }
+        }
+
+        let bsod = batches
+            .into_iter()
+            .find(|bsod| {
+                let (note_1, note_2) = swap.output_notes(bsod);
+                claims.contains(&(note_1.commit(), note_2.commit()))
+            })
+            .ok_or_else(|| anyhow!("no claimed batch swap matches this swap"))?;
+
+        Ok(Self::from_batch(swap, &bsod))
+    }
+
+    /// Build the receipt for `swap` from the output of the batch that executed it.
+    fn from_batch(swap: &SwapPlaintext, bsod: &BatchSwapOutputData) -> Self {
+        let inputs = (swap.delta_1_i, swap.delta_2_i);
+        let (output_1, output_2) = bsod.pro_rata_outputs(inputs);
+        Self {
+            swap_commitment: swap.swap_commitment(),
+            pair: swap.trading_pair,
+            input_1: swap.delta_1_i,
+            input_2: swap.delta_2_i,
+            output_1,
+            output_2,
+            clearing_price: settlement_price(inputs, (output_1, output_2)),
+            fee_paid: swap.claim_fee.clone(),
+            execution_height: bsod.height,
+        }
+    }
+
+    /// Describe the receipt in a single line, using `cache` to name and scale the assets.
+    ///
+    /// The clearing price is given in base units, as the two assets may have different
+    /// display units.
+    pub fn format_human(&self, cache: &asset::Cache) -> String {
+        let values = |amount_1: Amount, amount_2: Amount| {
+            let values: Vec<String> = [
+                (amount_1, self.pair.asset_1()),
+                (amount_2, self.pair.asset_2()),
+            ]
+            .into_iter()
+            .filter(|(amount, _)| *amount != Amount::zero())
+            .map(|(amount, asset_id)| Value { amount, asset_id }.format(cache))
+            .collect();
+            if values.is_empty() {
+                "nothing".to_string()
+            } else {
+                values.join(" and ")
+            }
+        };
+        let denom = |asset_id: asset::Id| {
+            cache
+                .get(&asset_id)
+                .map(|metadata| metadata.to_string())
+                .unwrap_or_else(|| asset_id.to_string())
+        };
+        let price = match self.clearing_price {
+            Some(price) => format!(
+                "{price} {} per {}",
+                denom(self.pair.asset_2()),
+                denom(self.pair.asset_1())
+            ),
+            None => "n/a".to_string(),
+        };
+
+        format!(
+            "Swapped {} for {} at height {}, clearing price {}, claim fee {}",
+            values(self.input_1, self.input_2),
+            values(self.output_1, self.output_2),
+            self.execution_height,
+            price,
+            self.fee_paid.format(cache),
+        )
+    }
+}
+
+/// Records the height of each swap, the batch swap output data of each pair at each height, and
+/// the output notes of each swap claim, so that a wallet holding a [`SwapPlaintext`] can look up
+/// its [`SwapReceipt`].
+#[derive(Debug)]
+pub struct SwapReceiptView {}
+
+impl SwapReceiptView {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Look up the receipt for `swap`, if its batch has executed and it has been claimed.
+    ///
+    /// A swap is executed in the batch of the block that included it, so only that batch is
+    /// fetched, and the claim is looked up by the output notes the batch gives the swap.
+    pub async fn receipt(
+        pool: &PgPool,
+        swap: &SwapPlaintext,
+    ) -> anyhow::Result<Option<SwapReceipt>> {
+        let height: Option<i64> = sqlx::query_scalar(
+            "SELECT height FROM dex_swap_receipt_swap WHERE swap_commitment = $1",
+        )
+        .bind(commitment_bytes(swap.swap_commitment()))
+        .fetch_optional(pool)
+        .await?;
+        let Some(height) = height else {
+            return Ok(None);
+        };
+
+        let bsod: Option<Vec<u8>> = sqlx::query_scalar(
+            "SELECT bsod FROM dex_swap_receipt_batch
+             WHERE asset1 = $1 AND asset2 = $2 AND height = $3",
+        )
+        .bind(Sql::from(swap.trading_pair.asset_1()))
+        .bind(Sql::from(swap.trading_pair.asset_2()))
+        .bind(height)
+        .fetch_optional(pool)
+        .await?;
+        let Some(bsod) = bsod else {
+            return Ok(None);
+        };
+        let bsod = BatchSwapOutputData::decode(bsod.as_slice())
+            .with_context(|| format!("invalid batch swap output data at height {height}"))?;
+
+        let (note_1, note_2) = swap.output_notes(&bsod);
+        let claimed: bool = sqlx::query_scalar(
+            "SELECT EXISTS (
+                 SELECT 1 FROM dex_swap_receipt_claim WHERE output_1 = $1 AND output_2 = $2
+             )",
+        )
+        .bind(commitment_bytes(note_1.commit()))
+        .bind(commitment_bytes(note_2.commit()))
+        .fetch_one(pool)
+        .await?;
+        if !claimed {
+            return Ok(None);
+        }
+
+        Ok(Some(SwapReceipt::from_batch(swap, &bsod)))
+    }
+}
+
+fn commitment_bytes(commitment: StateCommitment) -> Vec<u8> {
+    <[u8; 32]>::from(commitment).to_vec()
+}
+
+fn commitment(commitment: Option<pb_tct::StateCommitment>, name: &str) -> anyhow::Result<Vec<u8>> {
+    let commitment: StateCommitment = commitment
+        .ok_or_else(|| anyhow!("event missing {name}"))?
+        .try_into()
+        .with_context(|| format!("invalid {name}"))?;
+    Ok(commitment_bytes(commitment))
+}
+
+#[async_trait]
+impl AppView for SwapReceiptView {
+    async fn init_chain(
+        &self,
+        dbtx: &mut PgTransaction,
+        _app_state: &serde_json::Value,
+    ) -> anyhow::Result<()> {
+        for statement in [
+            "
+CREATE TABLE IF NOT EXISTS dex_swap_receipt_swap (
+    swap_commitment BYTEA PRIMARY KEY,
+    height BIGINT NOT NULL
+);
+",
+            "
+CREATE TABLE IF NOT EXISTS dex_swap_receipt_batch (
+    asset1 BYTEA NOT NULL,
+    asset2 BYTEA NOT NULL,
+    height BIGINT NOT NULL,
+    bsod BYTEA NOT NULL,
+    PRIMARY KEY (asset1, asset2, height)
+);
+",
+            "
+CREATE TABLE IF NOT EXISTS dex_swap_receipt_claim (
+    output_1 BYTEA PRIMARY KEY,
+    output_2 BYTEA NOT NULL,
+    height BIGINT NOT NULL
+);
+",
+        ] {
+            sqlx::query(statement).execute(dbtx.as_mut()).await?;
+        }
+        Ok(())
+    }
+
+    fn is_relevant(&self, type_str: &str) -> bool {
+        type_str == pb::EventSwap::full_name()
+            || type_str == pb::EventBatchSwap::full_name()
+            || type_str == pb::EventSwapClaim::full_name()
+    }
+
+    async fn index_event(
+        &self,
+        dbtx: &mut PgTransaction,
+        event: &ContextualizedEvent,
+        _src_db: &PgPool,
+    ) -> anyhow::Result<()> {
+        let height = i64::try_from(event.block_height)?;
+        if event.event.kind == pb::EventSwap::full_name() {
+            let pe = pb::EventSwap::from_event(event.as_ref())?;
+            sqlx::query(
+                "INSERT INTO dex_swap_receipt_swap (swap_commitment, height) VALUES ($1, $2)",
+            )
+            .bind(commitment(pe.swap_commitment, "swap_commitment")?)
+            .bind(height)
+            .execute(dbtx.as_mut())
+            .await?;
+        } else if event.event.kind == pb::EventBatchSwap::full_name() {
+            let pe = pb::EventBatchSwap::from_event(event.as_ref())?;
+            let bsod: BatchSwapOutputData = pe
+                .batch_swap_output_data
+                .ok_or_else(|| anyhow!("event missing batch_swap_output_data"))?
+                .try_into()?;
+            sqlx::query(
+                "INSERT INTO dex_swap_receipt_batch (asset1, asset2, height, bsod)
+                 VALUES ($1, $2, $3, $4)",
+            )
+            .bind(Sql::from(bsod.trading_pair.asset_1()))
+            .bind(Sql::from(bsod.trading_pair.asset_2()))
+            .bind(height)
+            .bind(bsod.encode_to_vec())
+            .execute(dbtx.as_mut())
+            .await?;
+        } else {
+            let pe = pb::EventSwapClaim::from_event(event.as_ref())?;
+            sqlx::query(
+                "INSERT INTO dex_swap_receipt_claim (output_1, output_2, height)
+                 VALUES ($1, $2, $3)",
+            )
+            .bind(commitment(pe.output_1_commitment, "output_1_commitment")?)
+            .bind(commitment(pe.output_2_commitment, "output_2_commitment")?)
+            .bind(height)
+            .execute(dbtx.as_mut())
+            .await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use penumbra_keys::Address;
+    use rand_core::OsRng;
+
+    use super::*;
+    use crate::testing;
+
+    fn contextualize(event: tendermint::abci::Event, block_height: u64) -> ContextualizedEvent {
+        ContextualizedEvent {
+            event,
+            block_height,
+            tx_hash: None,
+            local_rowid: 0,
+        }
+    }
+
+    fn gm_gn() -> (asset::Cache, TradingPair) {
+        let cache = asset::Cache::with_known_assets();
+        let pair = TradingPair::new(
+            cache.get_unit("gm").unwrap().id(),
+            cache.get_unit("gn").unwrap().id(),
+        );
+        (cache, pair)
+    }
+
+    fn bsod(pair: TradingPair, height: u64, lambda_2: u64) -> BatchSwapOutputData {
+        BatchSwapOutputData {
+            delta_1: 2_000_000u64.into(),
+            delta_2: 0u64.into(),
+            lambda_1: 0u64.into(),
+            lambda_2: lambda_2.into(),
+            unfilled_1: 0u64.into(),
+            unfilled_2: 0u64.into(),
+            height,
+            trading_pair: pair,
+            sct_position_prefix: Default::default(),
+        }
+    }
+
+    #[test]
+    fn receipt_is_built_from_the_claimed_batch() {
+        let (cache, pair) = gm_gn();
+        let swap = SwapPlaintext::new(
+            &mut OsRng,
+            pair,
+            1_000_000u64.into(),
+            0u64.into(),
+            Fee::default(),
+            Address::dummy(&mut OsRng),
+        );
+
+        // Two batches in the pair; only the second one executed this swap.
+        let other = bsod(pair, 10, 1_000_000);
+        let executed = bsod(pair, 12, 4_000_000);
+        let (note_1, note_2) = swap.output_notes(&executed);
+        let claim = pb::EventSwapClaim {
+            trading_pair: Some(pair.into()),
+            output_1_commitment: Some(note_1.commit().into()),
+            output_2_commitment: Some(note_2.commit().into()),
+            nullifier: None,
+        };
+        let events = vec![
+            contextualize(
+                pb::EventBatchSwap {
+                    batch_swap_output_data: Some(other.into()),
+                    swap_execution_1_for_2: None,
+                    swap_execution_2_for_1: None,
+                }
+                .into_event(),
+                10,
+            ),
+            contextualize(
+                pb::EventBatchSwap {
+                    batch_swap_output_data: Some(executed.into()),
+                    swap_execution_1_for_2: None,
+                    swap_execution_2_for_1: None,
+                }
+                .into_event(),
+                12,
+            ),
+            contextualize(claim.into_event(), 13),
+        ];
+
+        let receipt = SwapReceipt::from_claim_events(&swap, &events).unwrap();
+        assert_eq!(receipt.execution_height, 12);
+        assert_eq!(receipt.swap_commitment, swap.swap_commitment());
+        assert_eq!(
+            (receipt.output_1, receipt.output_2),
+            executed.pro_rata_outputs((swap.delta_1_i, swap.delta_2_i))
+        );
+
+        // Half of the batch's input, so half of its 4_000_000 output.
+        assert_eq!(receipt.output_2, 2_000_000u64.into());
+
+        let formatted = receipt.format_human(&cache);
+        let input = Value {
+            amount: 1_000_000u64.into(),
+            asset_id: pair.asset_1(),
+        }
+        .format(&cache);
+        let output = Value {
+            amount: 2_000_000u64.into(),
+            asset_id: pair.asset_2(),
+        }
+        .format(&cache);
+        let price = format!(
+            "clearing price 2 {} per {}",
+            cache.get(&pair.asset_2()).unwrap(),
+            cache.get(&pair.asset_1()).unwrap()
+        );
+        assert!(
+            formatted.contains(&format!("Swapped {input} for {output}")),
+            "{formatted}"
+        );
+        assert!(formatted.contains("at height 12"), "{formatted}");
+        assert!(formatted.contains(&price), "{formatted}");
+        assert!(
+            formatted.contains(&Fee::default().format(&cache)),
+            "{formatted}"
+        );
+    }
+
+    #[test]
+    fn receipt_requires_a_matching_claim() {
+        let (_, pair) = gm_gn();
+        let swap = SwapPlaintext::new(
+            &mut OsRng,
+            pair,
+            1_000_000u64.into(),
+            0u64.into(),
+            Fee::default(),
+            Address::dummy(&mut OsRng),
+        );
+        let events = vec![contextualize(
+            pb::EventBatchSwap {
+                batch_swap_output_data: Some(bsod(pair, 12, 4_000_000).into()),
+                swap_execution_1_for_2: None,
+                swap_execution_2_for_1: None,
+            }
+            .into_event(),
+            12,
+        )];
+
+        assert!(SwapReceipt::from_claim_events(&swap, &events).is_err());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a scratch database in PINDEXER_TEST_DATABASE_URL"]
+    async fn receipts_are_looked_up_once_the_swap_is_claimed() -> anyhow::Result<()> {
+        let pool = testing::database().await?;
+        let (_, pair) = gm_gn();
+        let swap = SwapPlaintext::new(
+            &mut OsRng,
+            pair,
+            1_000_000u64.into(),
+            0u64.into(),
+            Fee::default(),
+            Address::dummy(&mut OsRng),
+        );
+        let executed = bsod(pair, 12, 4_000_000);
+        let batch = |bsod: BatchSwapOutputData| pb::EventBatchSwap {
+            batch_swap_output_data: Some(bsod.into()),
+            swap_execution_1_for_2: None,
+            swap_execution_2_for_1: None,
+        };
+        let view = SwapReceiptView::new();
+        testing::index(
+            &pool,
+            &view,
+            [
+                contextualize(batch(bsod(pair, 10, 1_000_000)).into_event(), 10),
+                contextualize(
+                    pb::EventSwap {
+                        trading_pair: Some(pair.into()),
+                        delta_1_i: Some(swap.delta_1_i.into()),
+                        delta_2_i: Some(swap.delta_2_i.into()),
+                        swap_commitment: Some(swap.swap_commitment().into()),
+                    }
+                    .into_event(),
+                    12,
+                ),
+                contextualize(batch(executed).into_event(), 12),
+            ],
+        )
+        .await?;
+        assert_eq!(SwapReceiptView::receipt(&pool, &swap).await?, None);
+
+        let (note_1, note_2) = swap.output_notes(&executed);
+        let claim = pb::EventSwapClaim {
+            trading_pair: Some(pair.into()),
+            output_1_commitment: Some(note_1.commit().into()),
+            output_2_commitment: Some(note_2.commit().into()),
+            nullifier: None,
+        };
+        testing::index(&pool, &view, [contextualize(claim.into_event(), 13)]).await?;
+        let receipt = SwapReceiptView::receipt(&pool, &swap)
+            .await?
+            .expect("the swap has been claimed");
+        assert_eq!(receipt.execution_height, 12);
+        assert_eq!(receipt.output_2, 2_000_000u64.into());
+
+        // A corrupted batch is an error rather than a missing receipt.
+        sqlx::query("UPDATE dex_swap_receipt_batch SET bsod = '\\x00ff' WHERE height = 12")
+            .execute(&pool)
+            .await?;
+        assert!(SwapReceiptView::receipt(&pool, &swap).await.is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,128 @@
+use cometindex::async_trait;
+use penumbra_dex::event::SwapSettlementEvent;
+use penumbra_proto::{event::ProtoEvent, penumbra::core::component::dex::v1 as pb};
+use sqlx::PgPool;
+
+use super::volume::pair_bytes;
+use crate::{AppView, ContextualizedEvent, PgTransaction};
+
+/// Records how each batch swap settled, one row per trading pair per block.
+///
+/// Volume is measured on the batch inputs, as in [`VolumeView`](super::VolumeView). Clearing
+/// prices are stored as the 32-byte encoding of a [`U128x128`](penumbra_num::fixpoint::U128x128),
+/// so that they aren't rounded.
+#[derive(Debug)]
+pub struct SwapSettlementView {}
+
+impl SwapSettlementView {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl AppView for SwapSettlementView {
+    async fn init_chain(
+        &self,
+        dbtx: &mut PgTransaction,
+        _app_state: &serde_json::Value,
+    ) -> anyhow::Result<()> {
+        for statement in [
+            "
+CREATE TABLE IF NOT EXISTS swap_settlements (
+    id SERIAL PRIMARY KEY,
+    pair_hash BYTEA NOT NULL,
+    height BIGINT NOT NULL,
+    clearing_price BYTEA,
+    volume_1 NUMERIC NOT NULL,
+    volume_2 NUMERIC NOT NULL,
+    participant_count INT NOT NULL
+);
+",
+            "CREATE INDEX IF NOT EXISTS swap_settlements_pair_idx ON swap_settlements (pair_hash, height);",
+        ] {
+            sqlx::query(statement).execute(dbtx.as_mut()).await?;
+        }
+        Ok(())
+    }
+
+    fn is_relevant(&self, type_str: &str) -> bool {
+        type_str == "penumbra.core.component.dex.v1.EventSwapSettlement"
+    }
+
+    async fn index_event(
+        &self,
+        dbtx: &mut PgTransaction,
+        event: &ContextualizedEvent,
+        _src_db: &PgPool,
+    ) -> anyhow::Result<()> {
+        let pe = pb::EventSwapSettlement::from_event(event.as_ref())?;
+        let settlement = SwapSettlementEvent::try_from(pe)?;
+        sqlx::query(
+            "INSERT INTO swap_settlements
+             (pair_hash, height, clearing_price, volume_1, volume_2, participant_count)
+             VALUES ($1, $2, $3, CAST($4 AS NUMERIC), CAST($5 AS NUMERIC), $6)",
+        )
+        .bind(pair_bytes(&settlement.pair))
+        .bind(i64::try_from(settlement.block_height)?)
+        .bind(
+            settlement
+                .clearing_price
+                .map(|price| price.to_bytes().to_vec()),
+        )
+        .bind(settlement.aggregate_input_1.to_string())
+        .bind(settlement.aggregate_input_2.to_string())
+        .bind(i32::try_from(settlement.participant_count)?)
+        .execute(dbtx.as_mut())
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use penumbra_asset::asset;
+    use penumbra_dex::TradingPair;
+    use penumbra_num::fixpoint::U128x128;
+
+    use super::*;
+    use crate::testing;
+
+    #[tokio::test]
+    #[ignore = "needs a scratch database in PINDEXER_TEST_DATABASE_URL"]
+    async fn clearing_prices_are_stored_exactly() -> anyhow::Result<()> {
+        let pool = testing::database().await?;
+        let cache = asset::Cache::with_known_assets();
+        let pair = TradingPair::new(
+            cache.get_unit("gm").unwrap().id(),
+            cache.get_unit("gn").unwrap().id(),
+        );
+        // A third has no exact decimal representation.
+        let price = U128x128::ratio(1u64, 3u64)?;
+        let event = SwapSettlementEvent {
+            pair,
+            aggregate_input_1: 3u64.into(),
+            aggregate_input_2: 0u64.into(),
+            aggregate_output_1: 0u64.into(),
+            aggregate_output_2: 1u64.into(),
+            clearing_price: Some(price),
+            participant_count: 2,
+            block_height: 7,
+        };
+        let event = ContextualizedEvent {
+            event: event.to_protobuf().into_event(),
+            block_height: 7,
+            tx_hash: None,
+            local_rowid: 0,
+        };
+        testing::index(&pool, &SwapSettlementView::new(), [event]).await?;
+
+        let (stored, participant_count): (Vec<u8>, i32) =
+            sqlx::query_as("SELECT clearing_price, participant_count FROM swap_settlements")
+                .fetch_one(&pool)
+                .await?;
+        assert_eq!(U128x128::from_bytes(stored.try_into().unwrap()), price);
+        assert_eq!(participant_count, 2);
+        Ok(())
+    }
+}
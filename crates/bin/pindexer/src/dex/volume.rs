@@ -0,0 +1,450 @@
+use anyhow::{anyhow, Context as _};
+use cometindex::{async_trait, BlockEvents};
+use penumbra_asset::asset;
+use penumbra_dex::TradingPair;
+use penumbra_num::Amount;
+use penumbra_proto::{event::ProtoEvent, penumbra::core::component::dex::v1 as pb};
+use sqlx::PgPool;
+
+use crate::{AppView, ContextualizedEvent, PgTransaction};
+
+/// The block time targeted by Penumbra's consensus parameters, in seconds.
+pub const DEFAULT_BLOCK_TIME_SECS: u64 = 5;
+
+/// The lengths of the rolling windows maintained for each pair, in hours: 1h, 24h and 7d.
+pub const WINDOW_HOURS: [u32; 3] = [1, DAY_HOURS, 7 * 24];
+
+/// The window used by [`VolumeView::get_24h_volume`] and [`VolumeView::all_pairs_by_volume`].
+const DAY_HOURS: u32 = 24;
+
+/// The number of blocks spanned by a window of `window_hours`, given the expected block time.
+fn window_blocks(window_hours: u32, block_time_secs: u64) -> u64 {
+    (u64::from(window_hours) * 3600) / block_time_secs.max(1)
+}
+
+/// The greatest height whose entries have expired from a window of `window_hours`, as of `height`.
+///
+/// Entries at or below this height no longer count towards the window.
+fn expiry_height(height: u64, window_hours: u32, block_time_secs: u64) -> Option<u64> {
+    height.checked_sub(window_blocks(window_hours, block_time_secs))
+}
+
+/// The key for a trading pair in `swap_volume`: the concatenation of its two asset ids.
+pub(super) fn pair_bytes(pair: &TradingPair) -> Vec<u8> {
+    [pair.asset_1().to_bytes(), pair.asset_2().to_bytes()].concat()
+}
+
+/// Aggregates swap volume per trading pair over rolling windows of 1h, 24h and 7d.
+///
+/// Volume is measured on the swap inputs: `volume_1` is the total amount of `asset_1` swapped
+/// into the pair, and `volume_2` the total amount of `asset_2`. Each window of each pair has an
+/// entry for every block with swaps in the pair, at its `window_start_height`, which is deleted
+/// once the block falls out of the window.
+///
+/// Windows are measured back from the latest indexed block, whether or not it had swaps, so
+/// volume stops counting once its window has passed even if the pair has since been quiet.
+#[derive(Debug)]
+pub struct VolumeView {
+    block_time_secs: u64,
+}
+
+impl VolumeView {
+    /// Create a view that converts window lengths into blocks using the given block time.
+    ///
+    /// This is usually [`DEFAULT_BLOCK_TIME_SECS`].
+    pub fn new(block_time_secs: u64) -> Self {
+        Self { block_time_secs }
+    }
+
+    /// Delete the entries of `pair` that have fallen out of each of its windows as of `height`.
+    ///
+    /// Pairs are only cleaned up when they're swapped in, so a quiet pair keeps up to 7d of
+    /// entries, which the queries skip once they've expired.
+    pub async fn compute_rolling_window(
+        dbtx: &mut PgTransaction<'_>,
+        height: u64,
+        pair: &TradingPair,
+        block_time_secs: u64,
+    ) -> anyhow::Result<()> {
+        for window_hours in WINDOW_HOURS {
+            let Some(expired) = expiry_height(height, window_hours, block_time_secs) else {
+                continue;
+            };
+            sqlx::query(
+                "DELETE FROM swap_volume
+                 WHERE pair_hash = $1 AND window_hours = $2 AND window_start_height <= $3",
+            )
+            .bind(pair_bytes(pair))
+            .bind(i32::try_from(window_hours)?)
+            .bind(i64::try_from(expired)?)
+            .execute(dbtx.as_mut())
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// The lowest height still inside a window of `window_hours`, relative to the latest indexed
+    /// block.
+    async fn window_start(&self, pool: &PgPool, window_hours: u32) -> anyhow::Result<i64> {
+        let latest: Option<i64> =
+            sqlx::query_scalar("SELECT height FROM swap_volume_height WHERE id = 0")
+                .fetch_optional(pool)
+                .await?;
+        let expired = latest
+            .map(u64::try_from)
+            .transpose()?
+            .and_then(|h| expiry_height(h, window_hours, self.block_time_secs));
+        match expired {
+            Some(expired) => Ok(i64::try_from(expired)? + 1),
+            None => Ok(0),
+        }
+    }
+
+    /// Get the total volume of each asset swapped in `pair` over the last `window_hours`, which
+    /// must be one of [`WINDOW_HOURS`].
+    pub async fn get_volume(
+        &self,
+        pool: &PgPool,
+        pair: &TradingPair,
+        window_hours: u32,
+    ) -> anyhow::Result<(u128, u128)> {
+        anyhow::ensure!(
+            WINDOW_HOURS.contains(&window_hours),
+            "no {window_hours}h window is maintained"
+        );
+        let start = self.window_start(pool, window_hours).await?;
+        let (volume_1, volume_2): (String, String) = sqlx::query_as(
+            "SELECT COALESCE(SUM(volume_1), 0)::TEXT, COALESCE(SUM(volume_2), 0)::TEXT
+             FROM swap_volume
+             WHERE pair_hash = $1 AND window_hours = $2 AND window_start_height >= $3",
+        )
+        .bind(pair_bytes(pair))
+        .bind(i32::try_from(window_hours)?)
+        .bind(start)
+        .fetch_one(pool)
+        .await?;
+        Ok((volume_1.parse()?, volume_2.parse()?))
+    }
+
+    /// Get the total volume of each asset swapped in `pair` over the last 24 hours.
+    pub async fn get_24h_volume(
+        &self,
+        pool: &PgPool,
+        pair: &TradingPair,
+    ) -> anyhow::Result<(u128, u128)> {
+        self.get_volume(pool, pair, DAY_HOURS).await
+    }
+
+    /// List every pair with `asset` on one side that traded in the last 24 hours, with the
+    /// volume of `asset` swapped into it, from highest to lowest.
+    ///
+    /// Volumes of different assets can't be compared, so pairs are only ranked by the one asset
+    /// they have in common. Ties are broken by pair, so that the ranking is deterministic.
+    pub async fn all_pairs_by_volume(
+        &self,
+        pool: &PgPool,
+        asset: asset::Id,
+    ) -> anyhow::Result<Vec<(TradingPair, u128)>> {
+        let start = self.window_start(pool, DAY_HOURS).await?;
+        let rows: Vec<(Vec<u8>, String)> = sqlx::query_as(
+            "SELECT pair_hash, SUM(volume)::TEXT
+             FROM (
+                 SELECT pair_hash, volume_1 AS volume
+                 FROM swap_volume
+                 WHERE window_hours = $3 AND window_start_height >= $1
+                     AND substring(pair_hash FROM 1 FOR 32) = $2
+                 UNION ALL
+                 SELECT pair_hash, volume_2 AS volume
+                 FROM swap_volume
+                 WHERE window_hours = $3 AND window_start_height >= $1
+                     AND substring(pair_hash FROM 33 FOR 32) = $2
+             ) AS asset_volume
+             GROUP BY pair_hash
+             ORDER BY SUM(volume) DESC, pair_hash ASC",
+        )
+        .bind(start)
+        .bind(asset.to_bytes().to_vec())
+        .bind(i32::try_from(DAY_HOURS)?)
+        .fetch_all(pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|(pair, volume)| {
+                let pair: [u8; 64] = pair
+                    .try_into()
+                    .map_err(|_| anyhow!("stored trading pair has the wrong length"))?;
+                Ok((TradingPair::try_from(pair)?, volume.parse()?))
+            })
+            .collect()
+    }
+
+    async fn index_swap(
+        &self,
+        dbtx: &mut PgTransaction<'_>,
+        height: u64,
+        pair: TradingPair,
+        delta_1_i: Amount,
+        delta_2_i: Amount,
+    ) -> anyhow::Result<()> {
+        for window_hours in WINDOW_HOURS {
+            sqlx::query(
+                "INSERT INTO swap_volume
+                 VALUES ($1, $2, $3, CAST($4 AS NUMERIC), CAST($5 AS NUMERIC), 1)
+                 ON CONFLICT (pair_hash, window_hours, window_start_height) DO UPDATE
+                 SET volume_1 = swap_volume.volume_1 + EXCLUDED.volume_1,
+                     volume_2 = swap_volume.volume_2 + EXCLUDED.volume_2,
+                     trade_count = swap_volume.trade_count + 1",
+            )
+            .bind(pair_bytes(&pair))
+            .bind(i32::try_from(window_hours)?)
+            .bind(i64::try_from(height)?)
+            .bind(delta_1_i.to_string())
+            .bind(delta_2_i.to_string())
+            .execute(dbtx.as_mut())
+            .await?;
+        }
+        Self::compute_rolling_window(dbtx, height, &pair, self.block_time_secs).await
+    }
+}
+
+#[async_trait]
+impl AppView for VolumeView {
+    async fn init_chain(
+        &self,
+        dbtx: &mut PgTransaction,
+        _app_state: &serde_json::Value,
+    ) -> anyhow::Result<()> {
+        for statement in [
+            "
+CREATE TABLE IF NOT EXISTS swap_volume (
+    pair_hash BYTEA NOT NULL,
+    window_hours INT NOT NULL,
+    window_start_height BIGINT NOT NULL,
+    volume_1 NUMERIC NOT NULL,
+    volume_2 NUMERIC NOT NULL,
+    trade_count INT NOT NULL,
+    PRIMARY KEY (pair_hash, window_hours, window_start_height)
+);
+",
+            "CREATE INDEX IF NOT EXISTS swap_volume_window_idx ON swap_volume (window_hours, window_start_height);",
+            "
+CREATE TABLE IF NOT EXISTS swap_volume_height (
+    id INT PRIMARY KEY,
+    height BIGINT NOT NULL
+);
+",
+        ] {
+            sqlx::query(statement).execute(dbtx.as_mut()).await?;
+        }
+        Ok(())
+    }
+
+    fn is_relevant(&self, type_str: &str) -> bool {
+        type_str == "penumbra.core.component.dex.v1.EventSwap"
+    }
+
+    async fn index_event(
+        &self,
+        dbtx: &mut PgTransaction,
+        event: &ContextualizedEvent,
+        _src_db: &PgPool,
+    ) -> anyhow::Result<()> {
+        let pe = pb::EventSwap::from_event(event.as_ref())?;
+        let pair = pe
+            .trading_pair
+            .ok_or_else(|| anyhow!("event missing trading_pair"))?
+            .try_into()?;
+        let delta_1_i = pe
+            .delta_1_i
+            .ok_or_else(|| anyhow!("event missing delta_1_i"))?
+            .try_into()
+            .context("invalid delta_1_i")?;
+        let delta_2_i = pe
+            .delta_2_i
+            .ok_or_else(|| anyhow!("event missing delta_2_i"))?
+            .try_into()
+            .context("invalid delta_2_i")?;
+        self.index_swap(dbtx, event.block_height, pair, delta_1_i, delta_2_i)
+            .await
+    }
+
+    async fn post_block(
+        &self,
+        dbtx: &mut PgTransaction,
+        height: u64,
+        _events: BlockEvents<'_>,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            "INSERT INTO swap_volume_height (id, height) VALUES (0, $1)
+             ON CONFLICT (id) DO UPDATE SET height = EXCLUDED.height",
+        )
+        .bind(i64::try_from(height)?)
+        .execute(dbtx.as_mut())
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn volume_counts_until_it_crosses_the_window_boundary() {
+        // With 5 second blocks, an hour is 720 blocks.
+        let block_time_secs = 5;
+        assert_eq!(window_blocks(1, block_time_secs), 720);
+
+        let swap_height = 1_000;
+        let last_included = swap_height + 719;
+        let first_excluded = swap_height + 720;
+
+        assert!(expiry_height(last_included, 1, block_time_secs).unwrap() < swap_height);
+        assert!(expiry_height(first_excluded, 1, block_time_secs).unwrap() >= swap_height);
+        // The same swap is still inside the longer windows.
+        assert!(expiry_height(first_excluded, 24, block_time_secs).is_none());
+        assert!(expiry_height(first_excluded, 7 * 24, block_time_secs).is_none());
+    }
+
+    #[test]
+    fn nothing_expires_before_a_full_window_has_elapsed() {
+        assert_eq!(expiry_height(0, 1, 5), None);
+        assert_eq!(expiry_height(719, 1, 5), None);
+        assert_eq!(expiry_height(720, 1, 5), Some(0));
+        assert_eq!(expiry_height(20_000, 24, 5), Some(20_000 - 17_280));
+    }
+
+    /// With hour-long blocks, a window of `n` hours is `n` blocks.
+    const HOURLY: u64 = 3600;
+
+    fn pairs() -> (asset::Id, TradingPair, TradingPair) {
+        let cache = asset::Cache::with_known_assets();
+        let penumbra = cache.get_unit("upenumbra").unwrap().id();
+        (
+            penumbra,
+            TradingPair::new(penumbra, cache.get_unit("gm").unwrap().id()),
+            TradingPair::new(penumbra, cache.get_unit("gn").unwrap().id()),
+        )
+    }
+
+    /// A swap of `delta_1_i` of the first asset of `pair` and `delta_2_i` of the second.
+    fn swap(pair: TradingPair, delta_1_i: u64, delta_2_i: u64, height: u64) -> ContextualizedEvent {
+        ContextualizedEvent {
+            event: pb::EventSwap {
+                trading_pair: Some(pair.into()),
+                delta_1_i: Some(Amount::from(delta_1_i).into()),
+                delta_2_i: Some(Amount::from(delta_2_i).into()),
+                ..Default::default()
+            }
+            .into_event(),
+            block_height: height,
+            tx_hash: None,
+            local_rowid: 0,
+        }
+    }
+
+    /// The amount of `asset` swapped into `pair`, in the order `pair` lists its assets.
+    fn deltas(asset: asset::Id, pair: TradingPair, amount: u64) -> (u64, u64) {
+        if pair.asset_1() == asset {
+            (amount, 0)
+        } else {
+            (0, amount)
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a scratch database in PINDEXER_TEST_DATABASE_URL"]
+    async fn volume_leaves_each_window_as_it_expires() -> anyhow::Result<()> {
+        let pool = testing::database().await?;
+        let (_, pair, _) = pairs();
+        let view = VolumeView::new(HOURLY);
+        testing::index(
+            &pool,
+            &view,
+            [
+                swap(pair, 1, 0, 1),
+                swap(pair, 10, 0, 100),
+                swap(pair, 100, 0, 190),
+                swap(pair, 1_000, 0, 200),
+                swap(pair, 0, 7, 200),
+            ],
+        )
+        .await?;
+
+        assert_eq!(view.get_volume(&pool, &pair, 1).await?, (1_000, 7));
+        assert_eq!(view.get_24h_volume(&pool, &pair).await?, (1_100, 7));
+        assert_eq!(view.get_volume(&pool, &pair, 7 * 24).await?, (1_110, 7));
+
+        // Both swaps at the latest height share an entry in each window, and each window has
+        // deleted the entries that left it.
+        let rows: Vec<(i32, i64)> = sqlx::query_as(
+            "SELECT window_hours, COUNT(*) FROM swap_volume
+             GROUP BY window_hours ORDER BY window_hours",
+        )
+        .fetch_all(&pool)
+        .await?;
+        assert_eq!(rows, vec![(1, 1), (24, 2), (168, 3)]);
+
+        assert!(view.get_volume(&pool, &pair, 2).await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a scratch database in PINDEXER_TEST_DATABASE_URL"]
+    async fn windows_end_at_the_latest_block_rather_than_the_latest_swap() -> anyhow::Result<()> {
+        let pool = testing::database().await?;
+        let (_, pair, _) = pairs();
+        let view = VolumeView::new(HOURLY);
+        testing::index(
+            &pool,
+            &view,
+            [swap(pair, 10, 0, 100), swap(pair, 5, 0, 200)],
+        )
+        .await?;
+        assert_eq!(view.get_24h_volume(&pool, &pair).await?, (5, 0));
+
+        // A day and more passes without any swaps.
+        let mut dbtx = pool.begin().await?;
+        view.post_block(&mut dbtx, 230, BlockEvents::new(&[]))
+            .await?;
+        dbtx.commit().await?;
+
+        assert_eq!(view.get_volume(&pool, &pair, 1).await?, (0, 0));
+        assert_eq!(view.get_24h_volume(&pool, &pair).await?, (0, 0));
+        assert_eq!(view.get_volume(&pool, &pair, 7 * 24).await?, (15, 0));
+        assert!(view
+            .all_pairs_by_volume(&pool, pair.asset_1())
+            .await?
+            .is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a scratch database in PINDEXER_TEST_DATABASE_URL"]
+    async fn pairs_are_ranked_by_the_volume_of_one_asset() -> anyhow::Result<()> {
+        let pool = testing::database().await?;
+        let (penumbra, gm_pair, gn_pair) = pairs();
+        let (gm_1, gm_2) = deltas(penumbra, gm_pair, 50);
+        let (gn_1, gn_2) = deltas(penumbra, gn_pair, 80);
+        let view = VolumeView::new(HOURLY);
+        // Far more of the other asset is swapped into the gm pair, which mustn't count.
+        let (other_1, other_2) = (gm_2 * 1_000_000, gm_1 * 1_000_000);
+        testing::index(
+            &pool,
+            &view,
+            [
+                swap(gm_pair, gm_1, gm_2, 10),
+                swap(gm_pair, other_1, other_2, 10),
+                swap(gn_pair, gn_1, gn_2, 10),
+            ],
+        )
+        .await?;
+
+        assert_eq!(
+            view.all_pairs_by_volume(&pool, penumbra).await?,
+            vec![(gn_pair, 80), (gm_pair, 50)]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,260 @@
+use anyhow::anyhow;
+use cometindex::{async_trait, AppView, ContextualizedEvent, PgTransaction};
+use penumbra_fee::Fee;
+use penumbra_num::Amount;
+use penumbra_proto::{event::ProtoEvent, penumbra::core::component::fee::v1 as pb};
+use sqlx::PgPool;
+
+/// The reward of the proposer of a single block: the tips included in it.
+///
+/// Tips are burned along with the rest of the fees, so a reward measures how much fee-paying
+/// activity a proposer included, not what it earned.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockReward {
+    pub height: u64,
+    /// The consensus address of the proposer.
+    pub proposer: Vec<u8>,
+    pub reward: Amount,
+}
+
+impl TryFrom<&ContextualizedEvent> for BlockReward {
+    type Error = anyhow::Error;
+
+    fn try_from(event: &ContextualizedEvent) -> Result<Self, Self::Error> {
+        let pe = pb::EventBlockReward::from_event(event.as_ref())?;
+        let reward: Fee = pe
+            .reward
+            .ok_or_else(|| anyhow!("EventBlockReward missing reward"))?
+            .try_into()?;
+        Ok(Self {
+            height: event.block_height,
+            proposer: pe.proposer,
+            reward: reward.amount(),
+        })
+    }
+}
+
+/// The average reward over `block_count` blocks, rounded down.
+fn average_per_block(total: u128, block_count: u64) -> u64 {
+    match total.checked_div(u128::from(block_count)) {
+        Some(average) => u64::try_from(average).unwrap_or(u64::MAX),
+        None => 0,
+    }
+}
+
+/// Tracks the reward, that is the tips included, of the proposer of each block.
+#[derive(Debug)]
+pub struct ProposerRewardView {}
+
+impl ProposerRewardView {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// The total reward of `proposer` over every indexed block.
+    pub async fn total_rewards_for(pool: &PgPool, proposer: &[u8]) -> anyhow::Result<u128> {
+        let total: String = sqlx::query_scalar(
+            "SELECT COALESCE(SUM(reward), 0)::TEXT FROM proposer_rewards WHERE proposer = $1",
+        )
+        .bind(proposer)
+        .fetch_one(pool)
+        .await?;
+        Ok(total.parse()?)
+    }
+
+    /// The average reward of each indexed block, rounded down.
+    pub async fn average_reward_per_block(pool: &PgPool) -> anyhow::Result<u64> {
+        let (total, block_count): (String, i64) =
+            sqlx::query_as("SELECT COALESCE(SUM(reward), 0)::TEXT, COUNT(*) FROM proposer_rewards")
+                .fetch_one(pool)
+                .await?;
+        Ok(average_per_block(total.parse()?, block_count.try_into()?))
+    }
+
+    /// The `limit` proposers with the highest total reward, from highest to lowest.
+    ///
+    /// Ties are broken by proposer address, so that the ranking is deterministic.
+    pub async fn top_proposers(
+        pool: &PgPool,
+        limit: usize,
+    ) -> anyhow::Result<Vec<(Vec<u8>, u128)>> {
+        let rows: Vec<(Vec<u8>, String)> = sqlx::query_as(
+            "
+SELECT proposer, SUM(reward)::TEXT
+FROM proposer_rewards
+GROUP BY proposer
+ORDER BY SUM(reward) DESC, proposer ASC
+LIMIT $1
+",
+        )
+        .bind(i64::try_from(limit)?)
+        .fetch_all(pool)
+        .await?;
+        rows.into_iter()
+            .map(|(proposer, total)| Ok((proposer, total.parse()?)))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl AppView for ProposerRewardView {
+    async fn init_chain(
+        &self,
+        dbtx: &mut PgTransaction,
+        _app_state: &serde_json::Value,
+    ) -> anyhow::Result<()> {
+        for statement in [
+            "
+CREATE TABLE IF NOT EXISTS proposer_rewards (
+    height BIGINT PRIMARY KEY,
+    proposer BYTEA NOT NULL,
+    reward NUMERIC NOT NULL
+);
+",
+            "CREATE INDEX IF NOT EXISTS proposer_rewards_proposer_idx ON proposer_rewards (proposer);",
+        ] {
+            sqlx::query(statement).execute(dbtx.as_mut()).await?;
+        }
+        Ok(())
+    }
+
+    fn is_relevant(&self, type_str: &str) -> bool {
+        type_str == "penumbra.core.component.fee.v1.EventBlockReward"
+    }
+
+    async fn index_event(
+        &self,
+        dbtx: &mut PgTransaction,
+        event: &ContextualizedEvent,
+        _src_db: &PgPool,
+    ) -> anyhow::Result<()> {
+        let reward = BlockReward::try_from(event)?;
+        sqlx::query("INSERT INTO proposer_rewards VALUES ($1, $2, CAST($3 AS NUMERIC))")
+            .bind(i64::try_from(reward.height)?)
+            .bind(reward.proposer)
+            .bind(reward.reward.to_string())
+            .execute(dbtx.as_mut())
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing;
+
+    fn contextualize(proposer: u8, reward: u64, block_height: u64) -> ContextualizedEvent {
+        ContextualizedEvent {
+            event: pb::EventBlockReward {
+                proposer: vec![proposer; 20],
+                reward: Some(Fee::from_staking_token_amount(reward.into()).into()),
+            }
+            .into_event(),
+            block_height,
+            tx_hash: None,
+            local_rowid: 0,
+        }
+    }
+
+    /// Ten blocks from three proposers, including one block with no reward at all.
+    fn blocks() -> Vec<ContextualizedEvent> {
+        [
+            (1, 100),
+            (2, 50),
+            (1, 25),
+            (3, 0),
+            (2, 300),
+            (1, 75),
+            (3, 10),
+            (2, 5),
+            (1, 200),
+            (3, 40),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(height, (proposer, reward))| contextualize(proposer, reward, height as u64 + 1))
+        .collect()
+    }
+
+    #[test]
+    fn events_are_parsed() {
+        let reward = BlockReward::try_from(&blocks()[4]).unwrap();
+        assert_eq!(reward.height, 5);
+        assert_eq!(reward.proposer, vec![2; 20]);
+        assert_eq!(reward.reward, 300u64.into());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a scratch database in PINDEXER_TEST_DATABASE_URL"]
+    async fn rewards_are_totalled_averaged_and_ranked() -> anyhow::Result<()> {
+        let pool = testing::database().await?;
+        testing::index(&pool, &ProposerRewardView::new(), blocks()).await?;
+
+        assert_eq!(
+            ProposerRewardView::total_rewards_for(&pool, &[1; 20]).await?,
+            400
+        );
+        assert_eq!(
+            ProposerRewardView::total_rewards_for(&pool, &[2; 20]).await?,
+            355
+        );
+        assert_eq!(
+            ProposerRewardView::total_rewards_for(&pool, &[3; 20]).await?,
+            50
+        );
+        assert_eq!(
+            ProposerRewardView::total_rewards_for(&pool, &[4; 20]).await?,
+            0
+        );
+        assert_eq!(
+            ProposerRewardView::average_reward_per_block(&pool).await?,
+            80
+        );
+        assert_eq!(
+            ProposerRewardView::top_proposers(&pool, 2).await?,
+            vec![(vec![1; 20], 400), (vec![2; 20], 355)]
+        );
+        assert_eq!(ProposerRewardView::top_proposers(&pool, 10).await?.len(), 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a scratch database in PINDEXER_TEST_DATABASE_URL"]
+    async fn ties_are_ranked_by_proposer() -> anyhow::Result<()> {
+        let pool = testing::database().await?;
+        let blocks = [(2, 10), (1, 10), (3, 20)]
+            .into_iter()
+            .enumerate()
+            .map(|(height, (proposer, reward))| contextualize(proposer, reward, height as u64 + 1));
+        testing::index(&pool, &ProposerRewardView::new(), blocks).await?;
+
+        assert_eq!(
+            ProposerRewardView::top_proposers(&pool, 3).await?,
+            vec![(vec![3; 20], 20), (vec![1; 20], 10), (vec![2; 20], 10)]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a scratch database in PINDEXER_TEST_DATABASE_URL"]
+    async fn empty_chains_have_no_rewards() -> anyhow::Result<()> {
+        let pool = testing::database().await?;
+        testing::index(&pool, &ProposerRewardView::new(), []).await?;
+
+        assert_eq!(
+            ProposerRewardView::average_reward_per_block(&pool).await?,
+            0
+        );
+        assert!(ProposerRewardView::top_proposers(&pool, 3)
+            .await?
+            .is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn averages_saturate() {
+        assert_eq!(average_per_block(0, 0), 0);
+        assert_eq!(average_per_block(u128::MAX, 1), u64::MAX);
+    }
+}
@@ -0,0 +1,368 @@
+use anyhow::anyhow;
+use cometindex::{async_trait, AppView, ContextualizedEvent, PgTransaction};
+use sqlx::PgPool;
+
+const CREATE_CLIENT: &str = "create_client";
+const UPDATE_CLIENT: &str = "update_client";
+
+/// The latest known state of an IBC light client.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClientInfo {
+    pub client_id: String,
+    pub client_type: String,
+    /// The revision height of the latest consensus state the client trusts.
+    pub latest_height: u64,
+    /// The full latest consensus height, as `{revision_number}-{revision_height}`.
+    pub latest_consensus_height: String,
+    /// The local block height at which the client was created.
+    pub created_at: u64,
+    /// The local block height of the client's most recent update.
+    pub updated_at: u64,
+}
+
+impl ClientInfo {
+    /// Apply an update to `consensus_height` seen at local height `height`.
+    ///
+    /// Clients may be updated with headers older than their latest one, e.g. to fill in a
+    /// consensus state for a past height, so the latest height only ever moves forwards.
+    /// Heights are ordered by revision number first, so an upgrade to a new revision advances
+    /// the latest height even though its revision height starts again from a low number.
+    fn apply_update(&mut self, consensus_height: &str, height: u64) -> anyhow::Result<()> {
+        let update = super::parse_height(consensus_height)?;
+        let latest = super::parse_height(&self.latest_consensus_height)?;
+        if update > latest {
+            self.latest_height = update.1;
+            self.latest_consensus_height = consensus_height.to_string();
+        }
+        self.updated_at = height;
+        Ok(())
+    }
+}
+
+/// The attributes of a client lifecycle event.
+#[derive(Debug)]
+struct ClientEvent {
+    client_id: String,
+    client_type: String,
+    consensus_height: String,
+}
+
+impl TryFrom<&ContextualizedEvent> for ClientEvent {
+    type Error = anyhow::Error;
+
+    fn try_from(event: &ContextualizedEvent) -> Result<Self, Self::Error> {
+        let attribute = |key: &str| {
+            event
+                .event
+                .attributes
+                .iter()
+                .find(|attr| attr.key == key)
+                .map(|attr| attr.value.clone())
+                .ok_or_else(|| anyhow!("{} event missing {key}", event.event.kind))
+        };
+        Ok(Self {
+            client_id: attribute("client_id")?,
+            client_type: attribute("client_type")?,
+            consensus_height: attribute("consensus_height")?,
+        })
+    }
+}
+
+type ClientRow = (String, String, i64, String, i64, i64);
+
+fn client_from_row(
+    (client_id, client_type, latest_height, latest_consensus_height, created_at, updated_at): ClientRow,
+) -> anyhow::Result<ClientInfo> {
+    Ok(ClientInfo {
+        client_id,
+        client_type,
+        latest_height: latest_height.try_into()?,
+        latest_consensus_height,
+        created_at: created_at.try_into()?,
+        updated_at: updated_at.try_into()?,
+    })
+}
+
+/// Tracks the latest trusted height of each IBC light client, from client creation and update
+/// events.
+#[derive(Debug)]
+pub struct IbcClientView {}
+
+impl IbcClientView {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Get the revision height of the latest consensus state trusted by `client_id`.
+    pub async fn latest_height(pool: &PgPool, client_id: &str) -> anyhow::Result<u64> {
+        let height: Option<i64> =
+            sqlx::query_scalar("SELECT latest_height FROM ibc_clients WHERE client_id = $1")
+                .bind(client_id)
+                .fetch_optional(pool)
+                .await?;
+        Ok(height
+            .ok_or_else(|| anyhow!("no ibc client {client_id}"))?
+            .try_into()?)
+    }
+
+    /// List every known client, ordered by client id.
+    pub async fn all_clients(pool: &PgPool) -> anyhow::Result<Vec<ClientInfo>> {
+        let rows: Vec<ClientRow> = sqlx::query_as(
+            "SELECT client_id, client_type, latest_height, latest_consensus_height, created_at, updated_at
+             FROM ibc_clients
+             ORDER BY client_id",
+        )
+        .fetch_all(pool)
+        .await?;
+        rows.into_iter().map(client_from_row).collect()
+    }
+
+    async fn create(
+        dbtx: &mut PgTransaction<'_>,
+        client: ClientEvent,
+        height: u64,
+    ) -> anyhow::Result<()> {
+        let (_, latest_height) = super::parse_height(&client.consensus_height)?;
+        let height = i64::try_from(height)?;
+        sqlx::query("INSERT INTO ibc_clients VALUES ($1, $2, $3, $4, $5, $5)")
+            .bind(client.client_id)
+            .bind(client.client_type)
+            .bind(i64::try_from(latest_height)?)
+            .bind(client.consensus_height)
+            .bind(height)
+            .execute(dbtx.as_mut())
+            .await?;
+        Ok(())
+    }
+
+    async fn update(
+        dbtx: &mut PgTransaction<'_>,
+        client: ClientEvent,
+        height: u64,
+    ) -> anyhow::Result<()> {
+        let row: Option<ClientRow> = sqlx::query_as(
+            "SELECT client_id, client_type, latest_height, latest_consensus_height, created_at, updated_at
+             FROM ibc_clients
+             WHERE client_id = $1",
+        )
+        .bind(&client.client_id)
+        .fetch_optional(dbtx.as_mut())
+        .await?;
+        let mut info = client_from_row(
+            row.ok_or_else(|| anyhow!("update for unknown ibc client {}", client.client_id))?,
+        )?;
+        info.apply_update(&client.consensus_height, height)?;
+
+        sqlx::query(
+            "UPDATE ibc_clients
+             SET latest_height = $2, latest_consensus_height = $3, updated_at = $4
+             WHERE client_id = $1",
+        )
+        .bind(&info.client_id)
+        .bind(i64::try_from(info.latest_height)?)
+        .bind(&info.latest_consensus_height)
+        .bind(i64::try_from(info.updated_at)?)
+        .execute(dbtx.as_mut())
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AppView for IbcClientView {
+    async fn init_chain(
+        &self,
+        dbtx: &mut PgTransaction,
+        _app_state: &serde_json::Value,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            "
+CREATE TABLE IF NOT EXISTS ibc_clients (
+    client_id VARCHAR PRIMARY KEY,
+    client_type VARCHAR NOT NULL,
+    latest_height BIGINT NOT NULL,
+    latest_consensus_height VARCHAR NOT NULL,
+    created_at BIGINT NOT NULL,
+    updated_at BIGINT NOT NULL
+);
+",
+        )
+        .execute(dbtx.as_mut())
+        .await?;
+        Ok(())
+    }
+
+    fn is_relevant(&self, type_str: &str) -> bool {
+        [CREATE_CLIENT, UPDATE_CLIENT].contains(&type_str)
+    }
+
+    #[tracing::instrument(skip_all, fields(height = event.block_height, name = event.event.kind.as_str()))]
+    async fn index_event(
+        &self,
+        dbtx: &mut PgTransaction,
+        event: &ContextualizedEvent,
+        _src_db: &PgPool,
+    ) -> anyhow::Result<()> {
+        let client = ClientEvent::try_from(event)?;
+        match event.event.kind.as_str() {
+            CREATE_CLIENT => Self::create(dbtx, client, event.block_height).await,
+            UPDATE_CLIENT => Self::update(dbtx, client, event.block_height).await,
+            other => Err(anyhow!("unexpected event kind: {other}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tendermint::abci::Event;
+
+    use super::*;
+    use crate::testing;
+
+    fn client_event(
+        kind: &str,
+        client_id: &str,
+        consensus_height: &str,
+        height: u64,
+    ) -> ContextualizedEvent {
+        ContextualizedEvent {
+            event: Event {
+                kind: kind.to_string(),
+                attributes: [
+                    ("client_id", client_id),
+                    ("client_type", "07-tendermint"),
+                    ("consensus_height", consensus_height),
+                ]
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()).into())
+                .collect(),
+            },
+            block_height: height,
+            tx_hash: None,
+            local_rowid: 0,
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a scratch database in PINDEXER_TEST_DATABASE_URL"]
+    async fn clients_track_their_latest_update() -> anyhow::Result<()> {
+        let pool = testing::database().await?;
+        let events = [
+            client_event(CREATE_CLIENT, "07-tendermint-0", "1-100", 10),
+            client_event(UPDATE_CLIENT, "07-tendermint-0", "1-150", 11),
+            client_event(UPDATE_CLIENT, "07-tendermint-0", "1-220", 12),
+            client_event(UPDATE_CLIENT, "07-tendermint-0", "1-180", 13),
+            client_event(UPDATE_CLIENT, "07-tendermint-0", "1-400", 14),
+            client_event(UPDATE_CLIENT, "07-tendermint-0", "1-401", 15),
+            client_event(CREATE_CLIENT, "07-tendermint-1", "2-7", 16),
+        ];
+        testing::index(&pool, &IbcClientView::new(), events).await?;
+
+        assert_eq!(
+            IbcClientView::latest_height(&pool, "07-tendermint-0").await?,
+            401
+        );
+        assert!(IbcClientView::latest_height(&pool, "07-tendermint-2")
+            .await
+            .is_err());
+        assert_eq!(
+            IbcClientView::all_clients(&pool).await?,
+            [
+                ClientInfo {
+                    client_id: "07-tendermint-0".to_string(),
+                    client_type: "07-tendermint".to_string(),
+                    latest_height: 401,
+                    latest_consensus_height: "1-401".to_string(),
+                    created_at: 10,
+                    updated_at: 15,
+                },
+                ClientInfo {
+                    client_id: "07-tendermint-1".to_string(),
+                    client_type: "07-tendermint".to_string(),
+                    latest_height: 7,
+                    latest_consensus_height: "2-7".to_string(),
+                    created_at: 16,
+                    updated_at: 16,
+                },
+            ]
+        );
+
+        // Updates to clients that were never created can't be indexed.
+        assert!(testing::index(
+            &pool,
+            &IbcClientView::new(),
+            [client_event(UPDATE_CLIENT, "07-tendermint-2", "1-1", 17)]
+        )
+        .await
+        .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn latest_height_advances_monotonically_over_updates() {
+        let mut info = ClientInfo {
+            client_id: "07-tendermint-0".to_string(),
+            client_type: "07-tendermint".to_string(),
+            latest_height: 100,
+            latest_consensus_height: "1-100".to_string(),
+            created_at: 10,
+            updated_at: 10,
+        };
+
+        let updates = [
+            ("1-150", 11),
+            ("1-220", 12),
+            ("1-180", 13),
+            ("1-400", 14),
+            ("1-401", 15),
+        ];
+        let mut previous = info.latest_height;
+        for (consensus_height, height) in updates {
+            info.apply_update(consensus_height, height).unwrap();
+            assert!(info.latest_height >= previous);
+            assert_eq!(info.updated_at, height);
+            previous = info.latest_height;
+        }
+
+        assert_eq!(info.latest_height, 401);
+        assert_eq!(info.latest_consensus_height, "1-401");
+        assert_eq!(info.created_at, 10);
+    }
+
+    #[test]
+    fn older_update_does_not_regress_height() {
+        let mut info = ClientInfo {
+            client_id: "07-tendermint-1".to_string(),
+            client_type: "07-tendermint".to_string(),
+            latest_height: 500,
+            latest_consensus_height: "2-500".to_string(),
+            created_at: 1,
+            updated_at: 1,
+        };
+        info.apply_update("2-300", 2).unwrap();
+        assert_eq!(info.latest_height, 500);
+        assert_eq!(info.latest_consensus_height, "2-500");
+        assert!(info.apply_update("500", 3).is_err());
+    }
+
+    #[test]
+    fn a_new_revision_advances_the_height() {
+        let mut info = ClientInfo {
+            client_id: "07-tendermint-2".to_string(),
+            client_type: "07-tendermint".to_string(),
+            latest_height: 500,
+            latest_consensus_height: "1-500".to_string(),
+            created_at: 1,
+            updated_at: 1,
+        };
+        // The counterparty upgraded, restarting revision heights from 1.
+        info.apply_update("2-10", 2).unwrap();
+        assert_eq!(info.latest_height, 10);
+        assert_eq!(info.latest_consensus_height, "2-10");
+
+        // A late header from the old revision is older, despite its greater revision height.
+        info.apply_update("1-600", 3).unwrap();
+        assert_eq!(info.latest_height, 10);
+        assert_eq!(info.latest_consensus_height, "2-10");
+    }
+}
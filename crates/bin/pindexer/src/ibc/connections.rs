@@ -0,0 +1,420 @@
+use std::{fmt, str::FromStr};
+
+use anyhow::anyhow;
+use cometindex::{async_trait, AppView, ContextualizedEvent, PgTransaction};
+use sqlx::PgPool;
+
+const CONNECTION_OPEN_INIT: &str = "connection_open_init";
+const CONNECTION_OPEN_TRY: &str = "connection_open_try";
+const CONNECTION_OPEN_ACK: &str = "connection_open_ack";
+const CONNECTION_OPEN_CONFIRM: &str = "connection_open_confirm";
+const CHANNEL_OPEN_INIT: &str = "channel_open_init";
+const CHANNEL_OPEN_TRY: &str = "channel_open_try";
+
+/// The state of one end of an IBC connection, as it moves through the handshake.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// The handshake was started on this chain, with `ConnOpenInit`.
+    Init,
+    /// The handshake was started on the counterparty, and answered here with `ConnOpenTry`.
+    TryOpen,
+    /// The handshake completed, with `ConnOpenAck` or `ConnOpenConfirm`.
+    Open,
+}
+
+impl ConnectionState {
+    /// The state a connection in state `current`, or `None` if it doesn't exist yet, moves to
+    /// on the handshake event `kind`.
+    fn after(current: Option<ConnectionState>, kind: &str) -> anyhow::Result<ConnectionState> {
+        match (current, kind) {
+            (None, CONNECTION_OPEN_INIT) => Ok(ConnectionState::Init),
+            (None, CONNECTION_OPEN_TRY) => Ok(ConnectionState::TryOpen),
+            (Some(ConnectionState::Init), CONNECTION_OPEN_ACK) => Ok(ConnectionState::Open),
+            (Some(ConnectionState::TryOpen), CONNECTION_OPEN_CONFIRM) => Ok(ConnectionState::Open),
+            (None, kind) => Err(anyhow!("{kind} for unknown connection")),
+            (Some(current), kind) => Err(anyhow!("{kind} for connection in state {current}")),
+        }
+    }
+}
+
+impl fmt::Display for ConnectionState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ConnectionState::Init => "init",
+            ConnectionState::TryOpen => "try_open",
+            ConnectionState::Open => "open",
+        })
+    }
+}
+
+impl FromStr for ConnectionState {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "init" => Ok(ConnectionState::Init),
+            "try_open" => Ok(ConnectionState::TryOpen),
+            "open" => Ok(ConnectionState::Open),
+            other => Err(anyhow!("unknown connection state {other}")),
+        }
+    }
+}
+
+/// The latest known state of an IBC connection.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConnectionInfo {
+    pub connection_id: String,
+    pub client_id: String,
+    /// The connection's id on the counterparty, which is unknown until the counterparty has
+    /// answered a `ConnOpenInit`.
+    pub counterparty_connection_id: Option<String>,
+    pub counterparty_client_id: String,
+    pub state: ConnectionState,
+    /// The local block height at which the connection was created.
+    pub created_at: u64,
+}
+
+/// The attributes of a connection handshake event.
+#[derive(Debug)]
+struct ConnectionEvent {
+    connection_id: String,
+    client_id: String,
+    counterparty_connection_id: Option<String>,
+    counterparty_client_id: String,
+}
+
+impl TryFrom<&ContextualizedEvent> for ConnectionEvent {
+    type Error = anyhow::Error;
+
+    fn try_from(event: &ContextualizedEvent) -> Result<Self, Self::Error> {
+        let attribute = |key: &str| {
+            event
+                .event
+                .attributes
+                .iter()
+                .find(|attr| attr.key == key)
+                .map(|attr| attr.value.clone())
+                .ok_or_else(|| anyhow!("{} event missing {key}", event.event.kind))
+        };
+        Ok(Self {
+            connection_id: attribute("connection_id")?,
+            client_id: attribute("client_id")?,
+            // This is empty on `ConnOpenInit`, or missing entirely.
+            counterparty_connection_id: attribute("counterparty_connection_id")
+                .ok()
+                .filter(|id| !id.is_empty()),
+            counterparty_client_id: attribute("counterparty_client_id")?,
+        })
+    }
+}
+
+/// The attributes of a channel opening event that tie the channel to its connection.
+#[derive(Debug)]
+struct ChannelEvent {
+    channel_id: String,
+    connection_id: String,
+}
+
+impl TryFrom<&ContextualizedEvent> for ChannelEvent {
+    type Error = anyhow::Error;
+
+    fn try_from(event: &ContextualizedEvent) -> Result<Self, Self::Error> {
+        let attribute = |key: &str| {
+            event
+                .event
+                .attributes
+                .iter()
+                .find(|attr| attr.key == key)
+                .map(|attr| attr.value.clone())
+                .ok_or_else(|| anyhow!("{} event missing {key}", event.event.kind))
+        };
+        Ok(Self {
+            channel_id: attribute("channel_id")?,
+            connection_id: attribute("connection_id")?,
+        })
+    }
+}
+
+type ConnectionRow = (String, String, Option<String>, String, String, i64);
+
+fn connection_from_row(
+    (
+        connection_id,
+        client_id,
+        counterparty_connection_id,
+        counterparty_client_id,
+        state,
+        created_at,
+    ): ConnectionRow,
+) -> anyhow::Result<ConnectionInfo> {
+    Ok(ConnectionInfo {
+        connection_id,
+        client_id,
+        counterparty_connection_id,
+        counterparty_client_id,
+        state: state.parse()?,
+        created_at: created_at.try_into()?,
+    })
+}
+
+/// Tracks the state of IBC connections through their handshakes, and which connection each
+/// channel was opened on, for relayers.
+#[derive(Debug)]
+pub struct IbcConnectionView {}
+
+impl IbcConnectionView {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// List every connection which has completed its handshake, ordered by connection id.
+    pub async fn open_connections(pool: &PgPool) -> anyhow::Result<Vec<ConnectionInfo>> {
+        let rows: Vec<ConnectionRow> = sqlx::query_as(
+            "SELECT connection_id, client_id, counterparty_connection_id, counterparty_client_id, state, created_at
+             FROM ibc_connections
+             WHERE state = $1
+             ORDER BY connection_id",
+        )
+        .bind(ConnectionState::Open.to_string())
+        .fetch_all(pool)
+        .await?;
+        rows.into_iter().map(connection_from_row).collect()
+    }
+
+    /// Get the connection that `channel_id` was opened on, if the channel is known.
+    pub async fn connection_for_channel(
+        pool: &PgPool,
+        channel_id: &str,
+    ) -> anyhow::Result<Option<ConnectionInfo>> {
+        let row: Option<ConnectionRow> = sqlx::query_as(
+            "SELECT c.connection_id, c.client_id, c.counterparty_connection_id, c.counterparty_client_id, c.state, c.created_at
+             FROM ibc_connection_channels ch
+             JOIN ibc_connections c ON c.connection_id = ch.connection_id
+             WHERE ch.channel_id = $1",
+        )
+        .bind(channel_id)
+        .fetch_optional(pool)
+        .await?;
+        row.map(connection_from_row).transpose()
+    }
+
+    async fn handshake(
+        dbtx: &mut PgTransaction<'_>,
+        kind: &str,
+        connection: ConnectionEvent,
+        height: u64,
+    ) -> anyhow::Result<()> {
+        let current: Option<String> =
+            sqlx::query_scalar("SELECT state FROM ibc_connections WHERE connection_id = $1")
+                .bind(&connection.connection_id)
+                .fetch_optional(dbtx.as_mut())
+                .await?;
+        let current = current.map(|state| state.parse()).transpose()?;
+        let state = ConnectionState::after(current, kind)
+            .map_err(|e| e.context(format!("connection {}", connection.connection_id)))?;
+
+        if current.is_none() {
+            sqlx::query("INSERT INTO ibc_connections VALUES ($1, $2, $3, $4, $5, $6)")
+                .bind(connection.connection_id)
+                .bind(connection.client_id)
+                .bind(connection.counterparty_connection_id)
+                .bind(connection.counterparty_client_id)
+                .bind(state.to_string())
+                .bind(i64::try_from(height)?)
+                .execute(dbtx.as_mut())
+                .await?;
+        } else {
+            sqlx::query(
+                "UPDATE ibc_connections
+                 SET state = $2,
+                     counterparty_connection_id = COALESCE($3, counterparty_connection_id)
+                 WHERE connection_id = $1",
+            )
+            .bind(connection.connection_id)
+            .bind(state.to_string())
+            .bind(connection.counterparty_connection_id)
+            .execute(dbtx.as_mut())
+            .await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AppView for IbcConnectionView {
+    async fn init_chain(
+        &self,
+        dbtx: &mut PgTransaction,
+        _app_state: &serde_json::Value,
+    ) -> anyhow::Result<()> {
+        for statement in [
+            "
+CREATE TABLE IF NOT EXISTS ibc_connections (
+    connection_id VARCHAR PRIMARY KEY,
+    client_id VARCHAR NOT NULL,
+    counterparty_connection_id VARCHAR,
+    counterparty_client_id VARCHAR NOT NULL,
+    state VARCHAR NOT NULL,
+    created_at BIGINT NOT NULL
+);
+",
+            "
+CREATE TABLE IF NOT EXISTS ibc_connection_channels (
+    channel_id VARCHAR PRIMARY KEY,
+    connection_id VARCHAR NOT NULL
+);
+",
+        ] {
+            sqlx::query(statement).execute(dbtx.as_mut()).await?;
+        }
+        Ok(())
+    }
+
+    fn is_relevant(&self, type_str: &str) -> bool {
+        [
+            CONNECTION_OPEN_INIT,
+            CONNECTION_OPEN_TRY,
+            CONNECTION_OPEN_ACK,
+            CONNECTION_OPEN_CONFIRM,
+            CHANNEL_OPEN_INIT,
+            CHANNEL_OPEN_TRY,
+        ]
+        .contains(&type_str)
+    }
+
+    #[tracing::instrument(skip_all, fields(height = event.block_height, name = event.event.kind.as_str()))]
+    async fn index_event(
+        &self,
+        dbtx: &mut PgTransaction,
+        event: &ContextualizedEvent,
+        _src_db: &PgPool,
+    ) -> anyhow::Result<()> {
+        match event.event.kind.as_str() {
+            CHANNEL_OPEN_INIT | CHANNEL_OPEN_TRY => {
+                let channel = ChannelEvent::try_from(event)?;
+                sqlx::query("INSERT INTO ibc_connection_channels VALUES ($1, $2)")
+                    .bind(channel.channel_id)
+                    .bind(channel.connection_id)
+                    .execute(dbtx.as_mut())
+                    .await?;
+                Ok(())
+            }
+            kind => {
+                let connection = ConnectionEvent::try_from(event)?;
+                Self::handshake(dbtx, kind, connection, event.block_height).await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tendermint::abci::Event;
+
+    use super::*;
+
+    fn event(kind: &str, attributes: &[(&str, &str)]) -> ContextualizedEvent {
+        ContextualizedEvent {
+            event: Event {
+                kind: kind.to_string(),
+                attributes: attributes
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), value.to_string()).into())
+                    .collect(),
+            },
+            block_height: 1,
+            tx_hash: None,
+            local_rowid: 1,
+        }
+    }
+
+    #[test]
+    fn init_then_ack_opens_the_connection() {
+        let init = ConnectionState::after(None, CONNECTION_OPEN_INIT).unwrap();
+        assert_eq!(init, ConnectionState::Init);
+        let ack = ConnectionState::after(Some(init), CONNECTION_OPEN_ACK).unwrap();
+        assert_eq!(ack, ConnectionState::Open);
+    }
+
+    #[test]
+    fn try_then_confirm_opens_the_connection() {
+        let try_open = ConnectionState::after(None, CONNECTION_OPEN_TRY).unwrap();
+        assert_eq!(try_open, ConnectionState::TryOpen);
+        let confirm = ConnectionState::after(Some(try_open), CONNECTION_OPEN_CONFIRM).unwrap();
+        assert_eq!(confirm, ConnectionState::Open);
+    }
+
+    #[test]
+    fn out_of_order_handshake_steps_are_rejected() {
+        assert!(ConnectionState::after(None, CONNECTION_OPEN_ACK).is_err());
+        assert!(ConnectionState::after(None, CONNECTION_OPEN_CONFIRM).is_err());
+        assert!(
+            ConnectionState::after(Some(ConnectionState::Init), CONNECTION_OPEN_CONFIRM).is_err()
+        );
+        assert!(
+            ConnectionState::after(Some(ConnectionState::TryOpen), CONNECTION_OPEN_ACK).is_err()
+        );
+        assert!(ConnectionState::after(Some(ConnectionState::Open), CONNECTION_OPEN_INIT).is_err());
+        assert!(ConnectionState::after(Some(ConnectionState::Open), CONNECTION_OPEN_ACK).is_err());
+    }
+
+    #[test]
+    fn connection_states_roundtrip_through_strings() {
+        for state in [
+            ConnectionState::Init,
+            ConnectionState::TryOpen,
+            ConnectionState::Open,
+        ] {
+            assert_eq!(state.to_string().parse::<ConnectionState>().unwrap(), state);
+        }
+    }
+
+    #[test]
+    fn handshake_events_are_parsed() {
+        let init = ConnectionEvent::try_from(&event(
+            CONNECTION_OPEN_INIT,
+            &[
+                ("connection_id", "connection-0"),
+                ("client_id", "07-tendermint-0"),
+                ("counterparty_connection_id", ""),
+                ("counterparty_client_id", "07-tendermint-5"),
+            ],
+        ))
+        .unwrap();
+        assert_eq!(init.connection_id, "connection-0");
+        assert_eq!(init.counterparty_connection_id, None);
+
+        let ack = ConnectionEvent::try_from(&event(
+            CONNECTION_OPEN_ACK,
+            &[
+                ("connection_id", "connection-0"),
+                ("client_id", "07-tendermint-0"),
+                ("counterparty_connection_id", "connection-9"),
+                ("counterparty_client_id", "07-tendermint-5"),
+            ],
+        ))
+        .unwrap();
+        assert_eq!(
+            ack.counterparty_connection_id,
+            Some("connection-9".to_string())
+        );
+
+        assert!(ConnectionEvent::try_from(&event(
+            CONNECTION_OPEN_TRY,
+            &[("connection_id", "connection-1")]
+        ))
+        .is_err());
+
+        let channel = ChannelEvent::try_from(&event(
+            CHANNEL_OPEN_INIT,
+            &[
+                ("port_id", "transfer"),
+                ("channel_id", "channel-3"),
+                ("connection_id", "connection-0"),
+            ],
+        ))
+        .unwrap();
+        assert_eq!(channel.channel_id, "channel-3");
+        assert_eq!(channel.connection_id, "connection-0");
+    }
+}
@@ -0,0 +1,548 @@
+use std::{fmt, str::FromStr, sync::Mutex};
+
+use anyhow::{anyhow, Context as _};
+use cometindex::{async_trait, AppView, ContextualizedEvent, PgTransaction};
+use sqlx::PgPool;
+
+const CLIENT_MISBEHAVIOUR: &str = "client_misbehaviour";
+const UPDATE_CLIENT: &str = "update_client";
+
+/// The kind of misbehavior detected for a client.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MisbehaviorType {
+    /// Evidence of misbehavior was submitted with `MsgSubmitMisbehaviour`, and the client was
+    /// frozen.
+    Submitted,
+    /// The client was updated with two different headers for the same consensus height, which
+    /// means the counterparty chain forked, or the client is under a light client attack.
+    ConflictingUpdate,
+}
+
+impl MisbehaviorType {
+    /// The misbehavior in updating a client with `header`, when `seen` is the header it was
+    /// first updated with at the same consensus height, if any.
+    ///
+    /// Relayers racing to submit the same update is expected, so only a different header counts.
+    fn for_update(seen: Option<&[u8]>, header: &[u8]) -> Option<MisbehaviorType> {
+        seen.filter(|seen| *seen != header)
+            .map(|_| MisbehaviorType::ConflictingUpdate)
+    }
+}
+
+impl fmt::Display for MisbehaviorType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            MisbehaviorType::Submitted => "submitted",
+            MisbehaviorType::ConflictingUpdate => "conflicting_update",
+        })
+    }
+}
+
+impl FromStr for MisbehaviorType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "submitted" => Ok(MisbehaviorType::Submitted),
+            "conflicting_update" => Ok(MisbehaviorType::ConflictingUpdate),
+            other => Err(anyhow!("unknown misbehavior type {other}")),
+        }
+    }
+}
+
+/// A detected instance of client misbehavior.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MisbehaviorRecord {
+    pub id: i32,
+    pub client_id: String,
+    /// The local block height at which the misbehavior was detected.
+    pub height: u64,
+    pub misbehavior_type: MisbehaviorType,
+    /// For a conflicting update, the encoded header that conflicted with the first one seen.
+    ///
+    /// Misbehavior events don't carry the submitted evidence, so this is `None` for
+    /// [`MisbehaviorType::Submitted`].
+    pub evidence: Option<Vec<u8>>,
+}
+
+/// The attributes of an event relevant to client misbehavior.
+#[derive(Debug)]
+enum MisbehaviorEvent {
+    Submitted {
+        client_id: String,
+    },
+    Update {
+        client_id: String,
+        consensus_height: String,
+        header: Vec<u8>,
+    },
+}
+
+impl TryFrom<&ContextualizedEvent> for MisbehaviorEvent {
+    type Error = anyhow::Error;
+
+    fn try_from(event: &ContextualizedEvent) -> Result<Self, Self::Error> {
+        let attribute = |key: &str| {
+            event
+                .event
+                .attributes
+                .iter()
+                .find(|attr| attr.key == key)
+                .map(|attr| attr.value.clone())
+                .ok_or_else(|| anyhow!("{} event missing {key}", event.event.kind))
+        };
+        match event.event.kind.as_str() {
+            CLIENT_MISBEHAVIOUR => Ok(Self::Submitted {
+                client_id: attribute("client_id")?,
+            }),
+            UPDATE_CLIENT => Ok(Self::Update {
+                client_id: attribute("client_id")?,
+                consensus_height: attribute("consensus_height")?,
+                header: hex::decode(attribute("header")?)
+                    .context("update_client header is not hex")?,
+            }),
+            other => Err(anyhow!("unexpected event kind: {other}")),
+        }
+    }
+}
+
+type MisbehaviorRow = (i32, String, i64, String, Option<Vec<u8>>);
+
+fn misbehavior_from_row(
+    (id, client_id, height, misbehavior_type, evidence): MisbehaviorRow,
+) -> anyhow::Result<MisbehaviorRecord> {
+    Ok(MisbehaviorRecord {
+        id,
+        client_id,
+        height: height.try_into()?,
+        misbehavior_type: misbehavior_type.parse()?,
+        evidence,
+    })
+}
+
+type MisbehaviorHook = Box<dyn Fn(&MisbehaviorRecord) + Send>;
+
+/// Records misbehavior of IBC light clients: evidence submitted on chain, and conflicting client
+/// updates, which could indicate a light client attack.
+///
+/// To detect conflicting updates, this keeps the first header each client was updated with at
+/// each consensus height.
+pub struct IbcMisbehaviorView {
+    hooks: Mutex<Vec<MisbehaviorHook>>,
+}
+
+impl IbcMisbehaviorView {
+    pub fn new() -> Self {
+        Self {
+            hooks: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Call `hook` with each misbehavior record as it's indexed, e.g. to alert an operator.
+    ///
+    /// Hooks run before the block's transaction is committed, so a record may be reported again
+    /// if its block has to be indexed again.
+    pub fn on_misbehavior(self, hook: impl Fn(&MisbehaviorRecord) + Send + 'static) -> Self {
+        self.hooks
+            .lock()
+            .expect("no misbehavior hook panicked")
+            .push(Box::new(hook));
+        self
+    }
+
+    /// List the misbehavior detected at or after local height `since_height`, in the order it
+    /// was detected.
+    pub async fn detected_since(
+        pool: &PgPool,
+        since_height: u64,
+    ) -> anyhow::Result<Vec<MisbehaviorRecord>> {
+        let rows: Vec<MisbehaviorRow> = sqlx::query_as(
+            "SELECT id, client_id, height, misbehavior_type, evidence_bytes
+             FROM ibc_misbehavior
+             WHERE height >= $1
+             ORDER BY id",
+        )
+        .bind(i64::try_from(since_height)?)
+        .fetch_all(pool)
+        .await?;
+        rows.into_iter().map(misbehavior_from_row).collect()
+    }
+
+    /// Whether any misbehavior has been detected for `client_id`.
+    pub async fn has_misbehavior_for(pool: &PgPool, client_id: &str) -> anyhow::Result<bool> {
+        let exists = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM ibc_misbehavior WHERE client_id = $1)",
+        )
+        .bind(client_id)
+        .fetch_one(pool)
+        .await?;
+        Ok(exists)
+    }
+
+    fn alert(&self, record: &MisbehaviorRecord) {
+        tracing::warn!(
+            client_id = record.client_id,
+            height = record.height,
+            misbehavior_type = %record.misbehavior_type,
+            "detected ibc client misbehavior"
+        );
+        for hook in self
+            .hooks
+            .lock()
+            .expect("no misbehavior hook panicked")
+            .iter()
+        {
+            hook(record);
+        }
+    }
+
+    async fn record(
+        &self,
+        dbtx: &mut PgTransaction<'_>,
+        client_id: String,
+        height: u64,
+        misbehavior_type: MisbehaviorType,
+        evidence: Option<Vec<u8>>,
+    ) -> anyhow::Result<()> {
+        let id: i32 = sqlx::query_scalar(
+            "INSERT INTO ibc_misbehavior VALUES (DEFAULT, $1, $2, $3, $4) RETURNING id",
+        )
+        .bind(&client_id)
+        .bind(i64::try_from(height)?)
+        .bind(misbehavior_type.to_string())
+        .bind(&evidence)
+        .fetch_one(dbtx.as_mut())
+        .await?;
+        self.alert(&MisbehaviorRecord {
+            id,
+            client_id,
+            height,
+            misbehavior_type,
+            evidence,
+        });
+        Ok(())
+    }
+
+    async fn update(
+        &self,
+        dbtx: &mut PgTransaction<'_>,
+        client_id: String,
+        consensus_height: String,
+        header: Vec<u8>,
+        height: u64,
+    ) -> anyhow::Result<()> {
+        let seen: Option<Vec<u8>> = sqlx::query_scalar(
+            "SELECT header FROM ibc_misbehavior_headers
+             WHERE client_id = $1 AND consensus_height = $2",
+        )
+        .bind(&client_id)
+        .bind(&consensus_height)
+        .fetch_optional(dbtx.as_mut())
+        .await?;
+
+        if let Some(misbehavior_type) = MisbehaviorType::for_update(seen.as_deref(), &header) {
+            return self
+                .record(dbtx, client_id, height, misbehavior_type, Some(header))
+                .await;
+        }
+        if seen.is_none() {
+            sqlx::query("INSERT INTO ibc_misbehavior_headers VALUES ($1, $2, $3)")
+                .bind(client_id)
+                .bind(consensus_height)
+                .bind(header)
+                .execute(dbtx.as_mut())
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for IbcMisbehaviorView {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IbcMisbehaviorView").finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl AppView for IbcMisbehaviorView {
+    async fn init_chain(
+        &self,
+        dbtx: &mut PgTransaction,
+        _app_state: &serde_json::Value,
+    ) -> anyhow::Result<()> {
+        for statement in [
+            "
+CREATE TABLE IF NOT EXISTS ibc_misbehavior (
+    id SERIAL PRIMARY KEY,
+    client_id VARCHAR NOT NULL,
+    height BIGINT NOT NULL,
+    misbehavior_type VARCHAR NOT NULL,
+    evidence_bytes BYTEA
+);
+",
+            "
+CREATE INDEX IF NOT EXISTS ibc_misbehavior_client_id_idx ON ibc_misbehavior (client_id);
+",
+            "
+CREATE TABLE IF NOT EXISTS ibc_misbehavior_headers (
+    client_id VARCHAR NOT NULL,
+    consensus_height VARCHAR NOT NULL,
+    header BYTEA NOT NULL,
+    PRIMARY KEY (client_id, consensus_height)
+);
+",
+        ] {
+            sqlx::query(statement).execute(dbtx.as_mut()).await?;
+        }
+        Ok(())
+    }
+
+    fn is_relevant(&self, type_str: &str) -> bool {
+        [CLIENT_MISBEHAVIOUR, UPDATE_CLIENT].contains(&type_str)
+    }
+
+    #[tracing::instrument(skip_all, fields(height = event.block_height, name = event.event.kind.as_str()))]
+    async fn index_event(
+        &self,
+        dbtx: &mut PgTransaction,
+        event: &ContextualizedEvent,
+        _src_db: &PgPool,
+    ) -> anyhow::Result<()> {
+        match MisbehaviorEvent::try_from(event)? {
+            MisbehaviorEvent::Submitted { client_id } => {
+                self.record(
+                    dbtx,
+                    client_id,
+                    event.block_height,
+                    MisbehaviorType::Submitted,
+                    None,
+                )
+                .await
+            }
+            MisbehaviorEvent::Update {
+                client_id,
+                consensus_height,
+                header,
+            } => {
+                self.update(
+                    dbtx,
+                    client_id,
+                    consensus_height,
+                    header,
+                    event.block_height,
+                )
+                .await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use tendermint::abci::Event;
+
+    use super::*;
+    use crate::testing;
+
+    fn event(kind: &str, attributes: &[(&str, &str)], height: u64) -> ContextualizedEvent {
+        ContextualizedEvent {
+            event: Event {
+                kind: kind.to_string(),
+                attributes: attributes
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), value.to_string()).into())
+                    .collect(),
+            },
+            block_height: height,
+            tx_hash: None,
+            local_rowid: 1,
+        }
+    }
+
+    #[test]
+    fn submitted_misbehavior_is_recorded_and_reported() {
+        let submitted = MisbehaviorEvent::try_from(&event(
+            CLIENT_MISBEHAVIOUR,
+            &[
+                ("client_id", "07-tendermint-3"),
+                ("client_type", "07-tendermint"),
+            ],
+            42,
+        ))
+        .unwrap();
+        let MisbehaviorEvent::Submitted { client_id } = submitted else {
+            panic!("expected a submitted misbehavior, got {submitted:?}");
+        };
+        assert_eq!(client_id, "07-tendermint-3");
+
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let view = IbcMisbehaviorView::new().on_misbehavior({
+            let reported = reported.clone();
+            move |record| reported.lock().unwrap().push(record.clone())
+        });
+        let record = MisbehaviorRecord {
+            id: 1,
+            client_id,
+            height: 42,
+            misbehavior_type: MisbehaviorType::Submitted,
+            evidence: None,
+        };
+        view.alert(&record);
+        assert_eq!(*reported.lock().unwrap(), [record.clone()]);
+
+        // And the record survives a round trip through its row.
+        let row = (
+            record.id,
+            record.client_id.clone(),
+            42,
+            record.misbehavior_type.to_string(),
+            None,
+        );
+        assert_eq!(misbehavior_from_row(row).unwrap(), record);
+    }
+
+    #[test]
+    fn only_conflicting_updates_are_misbehavior() {
+        let update = |header: &str| {
+            let update = MisbehaviorEvent::try_from(&event(
+                UPDATE_CLIENT,
+                &[
+                    ("client_id", "07-tendermint-0"),
+                    ("client_type", "07-tendermint"),
+                    ("consensus_height", "1-100"),
+                    ("header", header),
+                ],
+                7,
+            ))
+            .unwrap();
+            match update {
+                MisbehaviorEvent::Update { header, .. } => header,
+                other => panic!("expected an update, got {other:?}"),
+            }
+        };
+        let first = update("0a0b0c");
+        let again = update("0a0b0c");
+        let conflicting = update("0a0b0d");
+        assert_eq!(first, [0x0a, 0x0b, 0x0c]);
+
+        assert_eq!(MisbehaviorType::for_update(None, &first), None);
+        assert_eq!(MisbehaviorType::for_update(Some(&first), &again), None);
+        assert_eq!(
+            MisbehaviorType::for_update(Some(&first), &conflicting),
+            Some(MisbehaviorType::ConflictingUpdate)
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a scratch database in PINDEXER_TEST_DATABASE_URL"]
+    async fn conflicting_updates_and_submitted_evidence_are_recorded() -> anyhow::Result<()> {
+        let pool = testing::database().await?;
+        let update = |header: &str, height| {
+            event(
+                UPDATE_CLIENT,
+                &[
+                    ("client_id", "07-tendermint-0"),
+                    ("client_type", "07-tendermint"),
+                    ("consensus_height", "1-100"),
+                    ("header", header),
+                ],
+                height,
+            )
+        };
+        let events = [
+            update("0a0b0c", 5),
+            update("0a0b0d", 6),
+            event(
+                CLIENT_MISBEHAVIOUR,
+                &[
+                    ("client_id", "07-tendermint-0"),
+                    ("client_type", "07-tendermint"),
+                ],
+                8,
+            ),
+        ];
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let view = IbcMisbehaviorView::new().on_misbehavior({
+            let reported = reported.clone();
+            move |record| reported.lock().unwrap().push(record.clone())
+        });
+        testing::index(&pool, &view, events).await?;
+
+        // Only the first header is kept, so later updates are compared against it.
+        let headers: Vec<(String, String, Vec<u8>)> = sqlx::query_as(
+            "SELECT client_id, consensus_height, header FROM ibc_misbehavior_headers",
+        )
+        .fetch_all(&pool)
+        .await?;
+        assert_eq!(
+            headers,
+            [(
+                "07-tendermint-0".to_string(),
+                "1-100".to_string(),
+                vec![0x0a, 0x0b, 0x0c]
+            )]
+        );
+
+        let detected = IbcMisbehaviorView::detected_since(&pool, 0).await?;
+        assert_eq!(
+            detected
+                .iter()
+                .map(|record| (
+                    record.height,
+                    record.misbehavior_type,
+                    record.evidence.clone()
+                ))
+                .collect::<Vec<_>>(),
+            [
+                (
+                    6,
+                    MisbehaviorType::ConflictingUpdate,
+                    Some(vec![0x0a, 0x0b, 0x0d])
+                ),
+                (8, MisbehaviorType::Submitted, None),
+            ]
+        );
+        assert_eq!(*reported.lock().unwrap(), detected);
+        assert_eq!(
+            IbcMisbehaviorView::detected_since(&pool, 7).await?,
+            detected[1..]
+        );
+
+        assert!(IbcMisbehaviorView::has_misbehavior_for(&pool, "07-tendermint-0").await?);
+        assert!(!IbcMisbehaviorView::has_misbehavior_for(&pool, "07-tendermint-1").await?);
+        Ok(())
+    }
+
+    #[test]
+    fn malformed_events_are_rejected() {
+        assert!(MisbehaviorEvent::try_from(&event(CLIENT_MISBEHAVIOUR, &[], 1)).is_err());
+        assert!(MisbehaviorEvent::try_from(&event(
+            UPDATE_CLIENT,
+            &[
+                ("client_id", "07-tendermint-0"),
+                ("consensus_height", "1-100"),
+                ("header", "not hex"),
+            ],
+            1,
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn misbehavior_types_roundtrip_through_strings() {
+        for misbehavior_type in [
+            MisbehaviorType::Submitted,
+            MisbehaviorType::ConflictingUpdate,
+        ] {
+            assert_eq!(
+                misbehavior_type
+                    .to_string()
+                    .parse::<MisbehaviorType>()
+                    .unwrap(),
+                misbehavior_type
+            );
+        }
+    }
+}
@@ -10,6 +10,25 @@ use penumbra_proto::{
 };
 use sqlx::PgPool;
 
+mod clients;
+mod connections;
+mod misbehavior;
+mod sequences;
+pub use clients::{ClientInfo, IbcClientView};
+pub use connections::{ConnectionInfo, ConnectionState, IbcConnectionView};
+pub use misbehavior::{IbcMisbehaviorView, MisbehaviorRecord, MisbehaviorType};
+pub use sequences::{IbcSequenceView, PacketStatus, PendingTimeout};
+
+/// Parse an IBC height attribute of the form `{revision_number}-{revision_height}`.
+fn parse_height(value: &str) -> anyhow::Result<(u64, u64)> {
+    let malformed = || anyhow!("malformed ibc height {value:?}");
+    let (revision_number, revision_height) = value.split_once('-').ok_or_else(malformed)?;
+    Ok((
+        revision_number.parse().map_err(|_| malformed())?,
+        revision_height.parse().map_err(|_| malformed())?,
+    ))
+}
+
 /// The kind of event we might care about.
 #[derive(Clone, Copy, Debug)]
 enum EventKind {
@@ -0,0 +1,423 @@
+use std::{fmt, str::FromStr};
+
+use anyhow::anyhow;
+use cometindex::{async_trait, AppView, ContextualizedEvent, PgTransaction};
+use sqlx::PgPool;
+
+const SEND_PACKET: &str = "send_packet";
+const ACKNOWLEDGE_PACKET: &str = "acknowledge_packet";
+const TIMEOUT_PACKET: &str = "timeout_packet";
+
+/// Where an outbound packet is in its lifecycle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PacketStatus {
+    /// The packet was sent, and has been neither acknowledged nor timed out.
+    Pending,
+    /// The counterparty acknowledged the packet.
+    Acknowledged,
+    /// The packet timed out on the counterparty.
+    TimedOut,
+}
+
+impl PacketStatus {
+    /// Move to the `next` status, rejecting transitions out of a terminal status.
+    pub fn transition(self, next: PacketStatus) -> anyhow::Result<PacketStatus> {
+        match (self, next) {
+            (PacketStatus::Pending, next) => Ok(next),
+            (current, next) if current == next => Ok(current),
+            (current, next) => Err(anyhow!(
+                "packet cannot move from {current} to {next}: {current} is final"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for PacketStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PacketStatus::Pending => "pending",
+            PacketStatus::Acknowledged => "acknowledged",
+            PacketStatus::TimedOut => "timed_out",
+        })
+    }
+}
+
+impl FromStr for PacketStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(PacketStatus::Pending),
+            "acknowledged" => Ok(PacketStatus::Acknowledged),
+            "timed_out" => Ok(PacketStatus::TimedOut),
+            other => Err(anyhow!("unknown packet status {other}")),
+        }
+    }
+}
+
+/// A sent packet whose timeout height has passed without an acknowledgement.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PendingTimeout {
+    pub channel_id: String,
+    pub sequence: u64,
+    pub sent_height: u64,
+    pub timeout_height: u64,
+}
+
+/// Parse an IBC height attribute of the form `{revision_number}-{revision_height}`, returning the
+/// revision height, or `None` if the packet has no timeout height.
+fn parse_timeout_height(value: &str) -> anyhow::Result<Option<u64>> {
+    let (_, height) = super::parse_height(value)?;
+    Ok((height != 0).then_some(height))
+}
+
+/// The attributes of a packet lifecycle event that identify the packet.
+#[derive(Debug)]
+struct PacketEvent {
+    channel_id: String,
+    sequence: u64,
+    timeout_height: Option<u64>,
+}
+
+impl TryFrom<&ContextualizedEvent> for PacketEvent {
+    type Error = anyhow::Error;
+
+    fn try_from(event: &ContextualizedEvent) -> Result<Self, Self::Error> {
+        let attribute = |key: &str| {
+            event
+                .event
+                .attributes
+                .iter()
+                .find(|attr| attr.key == key)
+                .map(|attr| attr.value.as_str())
+                .ok_or_else(|| anyhow!("{} event missing {key}", event.event.kind))
+        };
+        Ok(Self {
+            channel_id: attribute("packet_src_channel")?.to_string(),
+            sequence: attribute("packet_sequence")?.parse()?,
+            timeout_height: parse_timeout_height(attribute("packet_timeout_height")?)?,
+        })
+    }
+}
+
+/// Tracks the lifecycle of outbound IBC packets, so that relayers can find packets pending a
+/// timeout.
+///
+/// Timeout heights are heights on the counterparty chain, so comparing them against a local height
+/// is only an approximation of whether the packet can be timed out yet.
+#[derive(Debug)]
+pub struct IbcSequenceView {}
+
+impl IbcSequenceView {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// List the packets with a timeout height at or below `current_height` which have not been
+    /// acknowledged or timed out.
+    pub async fn pending_timeouts(
+        pool: &PgPool,
+        current_height: u64,
+    ) -> anyhow::Result<Vec<PendingTimeout>> {
+        let rows: Vec<(String, i64, i64, i64)> = sqlx::query_as(
+            "SELECT channel_id, sequence, sent_height, timeout_height
+             FROM ibc_sequences
+             WHERE status = $1 AND timeout_height IS NOT NULL AND timeout_height <= $2
+             ORDER BY timeout_height, channel_id, sequence",
+        )
+        .bind(PacketStatus::Pending.to_string())
+        .bind(i64::try_from(current_height)?)
+        .fetch_all(pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|(channel_id, sequence, sent_height, timeout_height)| {
+                Ok(PendingTimeout {
+                    channel_id,
+                    sequence: sequence.try_into()?,
+                    sent_height: sent_height.try_into()?,
+                    timeout_height: timeout_height.try_into()?,
+                })
+            })
+            .collect()
+    }
+
+    /// Move the packet identified by `channel_id` and `sequence` to `status`, at `height`.
+    ///
+    /// Packets sent before the view started indexing have no row, so updates to them are logged
+    /// and skipped, rather than halting the indexer.
+    pub async fn update_status(
+        dbtx: &mut PgTransaction<'_>,
+        channel_id: &str,
+        sequence: u64,
+        status: PacketStatus,
+        height: u64,
+    ) -> anyhow::Result<()> {
+        let current: Option<String> = sqlx::query_scalar(
+            "SELECT status FROM ibc_sequences WHERE channel_id = $1 AND sequence = $2",
+        )
+        .bind(channel_id)
+        .bind(i64::try_from(sequence)?)
+        .fetch_optional(dbtx.as_mut())
+        .await?;
+        let Some(current) = current else {
+            tracing::warn!(
+                channel_id,
+                sequence,
+                %status,
+                "skipping status update for a packet that was never indexed as sent"
+            );
+            return Ok(());
+        };
+        let status = current.parse::<PacketStatus>()?.transition(status)?;
+
+        let height = i64::try_from(height)?;
+        sqlx::query(
+            "UPDATE ibc_sequences
+             SET status = $3,
+                 acked_at = CASE WHEN $3 = 'acknowledged' THEN $4 ELSE acked_at END,
+                 timed_out_at = CASE WHEN $3 = 'timed_out' THEN $4 ELSE timed_out_at END
+             WHERE channel_id = $1 AND sequence = $2",
+        )
+        .bind(channel_id)
+        .bind(i64::try_from(sequence)?)
+        .bind(status.to_string())
+        .bind(height)
+        .execute(dbtx.as_mut())
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AppView for IbcSequenceView {
+    async fn init_chain(
+        &self,
+        dbtx: &mut PgTransaction,
+        _app_state: &serde_json::Value,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            "
+CREATE TABLE IF NOT EXISTS ibc_sequences (
+    channel_id VARCHAR NOT NULL,
+    sequence BIGINT NOT NULL,
+    sent_height BIGINT NOT NULL,
+    timeout_height BIGINT,
+    acked_at BIGINT,
+    timed_out_at BIGINT,
+    status VARCHAR NOT NULL,
+    PRIMARY KEY (channel_id, sequence)
+);
+",
+        )
+        .execute(dbtx.as_mut())
+        .await?;
+        Ok(())
+    }
+
+    fn is_relevant(&self, type_str: &str) -> bool {
+        [SEND_PACKET, ACKNOWLEDGE_PACKET, TIMEOUT_PACKET].contains(&type_str)
+    }
+
+    #[tracing::instrument(skip_all, fields(height = event.block_height, name = event.event.kind.as_str()))]
+    async fn index_event(
+        &self,
+        dbtx: &mut PgTransaction,
+        event: &ContextualizedEvent,
+        _src_db: &PgPool,
+    ) -> anyhow::Result<()> {
+        let packet = PacketEvent::try_from(event)?;
+        match event.event.kind.as_str() {
+            SEND_PACKET => {
+                sqlx::query(
+                    "INSERT INTO ibc_sequences (channel_id, sequence, sent_height, timeout_height, status)
+                     VALUES ($1, $2, $3, $4, $5)",
+                )
+                .bind(&packet.channel_id)
+                .bind(i64::try_from(packet.sequence)?)
+                .bind(i64::try_from(event.block_height)?)
+                .bind(packet.timeout_height.map(i64::try_from).transpose()?)
+                .bind(PacketStatus::Pending.to_string())
+                .execute(dbtx.as_mut())
+                .await?;
+                Ok(())
+            }
+            ACKNOWLEDGE_PACKET => {
+                Self::update_status(
+                    dbtx,
+                    &packet.channel_id,
+                    packet.sequence,
+                    PacketStatus::Acknowledged,
+                    event.block_height,
+                )
+                .await
+            }
+            TIMEOUT_PACKET => {
+                Self::update_status(
+                    dbtx,
+                    &packet.channel_id,
+                    packet.sequence,
+                    PacketStatus::TimedOut,
+                    event.block_height,
+                )
+                .await
+            }
+            other => Err(anyhow!("unexpected event kind: {other}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tendermint::abci::Event;
+
+    use super::*;
+    use crate::testing;
+
+    fn packet_event(
+        kind: &str,
+        sequence: u64,
+        timeout_height: &str,
+        height: u64,
+    ) -> ContextualizedEvent {
+        ContextualizedEvent {
+            event: Event {
+                kind: kind.to_string(),
+                attributes: [
+                    ("packet_src_channel", "channel-0".to_string()),
+                    ("packet_sequence", sequence.to_string()),
+                    ("packet_timeout_height", timeout_height.to_string()),
+                ]
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value).into())
+                .collect(),
+            },
+            block_height: height,
+            tx_hash: None,
+            local_rowid: 0,
+        }
+    }
+
+    /// The status and the heights at which `sequence` on `channel-0` was acknowledged or timed
+    /// out.
+    async fn status_of(
+        pool: &PgPool,
+        sequence: u64,
+    ) -> anyhow::Result<(String, Option<i64>, Option<i64>)> {
+        Ok(sqlx::query_as(
+            "SELECT status, acked_at, timed_out_at FROM ibc_sequences
+             WHERE channel_id = 'channel-0' AND sequence = $1",
+        )
+        .bind(i64::try_from(sequence)?)
+        .fetch_one(pool)
+        .await?)
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a scratch database in PINDEXER_TEST_DATABASE_URL"]
+    async fn acknowledged_packets_are_not_pending_a_timeout() -> anyhow::Result<()> {
+        let pool = testing::database().await?;
+        let events = [
+            packet_event(SEND_PACKET, 1, "1-20", 10),
+            packet_event(SEND_PACKET, 2, "1-20", 11),
+            packet_event(ACKNOWLEDGE_PACKET, 1, "1-20", 15),
+        ];
+        testing::index(&pool, &IbcSequenceView::new(), events).await?;
+
+        assert_eq!(
+            status_of(&pool, 1).await?,
+            ("acknowledged".to_string(), Some(15), None)
+        );
+        assert!(IbcSequenceView::pending_timeouts(&pool, 19)
+            .await?
+            .is_empty());
+        assert_eq!(
+            IbcSequenceView::pending_timeouts(&pool, 20).await?,
+            [PendingTimeout {
+                channel_id: "channel-0".to_string(),
+                sequence: 2,
+                sent_height: 11,
+                timeout_height: 20,
+            }]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a scratch database in PINDEXER_TEST_DATABASE_URL"]
+    async fn timed_out_packets_are_no_longer_pending() -> anyhow::Result<()> {
+        let pool = testing::database().await?;
+        let view = IbcSequenceView::new();
+        let events = [
+            packet_event(SEND_PACKET, 1, "1-20", 10),
+            // Packets without a timeout height are never pending a timeout.
+            packet_event(SEND_PACKET, 2, "0-0", 10),
+        ];
+        testing::index(&pool, &view, events).await?;
+        assert_eq!(
+            IbcSequenceView::pending_timeouts(&pool, 25).await?,
+            [PendingTimeout {
+                channel_id: "channel-0".to_string(),
+                sequence: 1,
+                sent_height: 10,
+                timeout_height: 20,
+            }]
+        );
+
+        testing::index(&pool, &view, [packet_event(TIMEOUT_PACKET, 1, "1-20", 25)]).await?;
+        assert_eq!(
+            status_of(&pool, 1).await?,
+            ("timed_out".to_string(), None, Some(25))
+        );
+        assert!(IbcSequenceView::pending_timeouts(&pool, 25)
+            .await?
+            .is_empty());
+
+        // A timed out packet can't be acknowledged afterwards.
+        let mut dbtx = pool.begin().await?;
+        assert!(view
+            .index_event(
+                &mut dbtx,
+                &packet_event(ACKNOWLEDGE_PACKET, 1, "1-20", 26),
+                &pool
+            )
+            .await
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn send_then_ack_lifecycle() {
+        let status = PacketStatus::Pending;
+        let status = status.transition(PacketStatus::Acknowledged).unwrap();
+        assert_eq!(status, PacketStatus::Acknowledged);
+        assert!(status.transition(PacketStatus::TimedOut).is_err());
+        assert!(status.transition(PacketStatus::Pending).is_err());
+    }
+
+    #[test]
+    fn send_then_timeout_lifecycle() {
+        let status = PacketStatus::Pending;
+        let status = status.transition(PacketStatus::TimedOut).unwrap();
+        assert_eq!(status, PacketStatus::TimedOut);
+        assert!(status.transition(PacketStatus::Acknowledged).is_err());
+    }
+
+    #[test]
+    fn status_round_trips_through_strings() {
+        for status in [
+            PacketStatus::Pending,
+            PacketStatus::Acknowledged,
+            PacketStatus::TimedOut,
+        ] {
+            assert_eq!(status.to_string().parse::<PacketStatus>().unwrap(), status);
+        }
+    }
+
+    #[test]
+    fn timeout_heights_are_parsed() {
+        assert_eq!(parse_timeout_height("1-1200").unwrap(), Some(1200));
+        assert_eq!(parse_timeout_height("0-0").unwrap(), None);
+        assert!(parse_timeout_height("1200").is_err());
+    }
+}
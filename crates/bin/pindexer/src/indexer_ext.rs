@@ -1,17 +1,35 @@
+use crate::Options;
+
 pub trait IndexerExt: Sized {
-    fn with_default_penumbra_app_views(self) -> Self;
+    fn with_default_penumbra_app_views(self, opts: &Options) -> Self;
 }
 
 impl IndexerExt for cometindex::Indexer {
-    fn with_default_penumbra_app_views(self) -> Self {
-        self.with_index(crate::shielded_pool::fmd::ClueSet {})
+    fn with_default_penumbra_app_views(self, opts: &Options) -> Self {
+        let indexer = self
+            .with_index(crate::shielded_pool::fmd::ClueSet {})
             .with_index(crate::stake::ValidatorSet {})
             .with_index(crate::stake::Slashings {})
             .with_index(crate::stake::DelegationTxs {})
             .with_index(crate::stake::UndelegationTxs {})
             .with_index(crate::governance::GovernanceProposals {})
             .with_index(crate::dex::Component::new())
+            .with_index(crate::dex::VolumeView::new(
+                crate::dex::DEFAULT_BLOCK_TIME_SECS,
+            ))
+            .with_index(crate::dex::SwapReceiptView::new())
+            .with_index(crate::dex::SwapSettlementView::new())
             .with_index(crate::supply::Component::new())
+            .with_index(crate::fee::ProposerRewardView::new())
             .with_index(crate::ibc::Component::new())
+            .with_index(crate::ibc::IbcSequenceView::new())
+            .with_index(crate::ibc::IbcClientView::new())
+            .with_index(crate::ibc::IbcConnectionView::new())
+            .with_index(crate::ibc::IbcMisbehaviorView::new())
+            .with_index(crate::sct::TctEpochRootView::new());
+        match opts.tct_snapshot_interval {
+            Some(interval) => indexer.with_index(crate::sct::TctSnapshotView::new(interval)),
+            None => indexer,
+        }
     }
 }
@@ -1,14 +1,22 @@
-pub use cometindex::{opt::Options, AppView, ContextualizedEvent, Indexer, PgPool, PgTransaction};
+pub use cometindex::{AppView, ContextualizedEvent, Indexer, PgPool, PgTransaction};
+
+mod opt;
+pub use opt::Options;
 
 mod indexer_ext;
 pub use indexer_ext::IndexerExt;
+pub mod audit;
 pub mod block;
 pub mod dex;
+pub mod fee;
 pub mod ibc;
 mod parsing;
+pub mod sct;
 pub mod shielded_pool;
 mod sql;
 pub mod stake;
 pub mod supply;
+#[cfg(test)]
+mod testing;
 
 pub mod governance;
@@ -5,9 +5,10 @@ use pindexer::{Indexer, IndexerExt as _, Options};
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    Indexer::new(Options::parse())
+    let opts = Options::parse();
+    Indexer::new(opts.indexer.clone())
         .with_default_tracing()
-        .with_default_penumbra_app_views()
+        .with_default_penumbra_app_views(&opts)
         .with_index(Block {})
         .run()
         .await?;
@@ -0,0 +1,20 @@
+use clap::Parser;
+
+/// This struct represents the command-line options
+#[derive(Clone, Debug, Parser)]
+#[clap(
+    name = "pindexer",
+    about = "indexes raw events emitted by penumbra into a database of compiled data",
+    version
+)]
+pub struct Options {
+    #[clap(flatten)]
+    pub indexer: cometindex::opt::Options,
+
+    /// If set, snapshot the state commitment tree every this many blocks.
+    ///
+    /// Snapshots are written to `tct_snapshots`, so that clients can resume from a checkpoint
+    /// without replaying every commitment since genesis.
+    #[clap(long)]
+    pub tct_snapshot_interval: Option<u64>,
+}
@@ -0,0 +1,566 @@
+use anyhow::{anyhow, Context as _};
+use cometindex::{async_trait, sqlx, AppView, ContextualizedEvent, PgTransaction};
+use penumbra_proto::{
+    core::component::sct::v1 as pb, crypto::tct::v1 as pb_tct, event::ProtoEvent,
+};
+use penumbra_tct as tct;
+use sqlx::PgPool;
+use tokio::sync::Mutex;
+
+/// Maintains a copy of the state commitment tree from commitment events, persisting a
+/// [`tct::Snapshot`] every `snapshot_interval` blocks.
+///
+/// Clients resuming from a checkpoint can fetch the snapshot at or below their checkpoint height
+/// from `tct_snapshots`, rather than replaying every commitment since genesis.
+#[derive(Debug)]
+pub struct TctSnapshotView {
+    snapshot_interval: u64,
+    /// The tree as of the last indexed event, loaded from the database on first use.
+    tree: Mutex<Option<tct::Tree>>,
+}
+
+impl TctSnapshotView {
+    /// Create a view that snapshots the tree every `snapshot_interval` blocks.
+    pub fn new(snapshot_interval: u64) -> Self {
+        Self {
+            snapshot_interval: snapshot_interval.max(1),
+            tree: Mutex::new(None),
+        }
+    }
+
+    /// Load the tree as of the last indexed block, or an empty tree if nothing has been indexed.
+    async fn load_tree(dbtx: &mut PgTransaction<'_>) -> anyhow::Result<tct::Tree> {
+        let row: Option<(Vec<u8>,)> =
+            sqlx::query_as("SELECT frontier_bytes FROM tct_frontier WHERE id = 0")
+                .fetch_optional(dbtx.as_mut())
+                .await?;
+        match row {
+            None => Ok(tct::Tree::new()),
+            Some((bytes,)) => {
+                let frontier: tct::snapshot::Frontier = bincode::deserialize(&bytes)
+                    .context("failed to deserialize stored tct frontier")?;
+                Ok(frontier.into())
+            }
+        }
+    }
+
+    /// Record the tree as of the end of `height`, writing a snapshot if `height` falls on the
+    /// snapshot interval.
+    ///
+    /// An epoch root is emitted after the block root for the same block, so this may be called
+    /// twice for the same height; the second call overwrites the first.
+    async fn persist(
+        &self,
+        dbtx: &mut PgTransaction<'_>,
+        tree: &tct::Tree,
+        height: u64,
+    ) -> anyhow::Result<()> {
+        let snapshot = tree.snapshot(height);
+        let frontier_bytes = bincode::serialize(&snapshot.frontier)?;
+
+        sqlx::query(
+            "INSERT INTO tct_frontier (id, height, frontier_bytes)
+             VALUES (0, $1, $2)
+             ON CONFLICT (id) DO UPDATE
+             SET height = EXCLUDED.height, frontier_bytes = EXCLUDED.frontier_bytes",
+        )
+        .bind(i64::try_from(height)?)
+        .bind(&frontier_bytes)
+        .execute(dbtx.as_mut())
+        .await?;
+
+        if height % self.snapshot_interval == 0 {
+            sqlx::query(
+                "INSERT INTO tct_snapshots (height, root, frontier_bytes)
+                 VALUES ($1, $2, $3)
+                 ON CONFLICT (height) DO UPDATE
+                 SET root = EXCLUDED.root, frontier_bytes = EXCLUDED.frontier_bytes",
+            )
+            .bind(i64::try_from(height)?)
+            .bind(pb_tct::MerkleRoot::from(snapshot.root).inner)
+            .bind(&frontier_bytes)
+            .execute(dbtx.as_mut())
+            .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AppView for TctSnapshotView {
+    async fn init_chain(
+        &self,
+        dbtx: &mut PgTransaction,
+        _: &serde_json::Value,
+    ) -> Result<(), anyhow::Error> {
+        sqlx::query(
+            "
+CREATE TABLE IF NOT EXISTS tct_snapshots (
+    height BIGINT PRIMARY KEY,
+    root BYTEA NOT NULL,
+    frontier_bytes BYTEA NOT NULL
+);
+",
+        )
+        .execute(dbtx.as_mut())
+        .await?;
+        sqlx::query(
+            "
+CREATE TABLE IF NOT EXISTS tct_frontier (
+    id INT PRIMARY KEY CHECK (id = 0),
+    height BIGINT NOT NULL,
+    frontier_bytes BYTEA NOT NULL
+);
+",
+        )
+        .execute(dbtx.as_mut())
+        .await?;
+        Ok(())
+    }
+
+    fn is_relevant(&self, type_str: &str) -> bool {
+        [
+            "penumbra.core.component.sct.v1.EventCommitment",
+            "penumbra.core.component.sct.v1.EventBlockRoot",
+            "penumbra.core.component.sct.v1.EventEpochRoot",
+        ]
+        .contains(&type_str)
+    }
+
+    async fn index_event(
+        &self,
+        dbtx: &mut PgTransaction,
+        event: &ContextualizedEvent,
+        _src_db: &PgPool,
+    ) -> Result<(), anyhow::Error> {
+        let mut guard = self.tree.lock().await;
+        if guard.is_none() {
+            *guard = Some(Self::load_tree(dbtx).await?);
+        }
+        let tree = guard.as_mut().expect("tree was loaded above");
+
+        match event.event.kind.as_str() {
+            "penumbra.core.component.sct.v1.EventCommitment" => {
+                let pe = pb::EventCommitment::from_event(event.as_ref())?;
+                let commitment: tct::StateCommitment = pe
+                    .commitment
+                    .ok_or_else(|| anyhow!("missing commitment"))?
+                    .try_into()?;
+                let position = tree.insert(tct::Witness::Forget, commitment)?;
+                anyhow::ensure!(
+                    u64::from(position) == pe.position,
+                    "state commitment tree is out of sync: inserted at {:?}, event says {}",
+                    position,
+                    pe.position
+                );
+            }
+            "penumbra.core.component.sct.v1.EventBlockRoot" => {
+                let pe = pb::EventBlockRoot::from_event(event.as_ref())?;
+                let expected: tct::builder::block::Root = pe
+                    .root
+                    .ok_or_else(|| anyhow!("missing block root"))?
+                    .try_into()?;
+                let block_root = tree.end_block()?;
+                anyhow::ensure!(
+                    block_root == expected,
+                    "state commitment tree is out of sync at height {}: computed block root {}, event says {}",
+                    pe.height,
+                    block_root,
+                    expected
+                );
+                self.persist(dbtx, tree, pe.height).await?;
+            }
+            "penumbra.core.component.sct.v1.EventEpochRoot" => {
+                let pe = pb::EventEpochRoot::from_event(event.as_ref())?;
+                let expected: tct::builder::epoch::Root = pe
+                    .root
+                    .ok_or_else(|| anyhow!("missing epoch root"))?
+                    .try_into()?;
+                let epoch_root = tree.end_epoch()?;
+                anyhow::ensure!(
+                    epoch_root == expected,
+                    "state commitment tree is out of sync at epoch {}: computed epoch root {}, event says {}",
+                    pe.index,
+                    epoch_root,
+                    expected
+                );
+                self.persist(dbtx, tree, event.block_height).await?;
+            }
+            other => anyhow::bail!("unexpected event type {}", other),
+        }
+
+        Ok(())
+    }
+
+    async fn rollback(&self) {
+        // The tree may be ahead of the rolled back transaction, so it's reloaded from the last
+        // committed frontier on the next event.
+        *self.tree.lock().await = None;
+    }
+}
+
+const EPOCH_ROOT: &str = "penumbra.core.component.sct.v1.EventEpochRoot";
+
+/// The attributes of an epoch root event.
+#[derive(Debug)]
+struct EpochRootEvent {
+    index: u64,
+    root: tct::builder::epoch::Root,
+}
+
+impl TryFrom<&ContextualizedEvent> for EpochRootEvent {
+    type Error = anyhow::Error;
+
+    fn try_from(event: &ContextualizedEvent) -> Result<Self, Self::Error> {
+        let pe = pb::EventEpochRoot::from_event(event.as_ref())?;
+        Ok(Self {
+            index: pe.index,
+            root: pe
+                .root
+                .ok_or_else(|| anyhow!("missing epoch root"))?
+                .try_into()?,
+        })
+    }
+}
+
+/// An ended epoch, and the range of heights it covered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct EpochRecord {
+    epoch_index: u64,
+    root: tct::builder::epoch::Root,
+    /// The height of the epoch's first block, or `None` if the epoch before it wasn't indexed.
+    start_height: Option<u64>,
+    /// The height of the epoch's last block, in which its root was emitted.
+    end_height: u64,
+}
+
+impl EpochRecord {
+    /// The record for the epoch ending with `event` at `end_height`, which starts just after the
+    /// epoch before it, `previous`, ended.
+    ///
+    /// If indexing began partway through the chain, the first epoch seen has nothing before it,
+    /// and its start height is unknown.
+    fn after(
+        previous: Option<&EpochRecord>,
+        event: EpochRootEvent,
+        end_height: u64,
+    ) -> anyhow::Result<EpochRecord> {
+        let start_height = match (event.index, previous) {
+            (0, _) => Some(0),
+            (index, Some(previous)) if previous.epoch_index + 1 == index => {
+                Some(previous.end_height + 1)
+            }
+            (index, Some(previous)) => anyhow::bail!(
+                "epoch {index} ended, but the last recorded epoch is {}",
+                previous.epoch_index
+            ),
+            (_, None) => None,
+        };
+        if let Some(start_height) = start_height {
+            anyhow::ensure!(
+                start_height <= end_height,
+                "epoch {} ended at height {end_height}, before it started at {start_height}",
+                event.index
+            );
+        }
+        Ok(EpochRecord {
+            epoch_index: event.index,
+            root: event.root,
+            start_height,
+            end_height,
+        })
+    }
+}
+
+type EpochRecordRow = (i64, Vec<u8>, Option<i64>, i64);
+
+fn epoch_record_from_row(
+    (epoch_index, root, start_height, end_height): EpochRecordRow,
+) -> anyhow::Result<EpochRecord> {
+    Ok(EpochRecord {
+        epoch_index: epoch_index.try_into()?,
+        root: root_from_bytes(root)?,
+        start_height: start_height.map(u64::try_from).transpose()?,
+        end_height: end_height.try_into()?,
+    })
+}
+
+fn root_from_bytes(inner: Vec<u8>) -> anyhow::Result<tct::builder::epoch::Root> {
+    pb_tct::MerkleRoot { inner }
+        .try_into()
+        .context("failed to decode stored epoch root")
+}
+
+/// Archives the root of every ended epoch, along with the heights it covered.
+///
+/// Clients only keep the roots of recent epochs, so this lets a wallet find the root to check an
+/// old witness against.
+#[derive(Debug)]
+pub struct TctEpochRootView {}
+
+impl TctEpochRootView {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Get the root of `epoch`, if it has ended.
+    pub async fn root_for_epoch(
+        pool: &PgPool,
+        epoch: u64,
+    ) -> anyhow::Result<Option<tct::builder::epoch::Root>> {
+        let root: Option<Vec<u8>> =
+            sqlx::query_scalar("SELECT root FROM tct_epoch_roots WHERE epoch_index = $1")
+                .bind(i64::try_from(epoch)?)
+                .fetch_optional(pool)
+                .await?;
+        root.map(root_from_bytes).transpose()
+    }
+
+    /// Get the root of the epoch that `height` was part of, if that epoch has ended.
+    ///
+    /// Heights in the first epoch indexed aren't found unless it's the first epoch of the chain,
+    /// since where that epoch started isn't known.
+    pub async fn root_at_height(
+        pool: &PgPool,
+        height: u64,
+    ) -> anyhow::Result<Option<tct::builder::epoch::Root>> {
+        let root: Option<Vec<u8>> = sqlx::query_scalar(
+            "SELECT root FROM tct_epoch_roots
+             WHERE start_height <= $1 AND $1 <= end_height",
+        )
+        .bind(i64::try_from(height)?)
+        .fetch_optional(pool)
+        .await?;
+        root.map(root_from_bytes).transpose()
+    }
+}
+
+#[async_trait]
+impl AppView for TctEpochRootView {
+    async fn init_chain(
+        &self,
+        dbtx: &mut PgTransaction,
+        _: &serde_json::Value,
+    ) -> Result<(), anyhow::Error> {
+        sqlx::query(
+            "
+CREATE TABLE IF NOT EXISTS tct_epoch_roots (
+    epoch_index BIGINT PRIMARY KEY,
+    root BYTEA NOT NULL,
+    start_height BIGINT,
+    end_height BIGINT NOT NULL
+);
+",
+        )
+        .execute(dbtx.as_mut())
+        .await?;
+        Ok(())
+    }
+
+    fn is_relevant(&self, type_str: &str) -> bool {
+        type_str == EPOCH_ROOT
+    }
+
+    async fn index_event(
+        &self,
+        dbtx: &mut PgTransaction,
+        event: &ContextualizedEvent,
+        _src_db: &PgPool,
+    ) -> Result<(), anyhow::Error> {
+        let pe = EpochRootEvent::try_from(event)?;
+        let previous: Option<EpochRecordRow> = sqlx::query_as(
+            "SELECT epoch_index, root, start_height, end_height
+             FROM tct_epoch_roots
+             ORDER BY epoch_index DESC
+             LIMIT 1",
+        )
+        .fetch_optional(dbtx.as_mut())
+        .await?;
+        let previous = previous.map(epoch_record_from_row).transpose()?;
+        let record = EpochRecord::after(previous.as_ref(), pe, event.block_height)?;
+
+        sqlx::query("INSERT INTO tct_epoch_roots VALUES ($1, $2, $3, $4)")
+            .bind(i64::try_from(record.epoch_index)?)
+            .bind(pb_tct::MerkleRoot::from(record.root).inner)
+            .bind(record.start_height.map(i64::try_from).transpose()?)
+            .bind(i64::try_from(record.end_height)?)
+            .execute(dbtx.as_mut())
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tendermint::abci::Event;
+
+    use super::*;
+    use crate::testing;
+
+    fn event(index: u64, root: tct::builder::epoch::Root, height: u64) -> ContextualizedEvent {
+        let event: Event = penumbra_sct::event::epoch_root(index, root, 0).into_event();
+        ContextualizedEvent {
+            event,
+            block_height: height,
+            tx_hash: None,
+            local_rowid: 1,
+        }
+    }
+
+    /// The roots of `count` distinct epochs, each holding a single commitment.
+    fn roots(count: u8) -> Vec<tct::builder::epoch::Root> {
+        let mut tree = tct::Tree::new();
+        (0..count)
+            .map(|i| {
+                let mut bytes = [0u8; 32];
+                bytes[0] = i;
+                let commitment = tct::StateCommitment::try_from(bytes).unwrap();
+                tree.insert(tct::Witness::Forget, commitment).unwrap();
+                tree.end_epoch().unwrap()
+            })
+            .collect()
+    }
+
+    /// Record the epochs ending at each of `end_heights`, as the view would.
+    fn records(end_heights: &[u64]) -> Vec<EpochRecord> {
+        let roots = roots(end_heights.len().try_into().unwrap());
+        let mut records: Vec<EpochRecord> = Vec::new();
+        for (index, (&root, &height)) in roots.iter().zip(end_heights).enumerate() {
+            let pe = EpochRootEvent::try_from(&event(index as u64, root, height)).unwrap();
+            records.push(EpochRecord::after(records.last(), pe, height).unwrap());
+        }
+        records
+    }
+
+    #[test]
+    fn epoch_root_events_are_parsed() {
+        let root = roots(1)[0];
+        let pe = EpochRootEvent::try_from(&event(3, root, 40)).unwrap();
+        assert_eq!(pe.index, 3);
+        assert_eq!(pe.root, root);
+    }
+
+    #[test]
+    fn sequential_epochs_start_after_the_last_one_ended() {
+        let spans: Vec<(Option<u64>, u64)> = records(&[9, 19, 20, 35, 50])
+            .iter()
+            .map(|record| (record.start_height, record.end_height))
+            .collect();
+        assert_eq!(
+            spans,
+            [
+                (Some(0), 9),
+                (Some(10), 19),
+                (Some(20), 20),
+                (Some(21), 35),
+                (Some(36), 50)
+            ]
+        );
+    }
+
+    #[test]
+    fn epochs_must_end_in_order() {
+        let roots = roots(3);
+        let first = EpochRecord::after(
+            None,
+            EpochRootEvent::try_from(&event(0, roots[0], 9)).unwrap(),
+            9,
+        )
+        .unwrap();
+
+        // An epoch that doesn't follow the last recorded one.
+        let skipped = EpochRootEvent::try_from(&event(2, roots[2], 19)).unwrap();
+        assert!(EpochRecord::after(Some(&first), skipped, 19).is_err());
+        // An epoch ending before it starts.
+        let early = EpochRootEvent::try_from(&event(1, roots[1], 9)).unwrap();
+        assert!(EpochRecord::after(Some(&first), early, 9).is_err());
+        // An epoch after the first with nothing before it, as when indexing begins partway
+        // through the chain, has an unknown start.
+        let orphan = EpochRootEvent::try_from(&event(1, roots[1], 19)).unwrap();
+        assert_eq!(
+            EpochRecord::after(None, orphan, 19).unwrap().start_height,
+            None
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a scratch database in PINDEXER_TEST_DATABASE_URL"]
+    async fn every_height_finds_the_root_of_its_epoch() -> anyhow::Result<()> {
+        let pool = testing::database().await?;
+        let end_heights = [9, 19, 20, 35, 50];
+        let roots = roots(5);
+        let events = roots
+            .iter()
+            .zip(end_heights)
+            .enumerate()
+            .map(|(index, (&root, height))| event(index as u64, root, height));
+        testing::index(&pool, &TctEpochRootView::new(), events).await?;
+
+        let mut epoch = 0;
+        for height in 0..=50 {
+            if height > end_heights[epoch] {
+                epoch += 1;
+            }
+            assert_eq!(
+                TctEpochRootView::root_at_height(&pool, height).await?,
+                Some(roots[epoch]),
+                "height {height}"
+            );
+        }
+        // The current epoch hasn't ended, so it has no root yet.
+        assert_eq!(TctEpochRootView::root_at_height(&pool, 51).await?, None);
+
+        for (epoch, root) in roots.iter().enumerate() {
+            assert_eq!(
+                TctEpochRootView::root_for_epoch(&pool, epoch as u64).await?,
+                Some(*root)
+            );
+        }
+        // Every epoch has its own root.
+        for (i, a) in roots.iter().enumerate() {
+            assert!(!roots[i + 1..].contains(a));
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a scratch database in PINDEXER_TEST_DATABASE_URL"]
+    async fn indexing_can_begin_partway_through_the_chain() -> anyhow::Result<()> {
+        let pool = testing::database().await?;
+        let roots = roots(3);
+        let events = [event(7, roots[0], 70), event(8, roots[1], 80)];
+        testing::index(&pool, &TctEpochRootView::new(), events).await?;
+
+        // Where the first epoch seen started isn't known, but the epochs after it are complete.
+        assert_eq!(
+            TctEpochRootView::root_for_epoch(&pool, 7).await?,
+            Some(roots[0])
+        );
+        assert_eq!(TctEpochRootView::root_at_height(&pool, 70).await?, None);
+        assert_eq!(
+            TctEpochRootView::root_at_height(&pool, 71).await?,
+            Some(roots[1])
+        );
+        assert_eq!(
+            TctEpochRootView::root_at_height(&pool, 80).await?,
+            Some(roots[1])
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rolling_back_discards_the_in_memory_tree() {
+        let view = TctSnapshotView::new(10);
+        let mut tree = tct::Tree::new();
+        tree.insert(
+            tct::Witness::Forget,
+            tct::StateCommitment::try_from([1u8; 32]).unwrap(),
+        )
+        .unwrap();
+        *view.tree.lock().await = Some(tree);
+
+        view.rollback().await;
+        assert!(view.tree.lock().await.is_none());
+    }
+}
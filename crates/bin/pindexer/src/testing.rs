@@ -0,0 +1,130 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use anyhow::{Context, Result};
+use cometindex::{AppView, BlockEvents, ContextualizedEvent, PgPool};
+use sqlx::{postgres::PgPoolOptions, Executor};
+use tendermint::abci;
+
+/// The environment variable holding the URL of a scratch database to test queries against.
+pub const TEST_DATABASE_URL: &str = "PINDEXER_TEST_DATABASE_URL";
+
+/// A pool of connections to a fresh schema in the test database.
+///
+/// Tests using this are marked `#[ignore]`, so that they only run when asked for with
+/// `--ignored`, and fail if they're run without a test database.
+///
+/// Each call creates a new schema, so that tests can run concurrently without seeing each
+/// other's tables. The schemas aren't dropped afterwards, so the database should be a scratch
+/// one.
+pub async fn database() -> Result<PgPool> {
+    static SCHEMAS: AtomicU64 = AtomicU64::new(0);
+
+    let url = std::env::var(TEST_DATABASE_URL)
+        .with_context(|| format!("{TEST_DATABASE_URL} must be set to run database tests"))?;
+    let schema = format!(
+        "pindexer_test_{}_{}_{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)?
+            .as_micros(),
+        SCHEMAS.fetch_add(1, Ordering::Relaxed)
+    );
+    PgPool::connect(&url)
+        .await?
+        .execute(format!("CREATE SCHEMA {schema}").as_str())
+        .await?;
+    let pool = PgPoolOptions::new()
+        .after_connect(move |conn, _| {
+            let search_path = format!("SET search_path TO {schema}");
+            Box::pin(async move {
+                conn.execute(search_path.as_str()).await?;
+                Ok(())
+            })
+        })
+        .connect(&url)
+        .await?;
+    Ok(pool)
+}
+
+/// Initialize `view`'s tables in `pool`, then index `events` with it, each in its own block.
+pub async fn index(
+    pool: &PgPool,
+    view: &impl AppView,
+    events: impl IntoIterator<Item = ContextualizedEvent>,
+) -> Result<()> {
+    let mut dbtx = pool.begin().await?;
+    view.init_chain(&mut dbtx, &serde_json::Value::Null).await?;
+    dbtx.commit().await?;
+    for event in events {
+        let mut dbtx = pool.begin().await?;
+        view.index_event(&mut dbtx, &event, pool).await?;
+        view.post_block(
+            &mut dbtx,
+            event.block_height,
+            BlockEvents::new(std::slice::from_ref(&event)),
+        )
+        .await?;
+        dbtx.commit().await?;
+    }
+    Ok(())
+}
+
+/// Create the tables of CometBFT's raw event database in `pool`, for testing code that reads
+/// them directly.
+pub async fn install_raw_schema(pool: &PgPool) -> Result<()> {
+    pool.execute(include_str!("../../../util/cometindex/vendor/schema.sql"))
+        .await?;
+    Ok(())
+}
+
+/// Insert a block into the raw event database, with the block events `events` and a transaction
+/// for each encoded `TxResult` in `tx_results`.
+pub async fn insert_raw_block(
+    pool: &PgPool,
+    height: u64,
+    events: &[abci::Event],
+    tx_results: &[Vec<u8>],
+) -> Result<()> {
+    let block_id: i64 = sqlx::query_scalar(
+        "INSERT INTO blocks (height, chain_id, created_at)
+         VALUES ($1, 'penumbra-test', NOW())
+         RETURNING rowid",
+    )
+    .bind(i64::try_from(height)?)
+    .fetch_one(pool)
+    .await?;
+    for (index, tx_result) in tx_results.iter().enumerate() {
+        sqlx::query(
+            "INSERT INTO tx_results (block_id, index, created_at, tx_hash, tx_result)
+             VALUES ($1, $2, NOW(), $3, $4)",
+        )
+        .bind(block_id)
+        .bind(i32::try_from(index)?)
+        .bind(format!("{height}-{index}"))
+        .bind(tx_result)
+        .execute(pool)
+        .await?;
+    }
+    for event in events {
+        let event_id: i64 = sqlx::query_scalar(
+            "INSERT INTO events (block_id, type) VALUES ($1, $2) RETURNING rowid",
+        )
+        .bind(block_id)
+        .bind(&event.kind)
+        .fetch_one(pool)
+        .await?;
+        for attribute in &event.attributes {
+            sqlx::query(
+                "INSERT INTO attributes (event_id, key, composite_key, value)
+                 VALUES ($1, $2, $3, $4)",
+            )
+            .bind(event_id)
+            .bind(&attribute.key)
+            .bind(format!("{}.{}", event.kind, attribute.key))
+            .bind(&attribute.value)
+            .execute(pool)
+            .await?;
+        }
+    }
+    Ok(())
+}
@@ -139,3 +139,37 @@ pub async fn claimed_anchor_is_valid<S: StateRead>(
 ) -> Result<()> {
     state.check_claimed_anchor(transaction.anchor).await
 }
+
+#[cfg(test)]
+mod tests {
+    use cnidarium::{StateDelta, TempStorage};
+    use penumbra_sct::component::clock::EpochManager as _;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn expiry_height_rejects_transactions_after_it_passes() -> Result<()> {
+        let storage = TempStorage::new().await?;
+        let mut state = StateDelta::new(storage.latest_snapshot());
+
+        // A transaction (e.g. carrying a swap) submitted at height 100, valid until height 110.
+        let expiry_height = 110;
+        for (height, valid) in [
+            (100, true),
+            (105, true),
+            (110, true),
+            (111, false),
+            (115, false),
+        ] {
+            state.put_block_height(height);
+            assert_eq!(
+                expiry_height_is_valid(&state, expiry_height).await.is_ok(),
+                valid,
+                "height {height}"
+            );
+        }
+
+        // A zero expiry height never expires.
+        expiry_height_is_valid(&state, 0).await
+    }
+}
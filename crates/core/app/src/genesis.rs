@@ -11,6 +11,7 @@ use penumbra_sct::genesis::Content as SctContent;
 use penumbra_shielded_pool::genesis::Content as ShieldedPoolContent;
 use penumbra_stake::genesis::Content as StakeContent;
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
 /// The application state at genesis.
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -209,6 +210,47 @@ impl Content {
     }
 }
 
+/// A hash identifying a chain by its genesis application state.
+///
+/// Clients can pin a [`GenesisHash`] to make sure that the node they are talking
+/// to serves the chain they expect, rather than some other chain that happens to
+/// reuse the same chain ID.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GenesisHash(pub [u8; 32]);
+
+impl GenesisHash {
+    /// Computes the hash of the provided genesis state, over its canonical
+    /// protobuf encoding.
+    pub fn compute(genesis: &AppState) -> Self {
+        let hash = blake2b_simd::Params::new()
+            .personal(b"Penumbra_Genesis")
+            .hash_length(32)
+            .hash(&genesis.encode_to_vec());
+        Self(
+            hash.as_bytes()
+                .try_into()
+                .expect("blake2b output is 32 bytes"),
+        )
+    }
+}
+
+impl std::fmt::Display for GenesisHash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&hex::encode(self.0))
+    }
+}
+
+impl FromStr for GenesisHash {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(s)?;
+        Ok(Self(bytes.try_into().map_err(|_| {
+            anyhow::anyhow!("genesis hash must be 32 bytes")
+        })?))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -224,4 +266,17 @@ mod test {
         assert!(a.stake_content.validators.is_empty());
         Ok(())
     }
+
+    #[test]
+    fn genesis_hash_depends_on_content() -> anyhow::Result<()> {
+        let a = AppState::Content(Content::default().with_chain_id("penumbra-1".to_owned()));
+        let b = AppState::Content(Content::default().with_chain_id("penumbra-2".to_owned()));
+
+        assert_eq!(GenesisHash::compute(&a), GenesisHash::compute(&a.clone()));
+        assert_ne!(GenesisHash::compute(&a), GenesisHash::compute(&b));
+
+        let hash = GenesisHash::compute(&a);
+        assert_eq!(hash, hash.to_string().parse()?);
+        Ok(())
+    }
 }
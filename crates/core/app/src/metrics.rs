@@ -23,6 +23,7 @@ pub fn register_metrics() {
     penumbra_governance::register_metrics();
     penumbra_ibc::component::register_metrics();
     penumbra_shielded_pool::component::register_metrics();
+    penumbra_compact_block::component::metrics::register_metrics();
 
     describe_counter!(
         MEMPOOL_CHECKTX_TOTAL,
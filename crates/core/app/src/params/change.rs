@@ -90,7 +90,11 @@ impl AppParameters {
                     outbound_ics20_transfers_enabled: _,
                 },
             sct_params: SctParameters { epoch_duration },
-            shielded_pool_params: ShieldedPoolParameters { fmd_meta_params: _ },
+            shielded_pool_params:
+                ShieldedPoolParameters {
+                    fmd_meta_params: _,
+                    supply_caps: _,
+                },
             stake_params:
                 StakeParameters {
                     active_validator_limit,
@@ -108,6 +112,7 @@ impl AppParameters {
                     max_hops: _,
                     max_positions_per_pair: _,
                     max_execution_budget: _,
+                    fee_tiers: _,
                 },
             // IMPORTANT: Don't use `..` here! We want to ensure every single field is verified!
         } = self;
@@ -185,7 +190,11 @@ impl AppParameters {
                     outbound_ics20_transfers_enabled,
                 },
             sct_params: SctParameters { epoch_duration },
-            shielded_pool_params: ShieldedPoolParameters { fmd_meta_params: _ },
+            shielded_pool_params:
+                ShieldedPoolParameters {
+                    fmd_meta_params: _,
+                    supply_caps,
+                },
             stake_params:
                 StakeParameters {
                     active_validator_limit,
@@ -203,6 +212,7 @@ impl AppParameters {
                     max_hops: _,
                     max_positions_per_pair: _,
                     max_execution_budget: _,
+                    fee_tiers,
                 },
             // IMPORTANT: Don't use `..` here! We want to ensure every single field is verified!
         } = self;
@@ -274,6 +284,22 @@ impl AppParameters {
                 *min_validator_stake >= 1_000_000u128.into(),
                 "the minimum validator stake must be at least 1penumbra",
             ),
+            (
+                fee_tiers.0.iter().all(|bps| *bps <= 5_000),
+                "fee tiers must be at most 5,000 basis points",
+            ),
+            (
+                supply_caps.iter().enumerate().all(|(i, cap)| {
+                    supply_caps[..i]
+                        .iter()
+                        .all(|other| other.asset() != cap.asset())
+                }),
+                "each asset may have at most one supply cap",
+            ),
+            (
+                supply_caps.iter().all(|cap| cap.is_ibc_asset()),
+                "supply caps may only be set on assets that arrive over IBC",
+            ),
         ])
     }
 }
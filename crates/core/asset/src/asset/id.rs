@@ -4,7 +4,10 @@ use ark_serialize::CanonicalDeserialize;
 use base64::Engine;
 use decaf377::Fq;
 use once_cell::sync::Lazy;
-use penumbra_num::Amount;
+use penumbra_num::{
+    field_utils::{bytes_to_fq, fq_to_bytes},
+    Amount,
+};
 use penumbra_proto::{penumbra::core::asset::v1 as pb, serializers::bech32str, DomainType};
 use serde::{Deserialize, Serialize};
 
@@ -33,7 +36,7 @@ pub struct Id(pub Fq);
 impl From<Id> for pb::AssetId {
     fn from(id: Id) -> Self {
         pb::AssetId {
-            inner: id.0.to_bytes().to_vec(),
+            inner: fq_to_bytes(id.0).to_vec(),
             // Never produce a proto encoding with the alt string encoding.
             alt_bech32m: String::new(),
             // Never produce a proto encoding with the alt base denom.
@@ -80,14 +83,14 @@ impl TryFrom<[u8; 32]> for Id {
     type Error = anyhow::Error;
 
     fn try_from(bytes: [u8; 32]) -> Result<Id, Self::Error> {
-        Ok(Id(Fq::from_bytes_checked(&bytes).expect("convert to bytes")))
+        Ok(Id(bytes_to_fq(&bytes)?))
     }
 }
 
 impl std::fmt::Debug for Id {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(&bech32str::encode(
-            &self.0.to_bytes(),
+            &fq_to_bytes(self.0),
             bech32str::asset_id::BECH32_PREFIX,
             bech32str::Bech32m,
         ))
@@ -98,7 +101,7 @@ impl std::fmt::Display for Id {
     // IMPORTANT: Changing this is state-breaking.
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         f.write_str(&bech32str::encode(
-            &self.0.to_bytes(),
+            &fq_to_bytes(self.0),
             bech32str::asset_id::BECH32_PREFIX,
             bech32str::Bech32m,
         ))
@@ -143,7 +146,7 @@ impl Id {
 
     /// Convert the asset ID to bytes.
     pub fn to_bytes(&self) -> [u8; 32] {
-        self.0.to_bytes()
+        fq_to_bytes(self.0)
     }
 
     /// Create a value of this denomination.
@@ -11,7 +11,8 @@ use penumbra_sct::component::tree::{SctManager as _, SctRead};
 use penumbra_shielded_pool::component::NoteManager as _;
 use tracing::instrument;
 
-use crate::{state_key, CompactBlock};
+use super::metrics;
+use crate::{state_key, CompactBlock, CompactBlockStats};
 
 #[async_trait]
 pub trait CompactBlockManager: StateWrite {
@@ -142,10 +143,10 @@ trait Inner: StateWrite {
             epoch_index,
         };
 
-        self.nonverifiable_put_raw(
-            state_key::compact_block(height).into_bytes(),
-            compact_block.encode_to_vec(),
-        );
+        let encoding = compact_block.encode_to_vec();
+        metrics::record_compact_block_stats(&CompactBlockStats::new(&compact_block, &encoding));
+
+        self.nonverifiable_put_raw(state_key::compact_block(height).into_bytes(), encoding);
 
         Ok(())
     }
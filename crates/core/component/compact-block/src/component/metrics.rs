@@ -28,10 +28,93 @@ pub fn register_metrics() {
         Unit::Count,
         "The total number of compact blocks served to clients"
     );
+
+    describe_histogram!(
+        COMPACT_BLOCK_STATE_PAYLOADS,
+        Unit::Count,
+        "The number of state payloads in each compact block"
+    );
+
+    describe_histogram!(
+        COMPACT_BLOCK_NULLIFIERS,
+        Unit::Count,
+        "The number of nullifiers in each compact block"
+    );
+
+    describe_histogram!(
+        COMPACT_BLOCK_SWAP_OUTPUTS,
+        Unit::Count,
+        "The number of batch swap outputs in each compact block"
+    );
+
+    describe_histogram!(
+        COMPACT_BLOCK_SIZE_BYTES,
+        Unit::Bytes,
+        "The serialized size of each compact block"
+    );
 }
 
+/// Record the statistics of a newly built compact block.
+pub fn record_compact_block_stats(stats: &crate::CompactBlockStats) {
+    histogram!(COMPACT_BLOCK_STATE_PAYLOADS).record(stats.note_commitment_count as f64);
+    histogram!(COMPACT_BLOCK_NULLIFIERS).record(stats.nullifier_count as f64);
+    histogram!(COMPACT_BLOCK_SWAP_OUTPUTS).record(stats.swap_output_count as f64);
+    histogram!(COMPACT_BLOCK_SIZE_BYTES).record(stats.serialized_bytes as f64);
+}
+
+/// Buckets for the number of items in a compact block.
+const COMPACT_BLOCK_COUNT_BUCKETS: &[f64; 10] =
+    &[0.0, 1.0, 2.0, 5.0, 10.0, 20.0, 50.0, 100.0, 200.0, 500.0];
+
+/// Buckets for the serialized size of a compact block, in bytes.
+const COMPACT_BLOCK_SIZE_BUCKETS: &[f64; 10] = &[
+    64.0, 256.0, 1024.0, 4096.0, 16384.0, 65536.0, 262144.0, 1048576.0, 4194304.0, 16777216.0,
+];
+
 pub const COMPACT_BLOCK_RANGE_ACTIVE_CONNECTIONS: &str =
     "cnidarium_component_compact_block_compact_block_range_active_connections";
 
 pub const COMPACT_BLOCK_RANGE_SERVED_TOTAL: &str =
     "cnidarium_component_compact_block_compact_block_range_served_total";
+
+pub const COMPACT_BLOCK_STATE_PAYLOADS: &str = "cnidarium_component_compact_block_state_payloads";
+pub const COMPACT_BLOCK_NULLIFIERS: &str = "cnidarium_component_compact_block_nullifiers";
+pub const COMPACT_BLOCK_SWAP_OUTPUTS: &str = "cnidarium_component_compact_block_swap_outputs";
+pub const COMPACT_BLOCK_SIZE_BYTES: &str = "cnidarium_component_compact_block_size_bytes";
+
+/// An extension trait providing compact block interfaces for [`PrometheusBuilder`].
+///
+/// [builder]: metrics_exporter_prometheus::PrometheusBuilder
+pub trait PrometheusBuilderExt
+where
+    Self: Sized,
+{
+    /// Configure buckets for histogram metrics.
+    fn set_buckets_for_compact_block_metrics(
+        self,
+    ) -> Result<Self, metrics_exporter_prometheus::BuildError>;
+}
+
+impl PrometheusBuilderExt for metrics_exporter_prometheus::PrometheusBuilder {
+    fn set_buckets_for_compact_block_metrics(
+        self,
+    ) -> Result<Self, metrics_exporter_prometheus::BuildError> {
+        use metrics_exporter_prometheus::Matcher::Full;
+        self.set_buckets_for_metric(
+            Full(COMPACT_BLOCK_STATE_PAYLOADS.to_owned()),
+            COMPACT_BLOCK_COUNT_BUCKETS,
+        )?
+        .set_buckets_for_metric(
+            Full(COMPACT_BLOCK_NULLIFIERS.to_owned()),
+            COMPACT_BLOCK_COUNT_BUCKETS,
+        )?
+        .set_buckets_for_metric(
+            Full(COMPACT_BLOCK_SWAP_OUTPUTS.to_owned()),
+            COMPACT_BLOCK_COUNT_BUCKETS,
+        )?
+        .set_buckets_for_metric(
+            Full(COMPACT_BLOCK_SIZE_BYTES.to_owned()),
+            COMPACT_BLOCK_SIZE_BUCKETS,
+        )
+    }
+}
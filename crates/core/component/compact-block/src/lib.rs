@@ -10,6 +10,10 @@ pub mod state_key;
 
 mod compact_block;
 mod state_payload;
+mod stats;
+mod swap_claim_scan;
 
 pub use compact_block::CompactBlock;
 pub use state_payload::{StatePayload, StatePayloadDebugKind};
+pub use stats::CompactBlockStats;
+pub use swap_claim_scan::{ScanSwapClaims, SwapClaimRecord, SwapClaimScanner};
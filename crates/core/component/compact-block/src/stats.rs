@@ -0,0 +1,89 @@
+use crate::CompactBlock;
+
+/// Summary statistics about the size of a [`CompactBlock`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CompactBlockStats {
+    pub height: u64,
+    /// The number of state payloads, one for each commitment added to the SCT in the block.
+    pub note_commitment_count: u32,
+    pub nullifier_count: u32,
+    /// The number of trading pairs with batch swap outputs in the block.
+    pub swap_output_count: u32,
+    /// The length of the block's protobuf encoding, as served to clients.
+    pub serialized_bytes: usize,
+}
+
+impl CompactBlockStats {
+    /// Summarize `block`, given `encoding`, its protobuf encoding.
+    ///
+    /// The caller already needs the encoding to store or serve the block, so it's passed in
+    /// rather than encoding the block again.
+    pub fn new(block: &CompactBlock, encoding: &[u8]) -> Self {
+        // A block can't contain anywhere near u32::MAX of anything, since the SCT only has room
+        // for 2^16 commitments per block.
+        let count = |n: usize| u32::try_from(n).unwrap_or(u32::MAX);
+        Self {
+            height: block.height,
+            note_commitment_count: count(block.state_payloads.len()),
+            nullifier_count: count(block.nullifiers.len()),
+            swap_output_count: count(block.swap_outputs.len()),
+            serialized_bytes: encoding.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use penumbra_proto::DomainType;
+    use penumbra_tct::StateCommitment;
+
+    use super::*;
+
+    #[test]
+    fn empty_block_stats() {
+        let block = CompactBlock {
+            height: 7,
+            ..Default::default()
+        };
+        let encoding = block.encode_to_vec();
+        let stats = CompactBlockStats::new(&block, &encoding);
+        assert_eq!(
+            stats,
+            CompactBlockStats {
+                height: 7,
+                note_commitment_count: 0,
+                nullifier_count: 0,
+                swap_output_count: 0,
+                serialized_bytes: encoding.len(),
+            }
+        );
+        assert!(stats.serialized_bytes < 32);
+    }
+
+    #[test]
+    fn block_with_100_commitments() {
+        let state_payloads = (0..100u8)
+            .map(|i| {
+                let mut bytes = [0u8; 32];
+                bytes[0] = i;
+                StateCommitment::try_from(bytes)
+                    .expect("small values are canonical")
+                    .into()
+            })
+            .collect();
+        let block = CompactBlock {
+            height: 8,
+            state_payloads,
+            ..Default::default()
+        };
+        let encoding = block.encode_to_vec();
+        let stats = CompactBlockStats::new(&block, &encoding);
+        assert_eq!(stats.height, 8);
+        assert_eq!(stats.note_commitment_count, 100);
+        assert_eq!(stats.nullifier_count, 0);
+        assert_eq!(stats.swap_output_count, 0);
+        // Each rolled up payload carries at least its 32-byte commitment.
+        assert!(stats.serialized_bytes >= 100 * 32);
+        assert_eq!(stats.serialized_bytes, encoding.len());
+    }
+}
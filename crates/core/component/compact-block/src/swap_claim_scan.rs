@@ -0,0 +1,190 @@
+use penumbra_dex::TradingPair;
+use penumbra_keys::keys::OutgoingViewingKey;
+use penumbra_num::Amount;
+use penumbra_tct::StateCommitment;
+
+use crate::{CompactBlock, StatePayload};
+
+/// A swap made with an [`OutgoingViewingKey`], together with the outputs it can claim.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SwapClaimRecord {
+    /// The commitment to the swap.
+    pub commitment: StateCommitment,
+    /// The height at which the swap was executed.
+    pub height: u64,
+    /// The amount of the pair's first asset the swap claims.
+    pub output_1: Amount,
+    /// The amount of the pair's second asset the swap claims.
+    pub output_2: Amount,
+    pub pair: TradingPair,
+}
+
+/// Finds historical swaps, and so the swap claims they entitle, using only an outgoing viewing
+/// key.
+pub trait ScanSwapClaims {
+    /// Find the swaps in `blocks` that were made with this key.
+    fn scan_swap_claims(&self, blocks: &[CompactBlock]) -> Vec<SwapClaimRecord>;
+}
+
+impl ScanSwapClaims for OutgoingViewingKey {
+    fn scan_swap_claims(&self, blocks: &[CompactBlock]) -> Vec<SwapClaimRecord> {
+        blocks
+            .iter()
+            .flat_map(|block| scan_block(self, block))
+            .collect()
+    }
+}
+
+/// Scans compact blocks one at a time as they arrive, accumulating the swaps made with an
+/// outgoing viewing key.
+#[derive(Clone, Debug)]
+pub struct SwapClaimScanner {
+    ovk: OutgoingViewingKey,
+    records: Vec<SwapClaimRecord>,
+}
+
+impl SwapClaimScanner {
+    pub fn new(ovk: OutgoingViewingKey) -> Self {
+        Self {
+            ovk,
+            records: Vec::new(),
+        }
+    }
+
+    /// Scan the next block, returning the swaps found in it.
+    pub fn scan_block(&mut self, block: &CompactBlock) -> &[SwapClaimRecord] {
+        let start = self.records.len();
+        self.records.extend(scan_block(&self.ovk, block));
+        &self.records[start..]
+    }
+
+    /// Every swap found so far, in the order the blocks were scanned.
+    pub fn records(&self) -> &[SwapClaimRecord] {
+        &self.records
+    }
+}
+
+fn scan_block<'a>(
+    ovk: &'a OutgoingViewingKey,
+    block: &'a CompactBlock,
+) -> impl Iterator<Item = SwapClaimRecord> + 'a {
+    block.state_payloads.iter().filter_map(move |payload| {
+        let StatePayload::Swap { swap, .. } = payload else {
+            return None;
+        };
+        // Swaps made with other keys won't decrypt.
+        let plaintext = swap.encrypted_swap.decrypt(ovk, swap.commitment).ok()?;
+        if plaintext.swap_commitment() != swap.commitment {
+            tracing::warn!(swap_commitment = ?swap.commitment, "decrypted swap does not match its commitment");
+            return None;
+        }
+        let Some(output_data) = block.swap_outputs.get(&plaintext.trading_pair) else {
+            tracing::warn!(height = block.height, pair = ?plaintext.trading_pair, "no batch swap output data for swap");
+            return None;
+        };
+        let (output_1, output_2) =
+            output_data.pro_rata_outputs((plaintext.delta_1_i, plaintext.delta_2_i));
+        Some(SwapClaimRecord {
+            commitment: swap.commitment,
+            height: block.height,
+            output_1,
+            output_2,
+            pair: plaintext.trading_pair,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use penumbra_asset::asset;
+    use penumbra_dex::{swap::SwapPlaintext, BatchSwapOutputData};
+    use penumbra_fee::Fee;
+    use penumbra_keys::{
+        keys::{Bip44Path, SeedPhrase, SpendKey},
+        Address,
+    };
+    use penumbra_sct::CommitmentSource;
+    use rand_core::OsRng;
+
+    use super::*;
+
+    fn ovk() -> OutgoingViewingKey {
+        SpendKey::from_seed_phrase_bip44(SeedPhrase::generate(OsRng), &Bip44Path::new(0))
+            .outgoing_viewing_key()
+            .clone()
+    }
+
+    /// Five blocks of ten swaps each, every fifth of which is made with `ours`.
+    fn blocks(ours: &OutgoingViewingKey) -> Vec<CompactBlock> {
+        let theirs = ovk();
+        let cache = asset::Cache::with_known_assets();
+        let pair = TradingPair::new(
+            cache.get_unit("upenumbra").unwrap().id(),
+            cache.get_unit("nala").unwrap().id(),
+        );
+
+        (1..=5u64)
+            .map(|height| {
+                let state_payloads = (0..10)
+                    .map(|i| {
+                        let swap = SwapPlaintext::new(
+                            &mut OsRng,
+                            pair,
+                            100u64.into(),
+                            0u64.into(),
+                            Fee::default(),
+                            Address::dummy(&mut OsRng),
+                        );
+                        let ovk = if i % 5 == 0 { ours } else { &theirs };
+                        StatePayload::Swap {
+                            source: CommitmentSource::transaction(),
+                            swap: Box::new(swap.encrypt(ovk)),
+                        }
+                    })
+                    .collect();
+                let output_data = BatchSwapOutputData {
+                    delta_1: 1_000u64.into(),
+                    delta_2: 0u64.into(),
+                    lambda_1: 0u64.into(),
+                    lambda_2: 2_000u64.into(),
+                    unfilled_1: 0u64.into(),
+                    unfilled_2: 0u64.into(),
+                    height,
+                    trading_pair: pair,
+                    sct_position_prefix: Default::default(),
+                };
+                CompactBlock {
+                    height,
+                    state_payloads,
+                    swap_outputs: BTreeMap::from([(pair, output_data)]),
+                    ..Default::default()
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn only_swaps_made_with_the_ovk_are_found() {
+        let ours = ovk();
+        let blocks = blocks(&ours);
+
+        let records = ours.scan_swap_claims(&blocks);
+        assert_eq!(records.len(), 10);
+        for record in &records {
+            assert_eq!(record.output_1, 0u64.into());
+            // Each swap is a tenth of the batch, so it's owed 200, but 1/10 has no exact
+            // fixed-point representation, so the share rounds down a unit.
+            assert_eq!(record.output_2, 199u64.into());
+        }
+
+        let mut scanner = SwapClaimScanner::new(ours);
+        for block in &blocks {
+            let found = scanner.scan_block(block);
+            assert_eq!(found.len(), 2);
+            assert!(found.iter().all(|record| record.height == block.height));
+        }
+        assert_eq!(scanner.records(), &records[..]);
+    }
+}
@@ -348,6 +348,7 @@ mod tests {
     use decaf377::Bls12_377;
     use penumbra_asset::asset;
     use penumbra_proof_params::{generate_test_parameters, DummyWitness};
+    use proptest::prelude::*;
     use rand_core::OsRng;
 
     use super::*;
@@ -388,6 +389,222 @@ mod tests {
         assert_eq!(lambda_2_i, Amount::from(28766268u64));
     }
 
+    /// A batch with the given totals, on an arbitrary trading pair.
+    fn batch(
+        (delta_1, delta_2): (u128, u128),
+        (lambda_1, lambda_2): (u128, u128),
+        (unfilled_1, unfilled_2): (u128, u128),
+    ) -> BatchSwapOutputData {
+        let cache = asset::Cache::with_known_assets();
+        let gm = cache.get_unit("gm").unwrap();
+        let gn = cache.get_unit("gn").unwrap();
+        BatchSwapOutputData {
+            delta_1: delta_1.into(),
+            delta_2: delta_2.into(),
+            lambda_1: lambda_1.into(),
+            lambda_2: lambda_2.into(),
+            unfilled_1: unfilled_1.into(),
+            unfilled_2: unfilled_2.into(),
+            height: 0,
+            trading_pair: TradingPair::new(gm.id(), gn.id()),
+            sct_position_prefix: 0u64.into(),
+        }
+    }
+
+    /// A batch whose inputs are the sums of the inputs of `swaps`.
+    fn batch_of(
+        swaps: &[(u128, u128)],
+        lambdas: (u128, u128),
+        unfilled: (u128, u128),
+    ) -> BatchSwapOutputData {
+        let delta_1 = swaps.iter().map(|&(delta_1_i, _)| delta_1_i).sum();
+        let delta_2 = swaps.iter().map(|&(_, delta_2_i)| delta_2_i).sum();
+        batch((delta_1, delta_2), lambdas, unfilled)
+    }
+
+    /// The outputs of a swap, as plain integers.
+    fn outputs(bsod: &BatchSwapOutputData, (delta_1_i, delta_2_i): (u128, u128)) -> (u128, u128) {
+        let (lambda_1_i, lambda_2_i) = bsod.pro_rata_outputs((delta_1_i.into(), delta_2_i.into()));
+        (lambda_1_i.value(), lambda_2_i.value())
+    }
+
+    /// The exact pro rata outputs of a swap, rounded down, computed with integer arithmetic.
+    ///
+    /// The products only fit in a `u128` for amounts of up to about 32 bits.
+    fn exact_outputs(
+        bsod: &BatchSwapOutputData,
+        (delta_1_i, delta_2_i): (u128, u128),
+    ) -> (u128, u128) {
+        // A leg no one swapped into contributes nothing, which is a share of 0/1.
+        let share = |delta_i: u128, delta: Amount| match delta.value() {
+            0 => (0, 1),
+            delta => (delta_i, delta),
+        };
+        let (n_1, d_1) = share(delta_1_i, bsod.delta_1);
+        let (n_2, d_2) = share(delta_2_i, bsod.delta_2);
+        let combine = |from_1: Amount, from_2: Amount| {
+            (n_1 * from_1.value() * d_2 + n_2 * from_2.value() * d_1) / (d_1 * d_2)
+        };
+        (
+            combine(bsod.unfilled_1, bsod.lambda_1),
+            combine(bsod.lambda_2, bsod.unfilled_2),
+        )
+    }
+
+    /// Check that the outputs of a swap are its exact pro rata share, less at most one unit.
+    ///
+    /// The share of the batch input is truncated to a fixed-point fraction before it's multiplied
+    /// by the batch output, so a share that's exactly an integer can come out one unit short. It
+    /// can never come out above the exact share.
+    fn assert_pro_rata(bsod: &BatchSwapOutputData, swap: (u128, u128)) -> (u128, u128) {
+        let (lambda_1_i, lambda_2_i) = outputs(bsod, swap);
+        let (exact_1, exact_2) = exact_outputs(bsod, swap);
+        assert!(
+            lambda_1_i <= exact_1 && exact_1 <= lambda_1_i + 1,
+            "swap {swap:?} got {lambda_1_i} of asset 1, expected {exact_1}"
+        );
+        assert!(
+            lambda_2_i <= exact_2 && exact_2 <= lambda_2_i + 1,
+            "swap {swap:?} got {lambda_2_i} of asset 2, expected {exact_2}"
+        );
+        (lambda_1_i, lambda_2_i)
+    }
+
+    #[test]
+    fn thousand_swaps_get_their_exact_share() {
+        // 576 * 1049 + 424 * 1048 = 2^20, so each swap's share of the input is exact in fixed
+        // point, and every output is an exact multiple of its input.
+        let swaps = [vec![(1049, 0); 576], vec![(1048, 0); 424]].concat();
+        assert_eq!(swaps.len(), 1000);
+        let bsod = batch_of(&swaps, (0, 3 << 20), (1 << 20, 0));
+
+        let mut total = (0, 0);
+        for &swap in &swaps {
+            let (lambda_1_i, lambda_2_i) = outputs(&bsod, swap);
+            assert_eq!((lambda_1_i, lambda_2_i), (swap.0, 3 * swap.0));
+            total = (total.0 + lambda_1_i, total.1 + lambda_2_i);
+        }
+        assert_eq!(total, (1 << 20, 3 << 20));
+    }
+
+    #[test]
+    fn integral_shares_of_inexact_fractions_round_down_a_unit() {
+        // 1/1000 has no exact fixed-point representation, so an even split of 20000 among 1000
+        // swaps pays out 19 each, rather than 20.
+        let swaps = vec![(10, 0); 1000];
+        let bsod = batch_of(&swaps, (0, 20_000), (0, 0));
+        for &swap in &swaps {
+            assert_eq!(assert_pro_rata(&bsod, swap), (0, 19));
+        }
+    }
+
+    #[test]
+    fn prime_total_input_rounds_every_share_down() {
+        let swaps = [(1, 0), (2, 0), (500, 0), (506, 0)];
+        // Both the total input, 1009, and the total output are prime, so no share is integral.
+        let bsod = batch_of(&swaps, (0, 10_007), (0, 0));
+        assert_eq!(bsod.delta_1, 1009u64.into());
+
+        let shares: Vec<u128> = swaps
+            .iter()
+            .map(|&swap| assert_pro_rata(&bsod, swap).1)
+            .collect();
+        assert_eq!(shares, [9, 19, 4958, 5018]);
+        assert!(shares.iter().sum::<u128>() <= 10_007);
+    }
+
+    #[test]
+    fn dominant_swap_gets_its_share_of_the_output() {
+        // One swap contributes 99% of the input, and 100 others the remaining 1%.
+        let mut swaps = vec![(9_900, 0)];
+        swaps.extend([(1, 0); 100]);
+        let bsod = batch_of(&swaps, (0, 7_919), (0, 0));
+
+        // 99% of 7919 is 7839.81.
+        assert_eq!(assert_pro_rata(&bsod, swaps[0]), (0, 7_839));
+        for &swap in &swaps[1..] {
+            assert_eq!(assert_pro_rata(&bsod, swap), (0, 0));
+        }
+    }
+
+    #[test]
+    fn zero_input_gets_nothing_from_that_leg() {
+        let bsod = batch((100, 300), (120, 50), (10, 30));
+
+        // A swap of only asset 2 gets nothing from the 1 => 2 leg, so the same outputs as if that
+        // leg had produced nothing at all.
+        let leg_2_only = batch((100, 300), (120, 0), (0, 30));
+        assert_eq!(outputs(&bsod, (0, 150)), outputs(&leg_2_only, (0, 150)));
+        assert_eq!(assert_pro_rata(&bsod, (0, 150)), (60, 15));
+
+        // And a swap of only asset 1 gets nothing from the 2 => 1 leg.
+        assert_eq!(assert_pro_rata(&bsod, (50, 0)), (5, 25));
+        assert_eq!(outputs(&bsod, (0, 0)), (0, 0));
+
+        // If no one swapped asset 2, no one gets anything from that leg, rather than dividing by
+        // zero.
+        let no_asset_2 = batch((100, 0), (0, 50), (10, 0));
+        assert_eq!(assert_pro_rata(&no_asset_2, (100, 0)), (10, 50));
+    }
+
+    /// Swaps of up to 32 bits of each asset, so that `exact_outputs` can't overflow.
+    fn arb_swaps() -> impl Strategy<Value = Vec<(u128, u128)>> {
+        prop::collection::vec(
+            (any::<u32>(), any::<u32>()).prop_map(|(d1, d2)| (u128::from(d1), u128::from(d2))),
+            1..50,
+        )
+    }
+
+    /// A batch of `swaps`, with arbitrary outputs of up to 32 bits.
+    fn arb_batch() -> impl Strategy<Value = (Vec<(u128, u128)>, BatchSwapOutputData)> {
+        (arb_swaps(), any::<[u32; 4]>()).prop_map(|(swaps, [l1, l2, u1, u2])| {
+            let bsod = batch_of(&swaps, (l1.into(), l2.into()), (u1.into(), u2.into()));
+            (swaps, bsod)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn every_swap_gets_its_pro_rata_share((swaps, bsod) in arb_batch()) {
+            for &swap in &swaps {
+                assert_pro_rata(&bsod, swap);
+            }
+        }
+
+        #[test]
+        fn pro_rata_outputs_never_exceed_the_batch_outputs((swaps, bsod) in arb_batch()) {
+            let (total_1, total_2) = swaps
+                .iter()
+                .map(|&swap| outputs(&bsod, swap))
+                .fold((0, 0), |(t1, t2), (o1, o2)| (t1 + o1, t2 + o2));
+            prop_assert!(total_1 <= bsod.lambda_1.value() + bsod.unfilled_1.value());
+            prop_assert!(total_2 <= bsod.lambda_2.value() + bsod.unfilled_2.value());
+        }
+
+        #[test]
+        fn zero_input_legs_contribute_nothing((_swaps, bsod) in arb_batch(), delta_2_i in any::<u32>()) {
+            // Zero the outputs of the 1 => 2 leg, leaving the inputs alone.
+            let leg_2_only = BatchSwapOutputData {
+                lambda_2: 0u64.into(),
+                unfilled_1: 0u64.into(),
+                ..bsod
+            };
+            let delta_2_i = u128::from(delta_2_i).min(bsod.delta_2.value());
+            prop_assert_eq!(outputs(&bsod, (0, delta_2_i)), outputs(&leg_2_only, (0, delta_2_i)));
+            prop_assert_eq!(outputs(&bsod, (0, 0)), (0, 0));
+        }
+
+        #[test]
+        fn dominant_swap_gets_99_percent(rest in 1u32..1_000_000, lambda_2 in any::<u32>()) {
+            let rest = u128::from(rest);
+            let dominant = (99 * rest, 0);
+            let bsod = batch_of(&[dominant, (rest, 0)], (0, lambda_2.into()), (0, 0));
+            let (_, lambda_2_i) = assert_pro_rata(&bsod, dominant);
+            prop_assert!(lambda_2_i <= u128::from(lambda_2) * 99 / 100);
+            prop_assert!(lambda_2_i + 1 >= u128::from(lambda_2) * 99 / 100);
+        }
+    }
+
     struct ProRataOutputCircuit {
         delta_1_i: Amount,
         delta_2_i: Amount,
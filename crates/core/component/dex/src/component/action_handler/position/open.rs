@@ -35,6 +35,9 @@ impl ActionHandler for PositionOpen {
             dex_params.is_enabled,
             "Dex MUST be enabled to open positions."
         );
+        dex_params
+            .fee_tiers
+            .validate_fee_bps(self.position.phi.component.fee)?;
 
         state.open_position(self.position.clone()).await?;
         Ok(())
@@ -51,6 +51,7 @@ impl ActionHandler for Swap {
         state
             .accumulate_swap_flow(&swap.body.trading_pair, flow.into())
             .await?;
+        state.increment_swap_count(&swap.body.trading_pair);
 
         // Record the swap commitment in the state.
         let source = state.get_current_source().expect("source is set");
@@ -404,6 +404,10 @@ pub(crate) trait InternalDexWrite: StateWrite {
             swap_execution_1_for_2,
             swap_execution_2_for_1,
         ));
+        self.record_proto(
+            event::SwapSettlementEvent::from_batch(&output_data, self.swap_count(&trading_pair))
+                .to_protobuf(),
+        );
 
         Ok(())
     }
@@ -52,6 +52,15 @@ pub trait SwapDataRead: StateRead {
             .unwrap_or_default()
     }
 
+    /// Get the number of swaps submitted in the given trading pair in this block so far.
+    fn swap_count(&self, pair: &TradingPair) -> u32 {
+        self.object_get::<im::OrdMap<TradingPair, u32>>(state_key::swap_counts())
+            .unwrap_or_default()
+            .get(pair)
+            .copied()
+            .unwrap_or_default()
+    }
+
     fn pending_batch_swap_outputs(&self) -> im::OrdMap<TradingPair, BatchSwapOutputData> {
         self.object_get(state_key::pending_outputs())
             .unwrap_or_default()
@@ -96,6 +105,15 @@ pub(crate) trait SwapDataWrite: StateWrite {
         Ok(())
     }
 
+    /// Record that a swap was submitted in the given trading pair in this block.
+    fn increment_swap_count(&mut self, trading_pair: &TradingPair) {
+        let counts = self
+            .object_get::<im::OrdMap<TradingPair, u32>>(state_key::swap_counts())
+            .unwrap_or_default()
+            .alter(|count| Some(count.unwrap_or_default() + 1), *trading_pair);
+        self.object_put(state_key::swap_counts(), counts);
+    }
+
     fn put_swap_execution_at_height(
         &mut self,
         height: u64,
@@ -108,3 +126,50 @@ pub(crate) trait SwapDataWrite: StateWrite {
 }
 
 impl<T: StateWrite + ?Sized> SwapDataWrite for T {}
+
+#[cfg(test)]
+mod tests {
+    use cnidarium::{StateDelta, TempStorage};
+    use penumbra_asset::asset::REGISTRY;
+
+    use super::*;
+    use crate::event::SwapSettlementEvent;
+
+    #[tokio::test]
+    /// Test that the swaps in each trading pair are counted separately, and summarized in the
+    /// pair's settlement event.
+    async fn swaps_are_counted_per_pair() -> anyhow::Result<()> {
+        let penumbra = REGISTRY.parse_denom("upenumbra").unwrap().id();
+        let gm = REGISTRY.parse_denom("gm").unwrap().id();
+        let gn = REGISTRY.parse_denom("gn").unwrap().id();
+        let pair_1 = TradingPair::new(penumbra, gm);
+        let pair_2 = TradingPair::new(penumbra, gn);
+
+        let storage = TempStorage::new().await?;
+        let mut delta = StateDelta::new(storage.latest_snapshot());
+        assert_eq!(delta.swap_count(&pair_1), 0);
+
+        for _ in 0..3 {
+            delta.increment_swap_count(&pair_1);
+        }
+        delta.increment_swap_count(&pair_2);
+        assert_eq!(delta.swap_count(&pair_1), 3);
+        assert_eq!(delta.swap_count(&pair_2), 1);
+
+        let bsod = BatchSwapOutputData {
+            delta_1: 0u64.into(),
+            delta_2: 0u64.into(),
+            lambda_1: 0u64.into(),
+            lambda_2: 0u64.into(),
+            unfilled_1: 0u64.into(),
+            unfilled_2: 0u64.into(),
+            height: 1,
+            trading_pair: pair_1,
+            sct_position_prefix: Default::default(),
+        };
+        let event = SwapSettlementEvent::from_batch(&bsod, delta.swap_count(&pair_1));
+        assert_eq!(event.participant_count, 3);
+
+        Ok(())
+    }
+}
@@ -5,13 +5,13 @@ use crate::{
     },
     swap::Swap,
     swap_claim::SwapClaim,
-    BatchSwapOutputData, CandlestickData, DirectedTradingPair, SwapExecution,
+    BatchSwapOutputData, CandlestickData, DirectedTradingPair, SwapExecution, TradingPair,
 };
 use anyhow::{anyhow, Context};
 use prost::Name;
 
 use penumbra_asset::asset;
-use penumbra_num::Amount;
+use penumbra_num::{fixpoint::U128x128, Amount};
 use penumbra_proto::{penumbra::core::component::dex::v1 as pb, DomainType};
 
 pub fn swap(swap: &Swap) -> pb::EventSwap {
@@ -170,3 +170,180 @@ impl From<EventCandlestickData> for pb::EventCandlestickData {
 impl DomainType for EventCandlestickData {
     type Proto = pb::EventCandlestickData;
 }
+
+/// A summary of how the batch swap in a trading pair settled.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SwapSettlementEvent {
+    pub pair: TradingPair,
+    pub aggregate_input_1: Amount,
+    pub aggregate_input_2: Amount,
+    /// The total amount of asset 1 paid out, both as output of 2=>1 trades and as unfilled
+    /// input of 1=>2 trades.
+    pub aggregate_output_1: Amount,
+    /// The total amount of asset 2 paid out, both as output of 1=>2 trades and as unfilled
+    /// input of 2=>1 trades.
+    pub aggregate_output_2: Amount,
+    /// The price of asset 1 in terms of asset 2, if anything was filled.
+    pub clearing_price: Option<U128x128>,
+    pub participant_count: u32,
+    pub block_height: u64,
+}
+
+impl SwapSettlementEvent {
+    /// Summarize a batch swap made up of `participant_count` individual swaps.
+    pub fn from_batch(bsod: &BatchSwapOutputData, participant_count: u32) -> Self {
+        let filled_1 = bsod.delta_1.saturating_sub(&bsod.unfilled_1);
+        let filled_2 = bsod.delta_2.saturating_sub(&bsod.unfilled_2);
+        // Quote the price from the 1=>2 trades if there were any, falling back to the 2=>1 ones.
+        let clearing_price = if filled_1 != Amount::zero() {
+            U128x128::ratio(bsod.lambda_2, filled_1).ok()
+        } else {
+            U128x128::ratio(filled_2, bsod.lambda_1).ok()
+        };
+
+        Self {
+            pair: bsod.trading_pair,
+            aggregate_input_1: bsod.delta_1,
+            aggregate_input_2: bsod.delta_2,
+            aggregate_output_1: bsod.lambda_1 + bsod.unfilled_1,
+            aggregate_output_2: bsod.lambda_2 + bsod.unfilled_2,
+            clearing_price,
+            participant_count,
+            block_height: bsod.height,
+        }
+    }
+
+    pub fn to_protobuf(&self) -> pb::EventSwapSettlement {
+        self.clone().into()
+    }
+}
+
+impl TryFrom<pb::EventSwapSettlement> for SwapSettlementEvent {
+    type Error = anyhow::Error;
+
+    fn try_from(value: pb::EventSwapSettlement) -> Result<Self, Self::Error> {
+        fn inner(value: pb::EventSwapSettlement) -> anyhow::Result<SwapSettlementEvent> {
+            let clearing_price = match value.clearing_price.len() {
+                0 => None,
+                _ => Some(U128x128::from_bytes(
+                    value.clearing_price[..]
+                        .try_into()
+                        .map_err(|_| anyhow!("`clearing_price` must be 32 bytes"))?,
+                )),
+            };
+            Ok(SwapSettlementEvent {
+                pair: value
+                    .trading_pair
+                    .ok_or(anyhow!("missing `trading_pair`"))?
+                    .try_into()?,
+                aggregate_input_1: value
+                    .aggregate_input_1
+                    .ok_or(anyhow!("missing `aggregate_input_1`"))?
+                    .try_into()?,
+                aggregate_input_2: value
+                    .aggregate_input_2
+                    .ok_or(anyhow!("missing `aggregate_input_2`"))?
+                    .try_into()?,
+                aggregate_output_1: value
+                    .aggregate_output_1
+                    .ok_or(anyhow!("missing `aggregate_output_1`"))?
+                    .try_into()?,
+                aggregate_output_2: value
+                    .aggregate_output_2
+                    .ok_or(anyhow!("missing `aggregate_output_2`"))?
+                    .try_into()?,
+                clearing_price,
+                participant_count: value.participant_count,
+                block_height: value.block_height,
+            })
+        }
+        inner(value).context(format!("parsing {}", pb::EventSwapSettlement::NAME))
+    }
+}
+
+impl From<SwapSettlementEvent> for pb::EventSwapSettlement {
+    fn from(value: SwapSettlementEvent) -> Self {
+        Self {
+            trading_pair: Some(value.pair.into()),
+            aggregate_input_1: Some(value.aggregate_input_1.into()),
+            aggregate_input_2: Some(value.aggregate_input_2.into()),
+            aggregate_output_1: Some(value.aggregate_output_1.into()),
+            aggregate_output_2: Some(value.aggregate_output_2.into()),
+            clearing_price: value
+                .clearing_price
+                .map(|price| price.to_bytes().to_vec())
+                .unwrap_or_default(),
+            participant_count: value.participant_count,
+            block_height: value.block_height,
+        }
+    }
+}
+
+impl DomainType for SwapSettlementEvent {
+    type Proto = pb::EventSwapSettlement;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settlement_aggregates_individual_swaps() {
+        let pair = TradingPair::new(
+            asset::Cache::with_known_assets()
+                .get_unit("gm")
+                .unwrap()
+                .id(),
+            asset::Cache::with_known_assets()
+                .get_unit("gn")
+                .unwrap()
+                .id(),
+        );
+        // Three swaps of asset 1 for asset 2, and two the other way.
+        let swaps: Vec<(Amount, Amount)> = [(100, 0), (250, 0), (50, 0), (0, 300), (0, 100)]
+            .into_iter()
+            .map(|(delta_1, delta_2): (u64, u64)| (delta_1.into(), delta_2.into()))
+            .collect();
+        let (delta_1, delta_2) = swaps.iter().fold(
+            (Amount::zero(), Amount::zero()),
+            |(total_1, total_2), (delta_1, delta_2)| (total_1 + *delta_1, total_2 + *delta_2),
+        );
+
+        // All of asset 1 is filled at a price of 2, while half of asset 2 is left unfilled.
+        let bsod = BatchSwapOutputData {
+            delta_1,
+            delta_2,
+            lambda_1: 100u64.into(),
+            lambda_2: 800u64.into(),
+            unfilled_1: 0u64.into(),
+            unfilled_2: 200u64.into(),
+            height: 42,
+            trading_pair: pair,
+            sct_position_prefix: Default::default(),
+        };
+
+        let event = SwapSettlementEvent::from_batch(&bsod, swaps.len().try_into().unwrap());
+        assert_eq!(event.pair, pair);
+        assert_eq!(event.aggregate_input_1, 400u64.into());
+        assert_eq!(event.aggregate_input_2, 400u64.into());
+        assert_eq!(event.aggregate_output_1, 100u64.into());
+        assert_eq!(event.aggregate_output_2, 1000u64.into());
+        assert_eq!(event.clearing_price, Some(U128x128::from(2u64)));
+        assert_eq!(event.participant_count, 5);
+        assert_eq!(event.block_height, 42);
+
+        // The individual swaps' outputs never exceed what the batch paid out.
+        let (output_1, output_2) = swaps.iter().fold(
+            (Amount::zero(), Amount::zero()),
+            |(total_1, total_2), swap| {
+                let (lambda_1_i, lambda_2_i) = bsod.pro_rata_outputs(*swap);
+                (total_1 + lambda_1_i, total_2 + lambda_2_i)
+            },
+        );
+        assert!(output_1 <= event.aggregate_output_1);
+        assert!(output_2 <= event.aggregate_output_2);
+
+        let decoded = SwapSettlementEvent::try_from(event.to_protobuf()).unwrap();
+        assert_eq!(decoded, event);
+    }
+}
@@ -4,9 +4,11 @@ mod reserves;
 mod trading_function;
 
 pub mod action;
+pub mod fee_tiers;
 pub mod plan;
 pub mod position;
 
+pub use fee_tiers::FeeTiers;
 pub use nft::LpNft;
 pub use order::{BuyOrder, SellOrder};
 pub use reserves::Reserves;
@@ -0,0 +1,157 @@
+use anyhow::{ensure, Result};
+use penumbra_asset::Value;
+use penumbra_num::Amount;
+use serde::{Deserialize, Serialize};
+
+use crate::TradingPair;
+
+use super::position::{self, Position};
+
+/// The fees, in basis points, that liquidity positions are allowed to charge.
+///
+/// An empty set of tiers places no restriction on position fees, beyond the
+/// stateless check that the fee is at most 50%.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FeeTiers(pub Vec<u32>);
+
+impl FeeTiers {
+    /// The 1, 5, 30 and 100 bps tiers.
+    pub fn standard() -> Self {
+        Self(vec![1, 5, 30, 100])
+    }
+
+    /// Checks that a position fee of `bps` falls on one of the allowed tiers.
+    pub fn validate_fee_bps(&self, bps: u32) -> Result<()> {
+        ensure!(
+            self.0.is_empty() || self.0.contains(&bps),
+            "position fee of {bps} bps is not one of the allowed fee tiers {:?}",
+            self.0
+        );
+        Ok(())
+    }
+}
+
+/// Selects the position with the lowest fee that can fill a trade of `amount`
+/// units of the pair's `asset_1` in full.
+///
+/// Ties between positions on the same tier are broken in favor of the one
+/// giving the most output. Returns `None` if no open position on `pair` has
+/// enough reserves to fill the trade.
+pub fn route_to_cheapest_tier<'a>(
+    amount: u64,
+    pair: &TradingPair,
+    positions: &'a [Position],
+) -> Option<&'a Position> {
+    let input = Value {
+        amount: amount.into(),
+        asset_id: pair.asset_1(),
+    };
+
+    positions
+        .iter()
+        .filter(|position| position.state == position::State::Opened && position.phi.pair == *pair)
+        .filter_map(|position| {
+            let (unfilled, _, output) = position.phi.fill(input, &position.reserves).ok()?;
+            (unfilled.amount == Amount::zero()).then_some((position, output.amount))
+        })
+        .min_by(|(a, a_output), (b, b_output)| {
+            a.phi
+                .component
+                .fee
+                .cmp(&b.phi.component.fee)
+                .then_with(|| b_output.cmp(a_output))
+        })
+        .map(|(position, _)| position)
+}
+
+#[cfg(test)]
+mod tests {
+    use penumbra_asset::asset;
+    use rand_core::OsRng;
+
+    use super::*;
+    use crate::{lp::Reserves, DirectedTradingPair};
+
+    fn position_at_tier(pair: &TradingPair, fee: u32, r2: u64) -> Position {
+        Position::new(
+            OsRng,
+            DirectedTradingPair::new(pair.asset_1(), pair.asset_2()),
+            fee,
+            1u64.into(),
+            1u64.into(),
+            Reserves {
+                r1: 0u64.into(),
+                r2: r2.into(),
+            },
+        )
+    }
+
+    fn pair() -> TradingPair {
+        let cache = asset::Cache::with_known_assets();
+        TradingPair::new(
+            cache.get_unit("gm").unwrap().id(),
+            cache.get_unit("gn").unwrap().id(),
+        )
+    }
+
+    #[test]
+    fn validate_fee_bps_only_accepts_listed_tiers() {
+        let tiers = FeeTiers::standard();
+        for bps in [1, 5, 30, 100] {
+            assert!(tiers.validate_fee_bps(bps).is_ok());
+        }
+        assert!(tiers.validate_fee_bps(0).is_err());
+        assert!(tiers.validate_fee_bps(10).is_err());
+
+        // No tiers means any fee is allowed.
+        assert!(FeeTiers::default().validate_fee_bps(10).is_ok());
+    }
+
+    #[test]
+    fn routing_prefers_the_cheapest_tier_with_enough_liquidity() {
+        let pair = pair();
+        let positions = vec![
+            position_at_tier(&pair, 100, 1_000_000),
+            position_at_tier(&pair, 30, 1_000_000),
+            position_at_tier(&pair, 1, 1_000),
+            position_at_tier(&pair, 5, 100_000),
+        ];
+
+        // Small trades go to the 1 bps tier.
+        let routed = route_to_cheapest_tier(500, &pair, &positions).unwrap();
+        assert_eq!(routed.phi.component.fee, 1);
+
+        // The 1 bps position cannot fill this, so the 5 bps tier is used.
+        let routed = route_to_cheapest_tier(50_000, &pair, &positions).unwrap();
+        assert_eq!(routed.phi.component.fee, 5);
+
+        // Only the deepest positions can fill this; the 30 bps tier is cheaper.
+        let routed = route_to_cheapest_tier(500_000, &pair, &positions).unwrap();
+        assert_eq!(routed.phi.component.fee, 30);
+
+        // Nothing can fill a trade larger than every position.
+        assert!(route_to_cheapest_tier(10_000_000, &pair, &positions).is_none());
+    }
+
+    #[test]
+    fn routing_skips_closed_positions_and_other_pairs() {
+        let pair = pair();
+        let cache = asset::Cache::with_known_assets();
+        let other_pair = TradingPair::new(
+            cache.get_unit("gm").unwrap().id(),
+            cache.get_unit("test_usd").unwrap().id(),
+        );
+
+        let mut closed = position_at_tier(&pair, 1, 1_000_000);
+        closed.state = position::State::Closed;
+        let positions = vec![
+            closed,
+            position_at_tier(&other_pair, 1, 1_000_000),
+            position_at_tier(&pair, 30, 1_000_000),
+        ];
+
+        let routed = route_to_cheapest_tier(1_000, &pair, &positions).unwrap();
+        assert_eq!(routed.phi.component.fee, 30);
+        assert_eq!(routed.phi.pair, pair);
+    }
+}
@@ -1,6 +1,8 @@
 use penumbra_asset::{asset, STAKING_TOKEN_ASSET_ID};
 use penumbra_proto::penumbra::core::component::dex::v1 as pb;
 use penumbra_proto::DomainType;
+
+use crate::lp::FeeTiers;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -12,6 +14,7 @@ pub struct DexParameters {
     pub max_hops: u32,
     pub max_positions_per_pair: u32,
     pub max_execution_budget: u32,
+    pub fee_tiers: FeeTiers,
 }
 
 impl DomainType for DexParameters {
@@ -32,6 +35,7 @@ impl TryFrom<pb::DexParameters> for DexParameters {
             max_hops: msg.max_hops,
             max_positions_per_pair: msg.max_positions_per_pair,
             max_execution_budget: msg.max_execution_budget,
+            fee_tiers: FeeTiers(msg.fee_tiers),
         })
     }
 }
@@ -48,6 +52,7 @@ impl From<DexParameters> for pb::DexParameters {
             max_hops: params.max_hops,
             max_positions_per_pair: params.max_positions_per_pair,
             max_execution_budget: params.max_execution_budget,
+            fee_tiers: params.fee_tiers.0,
         }
     }
 }
@@ -72,6 +77,7 @@ impl Default for DexParameters {
             max_hops: 4,
             max_positions_per_pair: 1_000,
             max_execution_budget: 64,
+            fee_tiers: FeeTiers::default(),
         }
     }
 }
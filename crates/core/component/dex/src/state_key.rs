@@ -101,6 +101,10 @@ pub fn swap_flows() -> &'static str {
     "dex/swap_flows"
 }
 
+pub fn swap_counts() -> &'static str {
+    "dex/swap_counts"
+}
+
 pub fn pending_position_closures() -> &'static str {
     "dex/pending_position_closures"
 }
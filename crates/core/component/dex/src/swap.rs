@@ -3,18 +3,28 @@ use once_cell::sync::Lazy;
 
 mod action;
 mod ciphertext;
+mod confirmation;
+mod dedup;
 mod payload;
 mod plaintext;
 mod plan;
+mod priority;
+mod scanner;
+#[cfg(test)]
+mod testing;
 mod view;
 
 pub mod proof;
 
 pub use action::{Body, Swap};
 pub use ciphertext::SwapCiphertext;
+pub use confirmation::SwapConfirmation;
+pub use dedup::SwapDeduplicator;
 pub use payload::SwapPayload;
 pub use plaintext::{SwapPlaintext, SwapPlaintextVar};
 pub use plan::SwapPlan;
+pub use priority::PrioritizedSwapQueue;
+pub use scanner::SwapScanner;
 pub use view::SwapView;
 
 // Swap ciphertext byte length.
@@ -37,6 +37,18 @@ impl SwapCiphertext {
     }
 }
 
+impl SwapCiphertext {
+    /// A short hex summary of the ciphertext, for log lines.
+    pub fn debug_hex(&self) -> String {
+        format!(
+            "{}…{} ({} bytes)",
+            hex::encode(&self.0[..8]),
+            hex::encode(&self.0[SWAP_CIPHERTEXT_BYTES - 4..]),
+            SWAP_CIPHERTEXT_BYTES
+        )
+    }
+}
+
 impl TryFrom<[u8; SWAP_CIPHERTEXT_BYTES]> for SwapCiphertext {
     type Error = anyhow::Error;
 
@@ -52,3 +64,19 @@ impl TryFrom<&[u8]> for SwapCiphertext {
         Ok(SwapCiphertext(slice[..].try_into()?))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debug_hex_is_truncated() {
+        let mut bytes = [0u8; SWAP_CIPHERTEXT_BYTES];
+        bytes[0] = 0xab;
+        bytes[SWAP_CIPHERTEXT_BYTES - 1] = 0xcd;
+        assert_eq!(
+            SwapCiphertext(bytes).debug_hex(),
+            "ab00000000000000…000000cd (272 bytes)"
+        );
+    }
+}
@@ -0,0 +1,224 @@
+use penumbra_asset::{asset, Value};
+use penumbra_num::Amount;
+use serde::Serialize;
+
+use super::{plaintext::display_unit, SwapPlaintext};
+
+/// A summary of a swap, for a wallet to show its user before the swap is submitted.
+///
+/// Assets are named by their display units, or by their asset IDs if `cache` doesn't know them.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapConfirmation {
+    /// The asset being sold.
+    pub selling_asset: String,
+    /// The amount being sold, in units of `selling_asset`.
+    pub selling_amount: String,
+    /// The asset being bought, of which the amount depends on the batch the swap executes in.
+    pub receiving_asset: String,
+    /// The fee prepaid for claiming the swap's outputs.
+    pub fee: String,
+    /// The short form of the address the outputs will be claimed to.
+    pub address_fingerprint: String,
+}
+
+impl SwapConfirmation {
+    /// Summarize `swap`, using `cache` to name its assets.
+    ///
+    /// A swap usually sells only one asset of its trading pair. If it sells both, both are listed
+    /// in each field, separated with commas, in the order of the trading pair.
+    pub fn from_plaintext(swap: &SwapPlaintext, cache: &asset::Cache) -> Self {
+        let pair = swap.trading_pair;
+        let mut legs = Vec::new();
+        if swap.delta_1_i != Amount::zero() || swap.delta_2_i == Amount::zero() {
+            legs.push((swap.delta_1_value(), pair.asset_2));
+        }
+        if swap.delta_2_i != Amount::zero() {
+            legs.push((swap.delta_2_value(), pair.asset_1));
+        }
+
+        let join = |parts: Vec<String>| parts.join(", ");
+        Self {
+            selling_asset: join(
+                legs.iter()
+                    .map(|(input, _)| display_unit(cache, input.asset_id))
+                    .collect(),
+            ),
+            selling_amount: join(legs.iter().map(|(input, _)| amount(cache, input)).collect()),
+            receiving_asset: join(
+                legs.iter()
+                    .map(|(_, output)| display_unit(cache, *output))
+                    .collect(),
+            ),
+            fee: swap.claim_fee.0.format(cache),
+            address_fingerprint: swap.claim_address.display_short_form(),
+        }
+    }
+
+    /// Format the summary as a two column table, for showing in a terminal.
+    pub fn to_terminal_table(&self) -> String {
+        let selling = format!("{} {}", self.selling_amount, self.selling_asset);
+        let rows = [
+            ("Selling", selling.as_str()),
+            ("Receiving", self.receiving_asset.as_str()),
+            ("Fee", self.fee.as_str()),
+            ("Claim address", self.address_fingerprint.as_str()),
+        ];
+        // Widths are in characters, as in `format!` padding, since values may contain `…`.
+        let label_width = rows
+            .iter()
+            .map(|(label, _)| label.chars().count())
+            .max()
+            .unwrap_or_default();
+        let value_width = rows
+            .iter()
+            .map(|(_, value)| value.chars().count())
+            .max()
+            .unwrap_or_default();
+
+        let border = format!(
+            "+{}+{}+",
+            "-".repeat(label_width + 2),
+            "-".repeat(value_width + 2)
+        );
+        let mut table = border.clone();
+        for (label, value) in rows {
+            table.push_str(&format!(
+                "\n| {label:<label_width$} | {value:<value_width$} |"
+            ));
+        }
+        table.push('\n');
+        table.push_str(&border);
+        table
+    }
+
+    /// The summary as a JSON object, with camel case keys.
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).expect("a struct of strings serializes to JSON")
+    }
+}
+
+/// `value`'s amount, in the display unit of its asset.
+fn amount(cache: &asset::Cache, value: &Value) -> String {
+    cache
+        .get(&value.asset_id)
+        .map(|metadata| metadata.default_unit().format_value(value.amount))
+        .unwrap_or_else(|| value.amount.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use penumbra_fee::Fee;
+    use penumbra_keys::Address;
+    use penumbra_proto::core::asset::v1 as pb;
+    use rand_core::OsRng;
+
+    use super::*;
+    use crate::{swap::testing, TradingPair};
+
+    /// Metadata for an asset with a base unit, and a display unit `exponent` orders of magnitude
+    /// larger.
+    fn metadata(base: &str, display: &str, exponent: u32) -> asset::Metadata {
+        let unit = |denom: &str, exponent| pb::DenomUnit {
+            denom: denom.to_string(),
+            exponent,
+            ..Default::default()
+        };
+        asset::Metadata::try_from(pb::Metadata {
+            base: base.to_string(),
+            display: display.to_string(),
+            denom_units: vec![unit(base, 0), unit(display, exponent)],
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn um_to_usdc_confirmation() {
+        let usdc = metadata("uusdc", "usdc", 6);
+        let mut cache = asset::Cache::with_known_assets();
+        cache.extend([usdc.clone()]);
+        let um = testing::asset_id("upenumbra");
+
+        let trading_pair = TradingPair::new(um, usdc.id());
+        let (delta_1_i, delta_2_i) = testing::selling(trading_pair, um, 12_500_000u64.into());
+        let address = Address::dummy(&mut OsRng);
+        let swap = SwapPlaintext::new(
+            &mut OsRng,
+            trading_pair,
+            delta_1_i,
+            delta_2_i,
+            Fee(Value {
+                amount: 5_000u64.into(),
+                asset_id: um,
+            }),
+            address.clone(),
+        );
+
+        let confirmation = SwapConfirmation::from_plaintext(&swap, &cache);
+        let fingerprint = address.display_short_form();
+        assert_eq!(
+            confirmation,
+            SwapConfirmation {
+                selling_asset: "penumbra".to_string(),
+                selling_amount: "12.5".to_string(),
+                receiving_asset: "usdc".to_string(),
+                fee: "5mpenumbra".to_string(),
+                address_fingerprint: fingerprint.clone(),
+            }
+        );
+
+        let value_width = fingerprint.chars().count();
+        let border = format!("+---------------+-{}-+", "-".repeat(value_width));
+        let row = |label: &str, value: &str| format!("| {label:<13} | {value:<value_width$} |");
+        assert_eq!(
+            confirmation.to_terminal_table(),
+            [
+                border.clone(),
+                row("Selling", "12.5 penumbra"),
+                row("Receiving", "usdc"),
+                row("Fee", "5mpenumbra"),
+                row("Claim address", &fingerprint),
+                border,
+            ]
+            .join("\n")
+        );
+
+        assert_eq!(
+            confirmation.to_json(),
+            serde_json::json!({
+                "sellingAsset": "penumbra",
+                "sellingAmount": "12.5",
+                "receivingAsset": "usdc",
+                "fee": "5mpenumbra",
+                "addressFingerprint": fingerprint,
+            })
+        );
+    }
+
+    #[test]
+    fn unknown_assets_are_named_by_id() {
+        let cache = asset::Cache::with_known_assets();
+        let um = testing::asset_id("upenumbra");
+        // Metadata which isn't in the cache.
+        let unknown = metadata("uunknown", "unknown", 6).id();
+        let trading_pair = TradingPair::new(um, unknown);
+        let (delta_1_i, delta_2_i) = testing::selling(trading_pair, unknown, 42u64.into());
+        let swap = SwapPlaintext::new(
+            &mut OsRng,
+            trading_pair,
+            delta_1_i,
+            delta_2_i,
+            Fee(Value {
+                amount: Amount::zero(),
+                asset_id: um,
+            }),
+            Address::dummy(&mut OsRng),
+        );
+
+        let confirmation = SwapConfirmation::from_plaintext(&swap, &cache);
+        assert_eq!(confirmation.selling_asset, unknown.to_string());
+        assert_eq!(confirmation.selling_amount, "42");
+        assert_eq!(confirmation.receiving_asset, "penumbra");
+    }
+}
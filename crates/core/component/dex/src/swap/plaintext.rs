@@ -121,6 +121,26 @@ impl SwapPlaintext {
         StateCommitment(inner)
     }
 
+    /// Hash the fields that describe what this swap does: the trading pair, the input amounts,
+    /// and the claim fee.
+    ///
+    /// Unlike the [`swap_commitment`](SwapPlaintext::swap_commitment), this excludes the claim
+    /// address and the `rseed`, so two swaps of the same amounts in the same pair have the same
+    /// content hash even if they were built separately.
+    pub fn content_hash(&self) -> [u8; 32] {
+        let hash = blake2b_simd::Params::new()
+            .personal(b"Penumbra_SwapCtH")
+            .hash_length(32)
+            .to_state()
+            .update(&self.trading_pair.to_bytes())
+            .update(&self.delta_1_i.to_le_bytes())
+            .update(&self.delta_2_i.to_le_bytes())
+            .update(&self.claim_fee.0.amount.to_le_bytes())
+            .update(&self.claim_fee.0.asset_id.to_bytes())
+            .finalize();
+        hash.as_bytes().try_into().expect("hash length is 32 bytes")
+    }
+
     pub fn diversified_generator(&self) -> &decaf377::Element {
         self.claim_address.diversified_generator()
     }
@@ -159,6 +179,32 @@ impl SwapPlaintext {
         }
     }
 
+    /// Describe the swap for debugging, like `Swap: 100penumbra → ? gm (fee: 1mpenumbra)`,
+    /// using `cache` to name the assets.
+    ///
+    /// The output amount is shown as `?`, since it depends on the batch the swap is executed in.
+    pub fn describe(&self, cache: &asset::Cache) -> String {
+        let leg_1 = format!(
+            "{} → ? {}",
+            self.delta_1_value().format(cache),
+            display_unit(cache, self.trading_pair.asset_2)
+        );
+        let leg_2 = format!(
+            "{} → ? {}",
+            self.delta_2_value().format(cache),
+            display_unit(cache, self.trading_pair.asset_1)
+        );
+        let legs = match (
+            self.delta_1_i == Amount::zero(),
+            self.delta_2_i == Amount::zero(),
+        ) {
+            (false, true) => leg_1,
+            (true, false) => leg_2,
+            _ => format!("{leg_1}, {leg_2}"),
+        };
+        format!("Swap: {legs} (fee: {})", self.claim_fee.0.format(cache))
+    }
+
     pub fn new<R: RngCore + CryptoRng>(
         rng: &mut R,
         trading_pair: TradingPair,
@@ -180,6 +226,14 @@ impl SwapPlaintext {
     }
 }
 
+/// The name of the display unit of `id`, or its asset ID if `cache` doesn't know it.
+pub(super) fn display_unit(cache: &asset::Cache, id: asset::Id) -> String {
+    cache
+        .get(&id)
+        .map(|metadata| metadata.default_unit().to_string())
+        .unwrap_or_else(|| id.to_string())
+}
+
 pub struct SwapPlaintextVar {
     pub claim_fee: ValueVar,
     pub delta_1_i: AmountVar,
@@ -420,6 +474,7 @@ mod tests {
     use rand_core::OsRng;
 
     use super::*;
+    use crate::swap::testing;
     use penumbra_asset::{asset, Value};
     use penumbra_keys::keys::{Bip44Path, SeedPhrase, SpendKey};
 
@@ -466,4 +521,73 @@ mod tests {
 
         assert_eq!(plaintext, swap);
     }
+
+    #[test]
+    fn swap_description_names_assets() {
+        let cache = asset::Cache::with_known_assets();
+        let um = testing::asset_id("upenumbra");
+        let trading_pair = TradingPair::new(um, testing::asset_id("ugm"));
+        let (delta_1_i, delta_2_i) = testing::selling(trading_pair, um, 100_000_000u64.into());
+
+        let swap = SwapPlaintext::new(
+            &mut OsRng,
+            trading_pair,
+            delta_1_i,
+            delta_2_i,
+            Fee(Value {
+                amount: 1_000u64.into(),
+                asset_id: um,
+            }),
+            Address::dummy(&mut OsRng),
+        );
+
+        assert_eq!(
+            swap.describe(&cache),
+            "Swap: 100penumbra → ? gm (fee: 1mpenumbra)"
+        );
+    }
+
+    #[test]
+    /// Check that amounts which don't fit in a `u64` survive the plaintext encoding.
+    fn swap_amounts_above_u64_max_roundtrip() {
+        let mut rng = OsRng;
+
+        let seed_phrase = SeedPhrase::generate(rng);
+        let sk = SpendKey::from_seed_phrase_bip44(seed_phrase, &Bip44Path::new(0));
+        let fvk = sk.full_viewing_key();
+        let ovk = fvk.outgoing();
+        let (dest, _dtk_d) = fvk.incoming().payment_address(0u32.into());
+        let upenumbra = asset::Cache::with_known_assets()
+            .get_unit("upenumbra")
+            .unwrap()
+            .id();
+        let trading_pair = TradingPair::new(
+            upenumbra,
+            asset::Cache::with_known_assets()
+                .get_unit("nala")
+                .unwrap()
+                .id(),
+        );
+
+        let swap = SwapPlaintext::new(
+            &mut rng,
+            trading_pair,
+            (u64::MAX as u128 + 1).into(),
+            u128::MAX.into(),
+            Fee(Value {
+                amount: (u64::MAX as u128 * 2).into(),
+                asset_id: upenumbra,
+            }),
+            dest,
+        );
+
+        let bytes: [u8; SWAP_LEN_BYTES] = (&swap).into();
+        assert_eq!(SwapPlaintext::try_from(bytes).unwrap(), swap);
+
+        let ciphertext = swap.encrypt(ovk).encrypted_swap;
+        let plaintext = SwapCiphertext::decrypt(&ciphertext, ovk, swap.swap_commitment())
+            .expect("can decrypt swap");
+        assert_eq!(plaintext.delta_1_i.value(), u64::MAX as u128 + 1);
+        assert_eq!(plaintext, swap);
+    }
 }
@@ -4,6 +4,7 @@ use ark_ff::Zero;
 use decaf377::{Fq, Fr};
 use penumbra_asset::{balance, Balance, Value};
 use penumbra_keys::FullViewingKey;
+use penumbra_num::field_utils::{bytes_to_fq, bytes_to_fr, fq_to_bytes, fr_to_bytes};
 use penumbra_proto::{penumbra::core::component::dex::v1 as pb, DomainType};
 use rand_core::{CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
@@ -122,9 +123,9 @@ impl From<SwapPlan> for pb::SwapPlan {
     fn from(msg: SwapPlan) -> Self {
         Self {
             swap_plaintext: Some(msg.swap_plaintext.into()),
-            fee_blinding: msg.fee_blinding.to_bytes().to_vec(),
-            proof_blinding_r: msg.proof_blinding_r.to_bytes().to_vec(),
-            proof_blinding_s: msg.proof_blinding_s.to_bytes().to_vec(),
+            fee_blinding: fr_to_bytes(msg.fee_blinding).to_vec(),
+            proof_blinding_r: fq_to_bytes(msg.proof_blinding_r).to_vec(),
+            proof_blinding_s: fq_to_bytes(msg.proof_blinding_s).to_vec(),
         }
     }
 }
@@ -145,17 +146,92 @@ impl TryFrom<pb::SwapPlan> for SwapPlan {
             .try_into()
             .map_err(|_| anyhow!("expected 32 byte fee blinding"))?;
         Ok(Self {
-            fee_blinding: Fr::from_bytes_checked(&fee_blinding_bytes)
-                .expect("fee blinding malformed"),
+            fee_blinding: bytes_to_fr(&fee_blinding_bytes).context("fee blinding malformed")?,
             swap_plaintext: msg
                 .swap_plaintext
                 .ok_or_else(|| anyhow!("missing swap_plaintext"))?
                 .try_into()
                 .context("swap plaintext malformed")?,
-            proof_blinding_r: Fq::from_bytes_checked(&proof_blinding_r_bytes)
-                .expect("proof_blinding_r malformed"),
-            proof_blinding_s: Fq::from_bytes_checked(&proof_blinding_s_bytes)
-                .expect("proof_blinding_r malformed"),
+            proof_blinding_r: bytes_to_fq(&proof_blinding_r_bytes)
+                .context("proof_blinding_r malformed")?,
+            proof_blinding_s: bytes_to_fq(&proof_blinding_s_bytes)
+                .context("proof_blinding_s malformed")?,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use penumbra_asset::asset;
+    use penumbra_fee::Fee;
+    use penumbra_keys::keys::{Bip44Path, SeedPhrase, SpendKey};
+    use rand_core::OsRng;
+
+    use super::*;
+    use crate::TradingPair;
+
+    fn plan(fvk: &FullViewingKey) -> SwapPlan {
+        let cache = asset::Cache::with_known_assets();
+        let upenumbra = cache.get_unit("upenumbra").unwrap().id();
+        let trading_pair = TradingPair::new(upenumbra, cache.get_unit("ugm").unwrap().id());
+        let (claim_address, _dtk_d) = fvk.incoming().payment_address(0u32.into());
+        let swap_plaintext = SwapPlaintext::new(
+            &mut OsRng,
+            trading_pair,
+            100u64.into(),
+            0u64.into(),
+            Fee(Value {
+                amount: 7u64.into(),
+                asset_id: upenumbra,
+            }),
+            claim_address,
+        );
+        SwapPlan::new(&mut OsRng, swap_plaintext)
+    }
+
+    /// The balance commitment of a swap with `body`, as computed by verifiers.
+    ///
+    /// The proof is a placeholder, since the balance commitment doesn't depend on it.
+    fn body_balance_commitment(body: swap::Body) -> balance::Commitment {
+        Swap {
+            body,
+            proof: SwapProof::try_from(pb::ZkSwapProof {
+                inner: vec![0; 192],
+            })
+            .unwrap(),
+        }
+        .balance_commitment_inner()
+    }
+
+    #[test]
+    fn swap_balance_commitment_includes_the_prepaid_fee() {
+        let sk = SpendKey::from_seed_phrase_bip44(SeedPhrase::generate(OsRng), &Bip44Path::new(0));
+        let fvk = sk.full_viewing_key();
+        let plan = plan(fvk);
+        let body = plan.swap_body(fvk);
+
+        // The swap's balance, including the claim fee, opens with only the fee blinding factor,
+        // which is what the transaction's binding signature accounts for.
+        assert_eq!(
+            body_balance_commitment(body),
+            plan.balance().commit(plan.fee_blinding)
+        );
+    }
+
+    #[test]
+    fn tampered_fee_commitment_breaks_the_balance() {
+        let sk = SpendKey::from_seed_phrase_bip44(SeedPhrase::generate(OsRng), &Bip44Path::new(0));
+        let fvk = sk.full_viewing_key();
+        let plan = plan(fvk);
+        let mut body = plan.swap_body(fvk);
+
+        let mut cheaper_fee = plan.swap_plaintext.claim_fee;
+        cheaper_fee.0.amount = 1u64.into();
+        body.fee_commitment = cheaper_fee.commit(plan.fee_blinding);
+
+        assert_ne!(
+            body_balance_commitment(body),
+            plan.balance().commit(plan.fee_blinding)
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use std::collections::BTreeMap;
+
+use penumbra_asset::asset;
+use penumbra_fee::Fee;
+use penumbra_num::{fixpoint::U128x128, Amount};
+
+use super::SwapPlaintext;
+
+#[derive(Clone, Debug)]
+struct QueuedSwap {
+    swap: SwapPlaintext,
+    /// The fee paid per byte of the transaction carrying the swap.
+    density: U128x128,
+}
+
+/// A queue of swaps waiting to be submitted, ordered by the fee each pays per byte.
+///
+/// Fees are compared by amount alone, so all the swaps in a queue should pay their fees in
+/// the same asset. Swaps with the same fee density keep the order they were pushed in.
+#[derive(Clone, Debug, Default)]
+pub struct PrioritizedSwapQueue {
+    queue: Vec<QueuedSwap>,
+}
+
+impl PrioritizedSwapQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Add a swap, carried by a transaction of `size_bytes` paying `fee`.
+    pub fn push(&mut self, swap: SwapPlaintext, fee: Fee, size_bytes: usize) {
+        let size = Amount::from(u64::try_from(size_bytes.max(1)).unwrap_or(u64::MAX));
+        let density = U128x128::ratio(fee.amount(), size).expect("size is nonzero");
+        self.queue.push(QueuedSwap { swap, density });
+    }
+
+    fn sort(&mut self) {
+        // The sort is stable, so ties are left in insertion order.
+        self.queue.sort_by(|a, b| b.density.cmp(&a.density));
+    }
+
+    /// Remove every swap from the queue, highest fee density first.
+    pub fn drain_in_priority_order(&mut self) -> impl Iterator<Item = SwapPlaintext> {
+        self.sort();
+        std::mem::take(&mut self.queue)
+            .into_iter()
+            .map(|queued| queued.swap)
+    }
+
+    /// Drop the lowest-priority swaps until at most `max_count` remain, with combined inputs of
+    /// at most `max_total_value` in each asset.
+    ///
+    /// The retained swaps are always a prefix of the priority order: once a swap doesn't fit,
+    /// every swap after it is dropped too, even if a smaller one would have fit.
+    pub fn truncate_to_limit(&mut self, max_count: u32, max_total_value: u128) {
+        self.sort();
+        let max_count = usize::try_from(max_count).unwrap_or(usize::MAX);
+        let mut totals = BTreeMap::<asset::Id, u128>::new();
+        let retained = self
+            .queue
+            .iter()
+            .take(max_count)
+            .take_while(|queued| {
+                let pair = queued.swap.trading_pair;
+                let inputs = [
+                    (pair.asset_1(), queued.swap.delta_1_i.value()),
+                    (pair.asset_2(), queued.swap.delta_2_i.value()),
+                ];
+                let fits = inputs.iter().all(|(asset, value)| {
+                    let total = totals.get(asset).copied().unwrap_or_default();
+                    matches!(total.checked_add(*value), Some(total) if total <= max_total_value)
+                });
+                if fits {
+                    for (asset, value) in inputs {
+                        *totals.entry(asset).or_default() += value;
+                    }
+                }
+                fits
+            })
+            .count();
+        self.queue.truncate(retained);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::swap::testing;
+
+    /// Ten swaps, identified by their input amount, with their fees and transaction sizes.
+    ///
+    /// In order of fee density, the swaps are 3, 7, 1, 9, 5, 2, 10, 4, 8, 6.
+    const SWAPS: [(u64, u64, usize); 10] = [
+        (1, 300, 100),
+        (2, 400, 200),
+        (3, 1_000, 100),
+        (4, 150, 100),
+        (5, 500, 200),
+        (6, 10, 100),
+        (7, 900, 100),
+        (8, 100, 100),
+        (9, 1_100, 400),
+        (10, 320, 200),
+    ];
+
+    fn queue() -> PrioritizedSwapQueue {
+        let mut queue = PrioritizedSwapQueue::new();
+        for (delta_1_i, fee, size_bytes) in SWAPS {
+            queue.push(
+                testing::swap(delta_1_i, 0),
+                Fee::from_staking_token_amount(fee.into()),
+                size_bytes,
+            );
+        }
+        queue
+    }
+
+    fn inputs(swaps: impl Iterator<Item = SwapPlaintext>) -> Vec<u64> {
+        swaps
+            .map(|swap| swap.delta_1_i.value().try_into().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn swaps_drain_in_fee_density_order() {
+        let mut queue = queue();
+        assert_eq!(
+            inputs(queue.drain_in_priority_order()),
+            vec![3, 7, 1, 9, 5, 2, 10, 4, 8, 6]
+        );
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn truncation_retains_the_highest_fee_density() {
+        for max_count in 0..=10 {
+            let mut queue = queue();
+            queue.truncate_to_limit(max_count, u128::MAX);
+            assert_eq!(queue.len(), max_count as usize);
+            assert_eq!(
+                inputs(queue.drain_in_priority_order()),
+                [3, 7, 1, 9, 5, 2, 10, 4, 8, 6][..max_count as usize].to_vec()
+            );
+        }
+    }
+
+    #[test]
+    fn truncation_stops_at_the_value_limit() {
+        let mut queue = queue();
+        // 3 + 7 + 1 = 11 fits, but adding 9 would exceed the limit.
+        queue.truncate_to_limit(10, 19);
+        assert_eq!(inputs(queue.drain_in_priority_order()), vec![3, 7, 1]);
+    }
+
+    #[test]
+    fn the_value_limit_applies_to_each_asset_separately() {
+        let mut queue = PrioritizedSwapQueue::new();
+        for (delta_1_i, delta_2_i, fee) in [(10u64, 0u64, 300u64), (0, 10, 200), (10, 0, 100)] {
+            queue.push(
+                testing::swap(delta_1_i, delta_2_i),
+                Fee::from_staking_token_amount(fee.into()),
+                100,
+            );
+        }
+        // The second swap fits, since its input is in the other asset, but the third would take
+        // the first asset over the limit.
+        queue.truncate_to_limit(10, 15);
+        let retained: Vec<(u128, u128)> = queue
+            .drain_in_priority_order()
+            .map(|swap| (swap.delta_1_i.value(), swap.delta_2_i.value()))
+            .collect();
+        assert_eq!(retained, vec![(10, 0), (0, 10)]);
+    }
+}
@@ -0,0 +1,110 @@
+use penumbra_keys::FullViewingKey;
+
+use super::{SwapPayload, SwapPlaintext};
+
+/// Trial-decrypts batches of swap payloads with a single full viewing key.
+///
+/// Swaps are encrypted to the sender's outgoing viewing key, so a wallet finds its own swaps by
+/// attempting to decrypt every [`SwapPayload`] in a block. Each attempt is independent, so with the
+/// `parallel` feature enabled, a batch can be spread across threads with
+/// [`scan_batch_parallel`](SwapScanner::scan_batch_parallel).
+#[derive(Clone, Debug)]
+pub struct SwapScanner {
+    fvk: FullViewingKey,
+}
+
+impl SwapScanner {
+    pub fn new(fvk: FullViewingKey) -> Self {
+        Self { fvk }
+    }
+
+    /// Scan the payloads one at a time, returning the plaintext of each swap belonging to this
+    /// key, in input order.
+    pub fn scan_batch_sequential(&self, payloads: &[SwapPayload]) -> Vec<Option<SwapPlaintext>> {
+        payloads
+            .iter()
+            .map(|payload| payload.trial_decrypt(&self.fvk))
+            .collect()
+    }
+
+    /// Scan the payloads across the rayon thread pool, returning the same results as
+    /// [`scan_batch_sequential`](SwapScanner::scan_batch_sequential).
+    #[cfg(feature = "parallel")]
+    pub fn scan_batch_parallel(&self, payloads: &[SwapPayload]) -> Vec<Option<SwapPlaintext>> {
+        use rayon::prelude::*;
+
+        payloads
+            .par_iter()
+            .map(|payload| payload.trial_decrypt(&self.fvk))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_core::OsRng;
+
+    use super::*;
+    use crate::TradingPair;
+    use penumbra_asset::asset;
+    use penumbra_fee::Fee;
+    use penumbra_keys::keys::{Bip44Path, SeedPhrase, SpendKey};
+
+    /// Build `count` swap payloads, of which every `hit_every`-th is encrypted to `fvk`.
+    fn payloads(fvk: &FullViewingKey, count: usize, hit_every: usize) -> Vec<SwapPayload> {
+        let other_fvk =
+            SpendKey::from_seed_phrase_bip44(SeedPhrase::generate(OsRng), &Bip44Path::new(0))
+                .full_viewing_key()
+                .clone();
+        let cache = asset::Cache::with_known_assets();
+        let trading_pair = TradingPair::new(
+            cache.get_unit("upenumbra").unwrap().id(),
+            cache.get_unit("nala").unwrap().id(),
+        );
+
+        (0..count)
+            .map(|i| {
+                let sender = if i % hit_every == 0 { fvk } else { &other_fvk };
+                let (claim_address, _) = sender.incoming().payment_address(0u32.into());
+                SwapPlaintext::new(
+                    &mut OsRng,
+                    trading_pair,
+                    (i as u64 + 1).into(),
+                    0u64.into(),
+                    Fee::default(),
+                    claim_address,
+                )
+                .encrypt(sender.outgoing())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sequential_scan_finds_only_own_swaps() {
+        let fvk = SpendKey::from_seed_phrase_bip44(SeedPhrase::generate(OsRng), &Bip44Path::new(0))
+            .full_viewing_key()
+            .clone();
+        let payloads = payloads(&fvk, 20, 4);
+
+        let results = SwapScanner::new(fvk).scan_batch_sequential(&payloads);
+
+        for (i, result) in results.iter().enumerate() {
+            assert_eq!(result.is_some(), i % 4 == 0, "unexpected result at {i}");
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_scan_matches_sequential_scan() {
+        let fvk = SpendKey::from_seed_phrase_bip44(SeedPhrase::generate(OsRng), &Bip44Path::new(0))
+            .full_viewing_key()
+            .clone();
+        let payloads = payloads(&fvk, 50, 10);
+        let scanner = SwapScanner::new(fvk);
+
+        assert_eq!(
+            scanner.scan_batch_parallel(&payloads),
+            scanner.scan_batch_sequential(&payloads)
+        );
+    }
+}
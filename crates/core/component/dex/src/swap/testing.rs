@@ -0,0 +1,49 @@
+//! Fixtures shared by the swap tests.
+
+use penumbra_asset::asset;
+use penumbra_fee::Fee;
+use penumbra_keys::Address;
+use penumbra_num::Amount;
+use rand_core::OsRng;
+
+use super::SwapPlaintext;
+use crate::TradingPair;
+
+/// The id of the known asset with the unit `denom`.
+pub(crate) fn asset_id(denom: &str) -> asset::Id {
+    asset::Cache::with_known_assets()
+        .get_unit(denom)
+        .expect("a known unit")
+        .id()
+}
+
+/// The pair of the staking token and `nala`.
+pub(crate) fn um_nala() -> TradingPair {
+    TradingPair::new(asset_id("upenumbra"), asset_id("nala"))
+}
+
+/// A swap of `delta_1_i` and `delta_2_i` in [`um_nala`], claimed by a dummy address for free.
+pub(crate) fn swap(delta_1_i: u64, delta_2_i: u64) -> SwapPlaintext {
+    SwapPlaintext::new(
+        &mut OsRng,
+        um_nala(),
+        delta_1_i.into(),
+        delta_2_i.into(),
+        Fee::default(),
+        Address::dummy(&mut OsRng),
+    )
+}
+
+/// The inputs `(delta_1_i, delta_2_i)` of a swap in `trading_pair` which sells `amount` of
+/// `asset`.
+pub(crate) fn selling(
+    trading_pair: TradingPair,
+    asset: asset::Id,
+    amount: Amount,
+) -> (Amount, Amount) {
+    if trading_pair.asset_1() == asset {
+        (amount, Amount::zero())
+    } else {
+        (Amount::zero(), amount)
+    }
+}
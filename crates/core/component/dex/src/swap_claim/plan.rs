@@ -1,6 +1,8 @@
+use anyhow::Context;
 use decaf377::Fq;
 use penumbra_asset::{Balance, Value};
 use penumbra_keys::{keys::IncomingViewingKey, FullViewingKey};
+use penumbra_num::field_utils::{bytes_to_fq, fq_to_bytes};
 use penumbra_proof_params::SWAPCLAIM_PROOF_PROVING_KEY;
 use penumbra_proto::{penumbra::core::component::dex::v1 as pb, DomainType};
 use penumbra_sct::Nullifier;
@@ -146,8 +148,8 @@ impl From<SwapClaimPlan> for pb::SwapClaimPlan {
             position: msg.position.into(),
             output_data: Some(msg.output_data.into()),
             epoch_duration: msg.epoch_duration,
-            proof_blinding_r: msg.proof_blinding_r.to_bytes().to_vec(),
-            proof_blinding_s: msg.proof_blinding_s.to_bytes().to_vec(),
+            proof_blinding_r: fq_to_bytes(msg.proof_blinding_r).to_vec(),
+            proof_blinding_s: fq_to_bytes(msg.proof_blinding_s).to_vec(),
         }
     }
 }
@@ -175,10 +177,10 @@ impl TryFrom<pb::SwapClaimPlan> for SwapClaimPlan {
                 .ok_or_else(|| anyhow::anyhow!("missing output_data"))?
                 .try_into()?,
             epoch_duration: msg.epoch_duration,
-            proof_blinding_r: Fq::from_bytes_checked(&proof_blinding_r_bytes)
-                .expect("proof_blinding_r malformed"),
-            proof_blinding_s: Fq::from_bytes_checked(&proof_blinding_s_bytes)
-                .expect("proof_blinding_s malformed"),
+            proof_blinding_r: bytes_to_fq(&proof_blinding_r_bytes)
+                .context("proof_blinding_r malformed")?,
+            proof_blinding_s: bytes_to_fq(&proof_blinding_s_bytes)
+                .context("proof_blinding_s malformed")?,
         })
     }
 }
@@ -33,11 +33,14 @@ impl Component for FeeComponent {
         }
     }
 
-    #[instrument(name = "fee", skip(_state, _begin_block))]
+    #[instrument(name = "fee", skip(state, begin_block))]
     async fn begin_block<S: StateWrite + 'static>(
-        _state: &mut Arc<S>,
-        _begin_block: &abci::request::BeginBlock,
+        state: &mut Arc<S>,
+        begin_block: &abci::request::BeginBlock,
     ) {
+        let state = Arc::get_mut(state).expect("state should be unique");
+        // Remember the proposer, so that its tips can be attributed to it at the end of the block.
+        state.put_block_proposer(begin_block.header.proposer_address.as_bytes().to_vec());
     }
 
     #[instrument(name = "fee", skip(state, _end_block))]
@@ -61,6 +64,13 @@ impl Component for FeeComponent {
             swapped_base_fee_total: Some(Fee::from_staking_token_amount(swapped_base).into()),
             swapped_tip_total: Some(Fee::from_staking_token_amount(swapped_tip).into()),
         });
+
+        if let Some(proposer) = state_ref.block_proposer() {
+            state_ref.record_proto(pb::EventBlockReward {
+                proposer,
+                reward: Some(Fee::from_staking_token_amount(swapped_tip).into()),
+            });
+        }
     }
 
     #[instrument(name = "fee", skip(_state))]
@@ -49,6 +49,11 @@ pub trait StateReadExt: StateRead {
         self.object_get(state_key::fee_accumulator())
             .unwrap_or_default()
     }
+
+    /// The consensus address of the proposer of the current block, if it has been recorded.
+    fn block_proposer(&self) -> Option<Vec<u8>> {
+        self.object_get(state_key::block_proposer())
+    }
 }
 
 impl<T: StateRead + ?Sized> StateReadExt for T {}
@@ -105,6 +110,11 @@ pub trait StateWriteExt: StateWrite {
         );
         self.object_put(state_key::fee_accumulator(), new);
     }
+
+    /// Records the consensus address of the proposer of the current block.
+    fn put_block_proposer(&mut self, proposer: Vec<u8>) {
+        self.object_put(state_key::block_proposer(), proposer);
+    }
 }
 
 impl<T: StateWrite + ?Sized> StateWriteExt for T {}
@@ -13,3 +13,7 @@ pub fn gas_prices_changed() -> &'static str {
 pub fn fee_accumulator() -> &'static str {
     "fee/accumulator"
 }
+
+pub fn block_proposer() -> &'static str {
+    "fee/block_proposer"
+}
@@ -1,9 +1,13 @@
+use anyhow::Context;
 use ark_ff::Zero;
 use decaf377::{Fq, Fr};
 use decaf377_rdsa::{Signature, SpendAuth};
 use penumbra_asset::{Balance, Value};
 use penumbra_keys::FullViewingKey;
-use penumbra_num::Amount;
+use penumbra_num::{
+    field_utils::{bytes_to_fq, bytes_to_fr, fq_to_bytes, fr_to_bytes},
+    Amount,
+};
 use penumbra_proof_params::DELEGATOR_VOTE_PROOF_PROVING_KEY;
 use penumbra_proto::{core::component::governance::v1 as pb, DomainType};
 use penumbra_sct::Nullifier;
@@ -161,9 +165,9 @@ impl From<DelegatorVotePlan> for pb::DelegatorVotePlan {
             staked_note: Some(inner.staked_note.into()),
             unbonded_amount: Some(inner.unbonded_amount.into()),
             staked_note_position: inner.position.into(),
-            randomizer: inner.randomizer.to_bytes().to_vec(),
-            proof_blinding_r: inner.proof_blinding_r.to_bytes().to_vec(),
-            proof_blinding_s: inner.proof_blinding_s.to_bytes().to_vec(),
+            randomizer: fr_to_bytes(inner.randomizer).to_vec(),
+            proof_blinding_r: fq_to_bytes(inner.proof_blinding_r).to_vec(),
+            proof_blinding_s: fq_to_bytes(inner.proof_blinding_s).to_vec(),
         }
     }
 }
@@ -197,18 +201,18 @@ impl TryFrom<pb::DelegatorVotePlan> for DelegatorVotePlan {
                 .ok_or_else(|| anyhow::anyhow!("missing unbonded amount in `DelegatorVotePlan`"))?
                 .try_into()?,
             position: value.staked_note_position.into(),
-            randomizer: Fr::from_bytes_checked(
+            randomizer: bytes_to_fr(
                 value
                     .randomizer
                     .as_slice()
                     .try_into()
                     .map_err(|_| anyhow::anyhow!("invalid randomizer"))?,
             )
-            .expect("randomizer malformed"),
-            proof_blinding_r: Fq::from_bytes_checked(&proof_blinding_r_bytes)
-                .expect("proof_blinding_r malformed"),
-            proof_blinding_s: Fq::from_bytes_checked(&proof_blinding_s_bytes)
-                .expect("proof_blinding_s malformed"),
+            .context("randomizer malformed")?,
+            proof_blinding_r: bytes_to_fq(&proof_blinding_r_bytes)
+                .context("proof_blinding_r malformed")?,
+            proof_blinding_s: bytes_to_fq(&proof_blinding_s_bytes)
+                .context("proof_blinding_s malformed")?,
         })
     }
 }
@@ -1,8 +1,10 @@
 use std::str::FromStr;
 
 use crate::{
-    component::{AssetRegistry, NoteManager},
-    event, Ics20Withdrawal,
+    component::{AssetRegistry, NoteManager, StateReadExt as _},
+    event,
+    supply_cap::SupplyCapChecker,
+    Ics20Withdrawal,
 };
 use anyhow::{Context, Result};
 use async_trait::async_trait;
@@ -294,10 +296,7 @@ impl AppHandlerCheck for Ics20Transfer {
 }
 
 // the main entry point for ICS20 transfer packet handling
-async fn recv_transfer_packet_inner<S: StateWrite>(
-    mut state: S,
-    msg: &MsgRecvPacket,
-) -> Result<()> {
+async fn recv_transfer_packet_inner<S: StateWrite>(mut state: S, packet: &Packet) -> Result<()> {
     // parse if we are source or dest, and mint or burn accordingly
     //
     // see this part of the spec for this logic:
@@ -305,7 +304,7 @@ async fn recv_transfer_packet_inner<S: StateWrite>(
     // https://github.com/cosmos/ibc/tree/main/spec/app/ics-020-fungible-token-transfer (onRecvPacket)
     //
     // NOTE: spec says proto but this is actually JSON according to the ibc-go implementation
-    let packet_data: FungibleTokenPacketData = serde_json::from_slice(msg.packet.data.as_slice())
+    let packet_data: FungibleTokenPacketData = serde_json::from_slice(packet.data.as_slice())
         .with_context(|| "failed to decode FTPD packet")?;
     let packet_denom: asset::Metadata = packet_data
         .denom
@@ -321,18 +320,13 @@ async fn recv_transfer_packet_inner<S: StateWrite>(
     // NOTE: here we assume we are chain A.
 
     // 2. check if we are the source chain for the denom.
-    if is_source(
-        &msg.packet.port_on_a,
-        &msg.packet.chan_on_a,
-        &packet_denom,
-        false,
-    ) {
+    if is_source(&packet.port_on_a, &packet.chan_on_a, &packet_denom, false) {
         // mint tokens to receiver in the amount of packet_data.amount in the denom of denom (with
         // the source removed, since we're the source)
         let prefix = format!(
             "{source_port}/{source_chan}/",
-            source_port = msg.packet.port_on_a,
-            source_chan = msg.packet.chan_on_a
+            source_port = packet.port_on_a,
+            source_chan = packet.chan_on_a
         );
 
         let denom: asset::Metadata = packet_data
@@ -354,7 +348,7 @@ async fn recv_transfer_packet_inner<S: StateWrite>(
         // check if we have enough balance to unescrow tokens to receiver
         let value_balance: Amount = state
             .get(&state_key::ics20_value_balance::by_asset_id(
-                &msg.packet.chan_on_b,
+                &packet.chan_on_b,
                 &denom.id(),
             ))
             .await?
@@ -370,9 +364,9 @@ async fn recv_transfer_packet_inner<S: StateWrite>(
                 value,
                 &receiver_address,
                 CommitmentSource::Ics20Transfer {
-                    packet_seq: msg.packet.sequence.0,
+                    packet_seq: packet.sequence.0,
                     // We are chain A
-                    channel_id: msg.packet.chan_on_a.0.clone(),
+                    channel_id: packet.chan_on_a.0.clone(),
                     sender: packet_data.sender.clone(),
                 },
             )
@@ -385,7 +379,7 @@ async fn recv_transfer_packet_inner<S: StateWrite>(
             .checked_sub(&receiver_amount)
             .context("underflow subtracing value balance in ics20 transfer")?;
         state.put(
-            state_key::ics20_value_balance::by_asset_id(&msg.packet.chan_on_b, &denom.id()),
+            state_key::ics20_value_balance::by_asset_id(&packet.chan_on_b, &denom.id()),
             new_value_balance,
         );
         state.record_proto(event::inbound_fungible_token_transfer(
@@ -393,8 +387,8 @@ async fn recv_transfer_packet_inner<S: StateWrite>(
             packet_data.sender.clone(),
             &receiver_address,
             FungibleTokenTransferPacketMetadata {
-                channel: msg.packet.chan_on_a.0.clone(),
-                sequence: msg.packet.sequence.0,
+                channel: packet.chan_on_a.0.clone(),
+                sequence: packet.sequence.0,
             },
         ));
     } else {
@@ -406,7 +400,7 @@ async fn recv_transfer_packet_inner<S: StateWrite>(
         // then mint that denom to packet_data.receiver in packet_data.amount
         let prefixed_denomination = format!(
             "{}/{}/{}",
-            msg.packet.port_on_b, msg.packet.chan_on_b, packet_data.denom
+            packet.port_on_b, packet.chan_on_b, packet_data.denom
         );
 
         let denom: asset::Metadata = prefixed_denomination
@@ -420,14 +414,29 @@ async fn recv_transfer_packet_inner<S: StateWrite>(
             asset_id: denom.id(),
         };
 
+        // The denom is prefixed with the channel it arrived over, so its value balance on that
+        // channel is its entire supply on this chain.
+        let value_balance: Amount = state
+            .get(&state_key::ics20_value_balance::by_asset_id(
+                &packet.chan_on_b,
+                &denom.id(),
+            ))
+            .await?
+            .unwrap_or_else(Amount::zero);
+
+        let params = state.get_shielded_pool_params().await?;
+        if let Some(cap) = params.supply_cap(&denom.id()) {
+            SupplyCapChecker::check(value, value_balance, cap)?;
+        }
+
         state
             .mint_note(
                 value,
                 &receiver_address,
                 CommitmentSource::Ics20Transfer {
-                    packet_seq: msg.packet.sequence.0,
+                    packet_seq: packet.sequence.0,
                     // We are chain A
-                    channel_id: msg.packet.chan_on_a.0.clone(),
+                    channel_id: packet.chan_on_a.0.clone(),
                     sender: packet_data.sender.clone(),
                 },
             )
@@ -435,17 +444,9 @@ async fn recv_transfer_packet_inner<S: StateWrite>(
             .context("failed to mint notes in ibc transfer")?;
 
         // update the value balance
-        let value_balance: Amount = state
-            .get(&state_key::ics20_value_balance::by_asset_id(
-                &msg.packet.chan_on_b,
-                &denom.id(),
-            ))
-            .await?
-            .unwrap_or_else(Amount::zero);
-
         let new_value_balance = value_balance.saturating_add(&value.amount);
         state.put(
-            state_key::ics20_value_balance::by_asset_id(&msg.packet.chan_on_b, &denom.id()),
+            state_key::ics20_value_balance::by_asset_id(&packet.chan_on_b, &denom.id()),
             new_value_balance,
         );
         state.record_proto(event::inbound_fungible_token_transfer(
@@ -453,8 +454,8 @@ async fn recv_transfer_packet_inner<S: StateWrite>(
             packet_data.sender.clone(),
             &receiver_address,
             FungibleTokenTransferPacketMetadata {
-                channel: msg.packet.chan_on_a.0.clone(),
-                sequence: msg.packet.sequence.0,
+                channel: packet.chan_on_a.0.clone(),
+                sequence: packet.sequence.0,
             },
         ));
     }
@@ -593,7 +594,7 @@ impl AppHandlerExecute for Ics20Transfer {
     async fn chan_close_init_execute<S: StateWrite>(_state: S, _msg: &MsgChannelCloseInit) {}
     async fn recv_packet_execute<S: StateWrite>(mut state: S, msg: &MsgRecvPacket) -> Result<()> {
         // recv packet should never fail a transaction, but it should record a failure acknowledgement.
-        let ack: Vec<u8> = match recv_transfer_packet_inner(&mut state, msg).await {
+        let ack: Vec<u8> = match recv_transfer_packet_inner(&mut state, &msg.packet).await {
             Ok(_) => {
                 // record packet acknowledgement without error
                 TokenTransferAcknowledgement::success().into()
@@ -650,3 +651,77 @@ impl AppHandlerExecute for Ics20Transfer {
 }
 
 impl AppHandler for Ics20Transfer {}
+
+#[cfg(test)]
+mod tests {
+    use cnidarium::{StateDelta, TempStorage};
+    use ibc_types::{core::channel::TimeoutHeight, timestamp::Timestamp};
+    use penumbra_keys::test_keys;
+
+    use super::*;
+    use crate::{
+        component::StateWriteExt as _,
+        params::ShieldedPoolParameters,
+        supply_cap::{AssetSupplyCap, SupplyCapError},
+    };
+
+    /// A transfer of `amount` uatom from a counterparty chain, arriving over `channel-0`.
+    fn inbound_transfer(sequence: u64, amount: u64) -> Packet {
+        let data = FungibleTokenPacketData {
+            denom: "uatom".to_string(),
+            amount: amount.to_string(),
+            sender: "cosmos1sender".to_string(),
+            receiver: test_keys::ADDRESS_0_STR.to_string(),
+            memo: String::new(),
+        };
+        Packet {
+            sequence: sequence.into(),
+            port_on_a: PortId::transfer(),
+            chan_on_a: "channel-1".parse().unwrap(),
+            port_on_b: PortId::transfer(),
+            chan_on_b: "channel-0".parse().unwrap(),
+            data: serde_json::to_vec(&data).unwrap(),
+            timeout_height_on_b: TimeoutHeight::Never,
+            timeout_timestamp_on_b: Timestamp::from_nanoseconds(0).unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn inbound_transfers_stop_at_the_supply_cap() -> anyhow::Result<()> {
+        let denom = asset::Metadata::try_from("transfer/channel-0/uatom")?;
+        let storage = TempStorage::new().await?;
+        let mut state = StateDelta::new(storage.latest_snapshot());
+        state.put_shielded_pool_params(ShieldedPoolParameters {
+            supply_caps: vec![AssetSupplyCap {
+                denom: denom.clone(),
+                max_supply: 150u64.into(),
+            }],
+            ..Default::default()
+        });
+
+        recv_transfer_packet_inner(&mut state, &inbound_transfer(1, 100)).await?;
+        // Reaching the cap exactly is allowed.
+        recv_transfer_packet_inner(&mut state, &inbound_transfer(2, 50)).await?;
+
+        let error = recv_transfer_packet_inner(&mut state, &inbound_transfer(3, 1))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<SupplyCapError>(),
+            Some(&SupplyCapError::SupplyCapExceeded {
+                asset: denom.id(),
+                current: 150u64.into(),
+                cap: 150u64.into(),
+            })
+        );
+
+        let supply: Option<Amount> = state
+            .get(&state_key::ics20_value_balance::by_asset_id(
+                &"channel-0".parse()?,
+                &denom.id(),
+            ))
+            .await?;
+        assert_eq!(supply, Some(150u64.into()));
+        Ok(())
+    }
+}
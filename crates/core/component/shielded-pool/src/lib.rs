@@ -11,6 +11,7 @@ pub mod fmd;
 pub mod genesis;
 pub mod params;
 pub mod state_key;
+pub mod supply_cap;
 
 pub mod note;
 mod note_payload;
@@ -223,10 +223,11 @@ impl Note {
 
     /// Encrypt a note, returning its ciphertext.
     pub fn encrypt(&self) -> NoteCiphertext {
-        let esk = self.ephemeral_secret_key();
-        let epk = esk.diversified_public(&self.diversified_generator());
-        let shared_secret = esk
-            .key_agreement_with(self.transmission_key())
+        // The note payload is encrypted with a fixed nonce, so go through `SingleUseSecret`
+        // to make sure the derived key agreement is only performed once here.
+        let esk = ka::SingleUseSecret::from(self.ephemeral_secret_key());
+        let (epk, shared_secret) = esk
+            .use_once(&self.diversified_generator(), self.transmission_key())
             .expect("key agreement succeeded");
 
         let key = PayloadKey::derive(&shared_secret, &epk);
@@ -1,3 +1,4 @@
+use anyhow::Context;
 use decaf377::{Fq, Fr};
 use decaf377_ka as ka;
 use penumbra_asset::{Balance, Value, STAKING_TOKEN_ASSET_ID};
@@ -6,6 +7,7 @@ use penumbra_keys::{
     symmetric::WrappedMemoKey,
     Address, PayloadKey,
 };
+use penumbra_num::field_utils::{bytes_to_fq, bytes_to_fr, fq_to_bytes, fr_to_bytes};
 use penumbra_proto::{core::component::shielded_pool::v1 as pb, DomainType};
 use rand_core::{CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
@@ -139,9 +141,9 @@ impl From<OutputPlan> for pb::OutputPlan {
             value: Some(msg.value.into()),
             dest_address: Some(msg.dest_address.into()),
             rseed: msg.rseed.to_bytes().to_vec(),
-            value_blinding: msg.value_blinding.to_bytes().to_vec(),
-            proof_blinding_r: msg.proof_blinding_r.to_bytes().to_vec(),
-            proof_blinding_s: msg.proof_blinding_s.to_bytes().to_vec(),
+            value_blinding: fr_to_bytes(msg.value_blinding).to_vec(),
+            proof_blinding_r: fq_to_bytes(msg.proof_blinding_r).to_vec(),
+            proof_blinding_s: fq_to_bytes(msg.proof_blinding_s).to_vec(),
         }
     }
 }
@@ -159,12 +161,12 @@ impl TryFrom<pb::OutputPlan> for OutputPlan {
                 .ok_or_else(|| anyhow::anyhow!("missing address"))?
                 .try_into()?,
             rseed: Rseed(msg.rseed.as_slice().try_into()?),
-            value_blinding: Fr::from_bytes_checked(msg.value_blinding.as_slice().try_into()?)
-                .expect("value_blinding malformed"),
-            proof_blinding_r: Fq::from_bytes_checked(msg.proof_blinding_r.as_slice().try_into()?)
-                .expect("proof_blinding_r malformed"),
-            proof_blinding_s: Fq::from_bytes_checked(msg.proof_blinding_s.as_slice().try_into()?)
-                .expect("proof_blinding_s malformed"),
+            value_blinding: bytes_to_fr(msg.value_blinding.as_slice().try_into()?)
+                .context("value_blinding malformed")?,
+            proof_blinding_r: bytes_to_fq(msg.proof_blinding_r.as_slice().try_into()?)
+                .context("proof_blinding_r malformed")?,
+            proof_blinding_s: bytes_to_fq(msg.proof_blinding_s.as_slice().try_into()?)
+                .context("proof_blinding_s malformed")?,
         })
     }
 }
@@ -1,9 +1,10 @@
+use penumbra_asset::asset;
 use penumbra_proto::penumbra::core::component::shielded_pool::v1 as pb;
 
 use penumbra_proto::DomainType;
 use serde::{Deserialize, Serialize};
 
-use crate::fmd;
+use crate::{fmd, supply_cap::AssetSupplyCap};
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq, Eq)]
 #[serde(
@@ -12,6 +13,15 @@ use crate::fmd;
 )]
 pub struct ShieldedPoolParameters {
     pub fmd_meta_params: fmd::MetaParameters,
+    /// Caps on the total supply of assets minted by inbound ICS-20 transfers.
+    pub supply_caps: Vec<AssetSupplyCap>,
+}
+
+impl ShieldedPoolParameters {
+    /// The supply cap for `asset_id`, if it has one.
+    pub fn supply_cap(&self, asset_id: &asset::Id) -> Option<&AssetSupplyCap> {
+        self.supply_caps.iter().find(|cap| cap.asset() == *asset_id)
+    }
 }
 
 impl DomainType for ShieldedPoolParameters {
@@ -27,6 +37,11 @@ impl TryFrom<pb::ShieldedPoolParameters> for ShieldedPoolParameters {
                 .fmd_meta_params
                 .ok_or_else(|| anyhow::anyhow!("missing fmd_meta_params"))?
                 .try_into()?,
+            supply_caps: msg
+                .supply_caps
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<anyhow::Result<_>>()?,
         })
     }
 }
@@ -37,6 +52,7 @@ impl From<ShieldedPoolParameters> for pb::ShieldedPoolParameters {
         pb::ShieldedPoolParameters {
             fmd_meta_params: Some(params.fmd_meta_params.into()),
             fixed_fmd_params: None,
+            supply_caps: params.supply_caps.into_iter().map(Into::into).collect(),
         }
     }
 }
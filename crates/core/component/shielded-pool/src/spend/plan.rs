@@ -1,7 +1,9 @@
+use anyhow::Context;
 use decaf377::{Fq, Fr};
 use decaf377_rdsa::{Signature, SpendAuth};
 use penumbra_asset::{Balance, Value, STAKING_TOKEN_ASSET_ID};
 use penumbra_keys::{keys::AddressIndex, FullViewingKey};
+use penumbra_num::field_utils::{bytes_to_fq, bytes_to_fr, fq_to_bytes, fr_to_bytes};
 use penumbra_proto::{core::component::shielded_pool::v1 as pb, DomainType};
 use penumbra_sct::Nullifier;
 use penumbra_tct as tct;
@@ -142,10 +144,10 @@ impl From<SpendPlan> for pb::SpendPlan {
         Self {
             note: Some(msg.note.into()),
             position: u64::from(msg.position),
-            randomizer: msg.randomizer.to_bytes().to_vec(),
-            value_blinding: msg.value_blinding.to_bytes().to_vec(),
-            proof_blinding_r: msg.proof_blinding_r.to_bytes().to_vec(),
-            proof_blinding_s: msg.proof_blinding_s.to_bytes().to_vec(),
+            randomizer: fr_to_bytes(msg.randomizer).to_vec(),
+            value_blinding: fr_to_bytes(msg.value_blinding).to_vec(),
+            proof_blinding_r: fq_to_bytes(msg.proof_blinding_r).to_vec(),
+            proof_blinding_s: fq_to_bytes(msg.proof_blinding_s).to_vec(),
         }
     }
 }
@@ -159,14 +161,14 @@ impl TryFrom<pb::SpendPlan> for SpendPlan {
                 .ok_or_else(|| anyhow::anyhow!("missing note"))?
                 .try_into()?,
             position: msg.position.into(),
-            randomizer: Fr::from_bytes_checked(msg.randomizer.as_slice().try_into()?)
-                .expect("randomizer malformed"),
-            value_blinding: Fr::from_bytes_checked(msg.value_blinding.as_slice().try_into()?)
-                .expect("value_blinding malformed"),
-            proof_blinding_r: Fq::from_bytes_checked(msg.proof_blinding_r.as_slice().try_into()?)
-                .expect("proof_blinding_r malformed"),
-            proof_blinding_s: Fq::from_bytes_checked(msg.proof_blinding_s.as_slice().try_into()?)
-                .expect("proof_blinding_s malformed"),
+            randomizer: bytes_to_fr(msg.randomizer.as_slice().try_into()?)
+                .context("randomizer malformed")?,
+            value_blinding: bytes_to_fr(msg.value_blinding.as_slice().try_into()?)
+                .context("value_blinding malformed")?,
+            proof_blinding_r: bytes_to_fq(msg.proof_blinding_r.as_slice().try_into()?)
+                .context("proof_blinding_r malformed")?,
+            proof_blinding_s: bytes_to_fq(msg.proof_blinding_s.as_slice().try_into()?)
+                .context("proof_blinding_s malformed")?,
         })
     }
 }
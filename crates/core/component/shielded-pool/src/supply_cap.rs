@@ -0,0 +1,192 @@
+use anyhow::anyhow;
+use penumbra_asset::{asset, Value};
+use penumbra_num::Amount;
+use penumbra_proto::{penumbra::core::component::shielded_pool::v1 as pb, DomainType};
+use serde::{Deserialize, Serialize};
+
+/// A maximum total supply for an asset that arrives over IBC.
+///
+/// Caps are only enforced when an inbound ICS-20 transfer mints new units of an asset, so the
+/// capped asset is named by its IBC-prefixed denom, and chain parameters capping any other asset
+/// are rejected. Value created any other way isn't checked against a cap: genesis allocations,
+/// community pool outputs, funding stream rewards, and value created by a transaction's own
+/// actions, such as delegations, swap claims and position withdrawals. Refunds of outbound
+/// transfers, and inbound transfers of assets this chain is the source of, release value that was
+/// escrowed, so they aren't checked either.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(try_from = "pb::AssetSupplyCap", into = "pb::AssetSupplyCap")]
+pub struct AssetSupplyCap {
+    pub denom: asset::Metadata,
+    pub max_supply: Amount,
+}
+
+impl AssetSupplyCap {
+    /// The ID of the capped asset.
+    pub fn asset(&self) -> asset::Id {
+        self.denom.id()
+    }
+
+    /// Whether the capped asset is one that arrives over IBC, which is the only kind of asset
+    /// whose cap is enforced.
+    pub fn is_ibc_asset(&self) -> bool {
+        matches!(self.denom.ibc_transfer_path(), Ok(Some(_)))
+    }
+}
+
+impl DomainType for AssetSupplyCap {
+    type Proto = pb::AssetSupplyCap;
+}
+
+impl TryFrom<pb::AssetSupplyCap> for AssetSupplyCap {
+    type Error = anyhow::Error;
+
+    fn try_from(msg: pb::AssetSupplyCap) -> anyhow::Result<Self> {
+        let denom: asset::Denom = msg
+            .denom
+            .ok_or_else(|| anyhow!("missing denom"))?
+            .try_into()?;
+        Ok(AssetSupplyCap {
+            denom: denom.denom.as_str().try_into()?,
+            max_supply: msg
+                .max_supply
+                .ok_or_else(|| anyhow!("missing max_supply"))?
+                .try_into()?,
+        })
+    }
+}
+
+impl From<AssetSupplyCap> for pb::AssetSupplyCap {
+    fn from(cap: AssetSupplyCap) -> Self {
+        pb::AssetSupplyCap {
+            denom: Some(cap.denom.base_denom().into()),
+            max_supply: Some(cap.max_supply.into()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum SupplyCapError {
+    #[error("minting would take the supply of {asset} from {current} past its cap of {cap}")]
+    SupplyCapExceeded {
+        asset: asset::Id,
+        current: Amount,
+        cap: Amount,
+    },
+}
+
+/// Checks that minting new value doesn't take an asset's supply past its cap.
+pub struct SupplyCapChecker;
+
+impl SupplyCapChecker {
+    /// Check that minting `minted` on top of a `current_supply` of the same asset stays within
+    /// `cap`.
+    ///
+    /// A supply exactly at the cap is allowed. Value of an asset other than the capped one is
+    /// always allowed.
+    pub fn check(
+        minted: Value,
+        current_supply: Amount,
+        cap: &AssetSupplyCap,
+    ) -> Result<(), SupplyCapError> {
+        if minted.asset_id != cap.asset() {
+            return Ok(());
+        }
+        match current_supply.checked_add(&minted.amount) {
+            Some(new_supply) if new_supply <= cap.max_supply => Ok(()),
+            _ => Err(SupplyCapError::SupplyCapExceeded {
+                asset: cap.asset(),
+                current: current_supply,
+                cap: cap.max_supply,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use penumbra_asset::STAKING_TOKEN_ASSET_ID;
+
+    use super::*;
+    use crate::params::ShieldedPoolParameters;
+
+    fn capped_denom() -> asset::Metadata {
+        asset::Metadata::try_from("transfer/channel-0/uatom").expect("valid ibc denom")
+    }
+
+    fn capped_asset() -> asset::Id {
+        capped_denom().id()
+    }
+
+    fn params() -> ShieldedPoolParameters {
+        ShieldedPoolParameters {
+            supply_caps: vec![AssetSupplyCap {
+                denom: capped_denom(),
+                max_supply: 1_000u64.into(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn check(asset_id: asset::Id, amount: u64, current_supply: u64) -> Result<(), SupplyCapError> {
+        let minted = Value {
+            asset_id,
+            amount: amount.into(),
+        };
+        match params().supply_cap(&asset_id) {
+            Some(cap) => SupplyCapChecker::check(minted, current_supply.into(), cap),
+            None => Ok(()),
+        }
+    }
+
+    #[test]
+    fn minting_up_to_the_cap_is_allowed() {
+        assert_eq!(check(capped_asset(), 400, 600), Ok(()));
+        assert_eq!(check(capped_asset(), 1_000, 0), Ok(()));
+    }
+
+    #[test]
+    fn minting_past_the_cap_is_rejected() {
+        assert_eq!(
+            check(capped_asset(), 401, 600),
+            Err(SupplyCapError::SupplyCapExceeded {
+                asset: capped_asset(),
+                current: 600u64.into(),
+                cap: 1_000u64.into(),
+            })
+        );
+    }
+
+    #[test]
+    fn uncapped_assets_are_always_allowed() {
+        assert_eq!(check(*STAKING_TOKEN_ASSET_ID, u64::MAX, u64::MAX), Ok(()));
+
+        // The checker itself ignores value of other assets, too.
+        let cap = &params().supply_caps[0];
+        let minted = Value {
+            asset_id: *STAKING_TOKEN_ASSET_ID,
+            amount: 2_000u64.into(),
+        };
+        assert_eq!(SupplyCapChecker::check(minted, 0u64.into(), cap), Ok(()));
+    }
+
+    #[test]
+    fn only_ibc_assets_can_be_capped() {
+        assert!(params().supply_caps[0].is_ibc_asset());
+
+        let native = AssetSupplyCap {
+            denom: asset::Cache::with_known_assets()
+                .get_unit("gm")
+                .expect("gm is a known asset")
+                .base(),
+            max_supply: 1_000u64.into(),
+        };
+        assert!(!native.is_ibc_asset());
+    }
+
+    #[test]
+    fn caps_round_trip_through_their_denom() {
+        let cap = params().supply_caps[0].clone();
+        let proto = pb::AssetSupplyCap::from(cap.clone());
+        assert_eq!(AssetSupplyCap::try_from(proto).unwrap(), cap);
+    }
+}
@@ -7,7 +7,7 @@ use penumbra_proto::{
 };
 use serde::{Deserialize, Serialize};
 
-use decaf377_rdsa::{SpendAuth, VerificationKeyBytes};
+use decaf377_rdsa::{SpendAuth, VerificationKey, VerificationKeyBytes};
 
 /// The root of a validator's identity.
 ///
@@ -26,6 +26,39 @@ impl IdentityKey {
     pub fn to_bytes(&self) -> [u8; 32] {
         self.0.into()
     }
+
+    /// Parse an identity key from its byte encoding, checking that it is a valid verification key.
+    pub fn from_bytes(bytes: [u8; 32]) -> anyhow::Result<Self> {
+        let vk = VerificationKey::<SpendAuth>::try_from(bytes)?;
+        Ok(Self(vk.into()))
+    }
+
+    /// A short, non-unique identifier for this key, for use in human-readable displays.
+    pub fn fingerprint(&self) -> IdentityKeyFingerprint {
+        let hash = blake2b_simd::blake2b(&self.to_bytes());
+        let mut fingerprint = [0u8; 8];
+        fingerprint.copy_from_slice(&hash.as_bytes()[..8]);
+        IdentityKeyFingerprint(fingerprint)
+    }
+}
+
+/// The first 8 bytes of the BLAKE2b hash of an [`IdentityKey`].
+///
+/// Fingerprints are only meant to help people tell validators apart at a glance; they are too
+/// short to be relied on to identify a validator.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IdentityKeyFingerprint(pub [u8; 8]);
+
+impl IdentityKeyFingerprint {
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.0)
+    }
+}
+
+impl std::fmt::Display for IdentityKeyFingerprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_hex())
+    }
 }
 
 // IMPORTANT: Changing this implementation is state-breaking.
@@ -92,3 +125,38 @@ impl TryFrom<CurrentValidatorRateRequest> for IdentityKey {
             .try_into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use decaf377_rdsa::SigningKey;
+    use rand_core::OsRng;
+
+    use super::*;
+
+    fn identity_key() -> IdentityKey {
+        let sk = SigningKey::<SpendAuth>::new(OsRng);
+        IdentityKey(VerificationKey::from(&sk).into())
+    }
+
+    #[test]
+    fn identity_key_round_trips() {
+        let ik = identity_key();
+
+        assert_eq!(IdentityKey::from_bytes(ik.to_bytes()).unwrap(), ik);
+
+        let encoded = ik.to_string();
+        assert!(encoded.starts_with(BECH32_PREFIX));
+        assert_eq!(encoded.parse::<IdentityKey>().unwrap(), ik);
+    }
+
+    #[test]
+    fn fingerprint_is_stable_and_short() {
+        let ik = identity_key();
+
+        let fingerprint = ik.fingerprint();
+        assert_eq!(fingerprint, ik.fingerprint());
+        assert_eq!(fingerprint.to_hex().len(), 16);
+        assert_eq!(fingerprint.to_string(), fingerprint.to_hex());
+        assert_ne!(fingerprint, identity_key().fingerprint());
+    }
+}
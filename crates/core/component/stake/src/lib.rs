@@ -10,6 +10,7 @@ mod event;
 mod governance_key;
 mod identity_key;
 mod penalty;
+mod reward;
 mod unbonding_token;
 mod uptime;
 
@@ -40,8 +41,9 @@ pub use self::undelegate_claim::{
 
 pub use self::delegation_token::DelegationToken;
 pub use self::governance_key::GovernanceKey;
-pub use self::identity_key::IdentityKey;
+pub use self::identity_key::{IdentityKey, IdentityKeyFingerprint};
 pub use self::penalty::Penalty;
+pub use self::reward::{EpochRewardDistributor, ValidatorReward, ValidatorStake};
 pub use self::unbonding_token::UnbondingToken;
 
 pub use self::changes::DelegationChanges;
@@ -0,0 +1,309 @@
+//! Splitting an epoch's staking issuance between validators.
+//!
+//! Staking rewards aren't paid out directly: delegators are rewarded by the growth of their
+//! validator's exchange rate, and commission is minted to funding streams by the funding
+//! component. [`EpochRewardDistributor`] approximates what those mechanisms amount to for each
+//! validator, in whole staking tokens, for use outside of consensus.
+//!
+//! The approximation splits the issuance in proportion to stake and commission rates. It doesn't
+//! reuse the exchange rate update or
+//! [`FundingStream::reward_amount`](crate::FundingStream::reward_amount), so its results can differ
+//! from the amounts actually issued.
+
+use penumbra_num::{fixpoint::U128x128, Amount};
+
+use crate::{rate::RateData, FundingStreams, IdentityKey};
+
+/// An active validator's stake at the end of an epoch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatorStake {
+    /// The validator's rates in the epoch, which identify the validator.
+    pub rate_data: RateData,
+    /// The validator's funding streams, which determine its commission.
+    pub funding_streams: FundingStreams,
+    /// The number of delegation tokens in the validator's pool.
+    pub delegation_pool_size: Amount,
+}
+
+impl ValidatorStake {
+    /// The amount of staking tokens delegated to the validator.
+    pub fn staked_amount(&self) -> Amount {
+        self.rate_data.unbonded_amount(self.delegation_pool_size)
+    }
+
+    /// The validator's total commission rate, in basis points.
+    fn commission_bps(&self) -> u16 {
+        self.funding_streams
+            .iter()
+            .map(|stream| stream.rate_bps())
+            .sum::<u16>()
+            .min(10_000)
+    }
+}
+
+/// A validator's share of an epoch's staking issuance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatorReward {
+    pub validator: IdentityKey,
+    /// The part of the reward going to the validator's delegators.
+    pub delegator_reward: Amount,
+    /// The part of the reward going to the validator's funding streams.
+    pub commission: Amount,
+}
+
+impl ValidatorReward {
+    /// The validator's whole share of the issuance.
+    pub fn total(&self) -> Amount {
+        self.delegator_reward + self.commission
+    }
+}
+
+/// Approximates how an epoch's staking issuance is split between validators.
+pub struct EpochRewardDistributor;
+
+impl EpochRewardDistributor {
+    /// The staking issuance for an epoch lasting `num_blocks` blocks.
+    ///
+    /// Issuance is a constant amount per block, so this matches the budget the distributions
+    /// component computes at the end of each epoch.
+    pub fn total_issuance(staking_issuance_per_block: u64, num_blocks: u64) -> Amount {
+        Amount::from(u128::from(staking_issuance_per_block) * u128::from(num_blocks))
+    }
+
+    /// Split `total_issuance` between `validators` in proportion to their stake, and each
+    /// validator's share between its delegators and its funding streams.
+    ///
+    /// Shares are rounded down, and the few units lost to rounding go to the validators with the
+    /// most stake, one each, so that the rewards add up to exactly `total_issuance`. If no stake
+    /// is delegated to any of the validators, there's nothing to split it by, so no rewards are
+    /// returned.
+    pub fn compute(validators: &[ValidatorStake], total_issuance: Amount) -> Vec<ValidatorReward> {
+        let stakes: Vec<Amount> = validators
+            .iter()
+            .map(ValidatorStake::staked_amount)
+            .collect();
+        let total_stake = stakes
+            .iter()
+            .try_fold(Amount::zero(), |total, stake| total.checked_add(stake))
+            .expect("total stake is less than the staking token supply");
+        if total_stake == Amount::zero() {
+            return Vec::new();
+        }
+
+        let mut shares: Vec<Amount> = stakes
+            .iter()
+            .map(|&stake| {
+                U128x128::ratio(stake, total_stake)
+                    .and_then(|fraction| fraction.apply_to_amount(&total_issuance))
+                    .expect("a fraction of at most one of the issuance does not overflow")
+            })
+            .collect();
+
+        // Rounding down loses less than a unit per share, plus a vanishing fixed-point error.
+        let mut remainder = total_issuance - shares.iter().copied().sum::<Amount>();
+        let mut by_stake: Vec<usize> = (0..validators.len()).collect();
+        by_stake.sort_by(|&a, &b| {
+            stakes[b].cmp(&stakes[a]).then_with(|| {
+                validators[a]
+                    .rate_data
+                    .identity_key
+                    .cmp(&validators[b].rate_data.identity_key)
+            })
+        });
+        for &i in by_stake.iter().cycle() {
+            if remainder == Amount::zero() {
+                break;
+            }
+            shares[i] += Amount::from(1u64);
+            remainder -= Amount::from(1u64);
+        }
+
+        validators
+            .iter()
+            .zip(shares)
+            .map(|(validator, share)| {
+                // Split the share into whole and partial multiples of 10,000, so that the
+                // commission is rounded down exactly, and can't overflow.
+                let bps = u128::from(validator.commission_bps());
+                let (whole, partial) = (share.value() / 10_000, share.value() % 10_000);
+                let commission = Amount::from(whole * bps + partial * bps / 10_000);
+                ValidatorReward {
+                    validator: validator.rate_data.identity_key,
+                    delegator_reward: share - commission,
+                    commission,
+                }
+            })
+            .collect()
+    }
+
+    /// Whether `rewards` add up to exactly `issuance`.
+    pub fn verify_conservation(rewards: &[ValidatorReward], issuance: Amount) -> bool {
+        rewards.iter().try_fold(Amount::zero(), |total, reward| {
+            total
+                .checked_add(&reward.delegator_reward)?
+                .checked_add(&reward.commission)
+        }) == Some(issuance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use decaf377_rdsa as rdsa;
+    use penumbra_keys::Address;
+    use rand_core::OsRng;
+
+    use super::*;
+    use crate::FundingStream;
+
+    fn validator(delegation_pool_size: u64, exchange_rate: u128, bps: &[u16]) -> ValidatorStake {
+        let vk = rdsa::VerificationKey::from(rdsa::SigningKey::new(OsRng));
+        let funding_streams = bps
+            .iter()
+            .map(|&rate_bps| FundingStream::ToAddress {
+                address: Address::dummy(&mut OsRng),
+                rate_bps,
+            })
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap();
+        ValidatorStake {
+            rate_data: RateData {
+                identity_key: IdentityKey(vk.into()),
+                validator_reward_rate: 0u128.into(),
+                validator_exchange_rate: exchange_rate.into(),
+            },
+            funding_streams,
+            delegation_pool_size: delegation_pool_size.into(),
+        }
+    }
+
+    #[test]
+    fn issuance_is_constant_per_block() {
+        assert_eq!(
+            EpochRewardDistributor::total_issuance(1_000_000, 719),
+            719_000_000u64.into()
+        );
+        assert_eq!(
+            EpochRewardDistributor::total_issuance(1_000_000, 0),
+            Amount::zero()
+        );
+        assert_eq!(
+            EpochRewardDistributor::total_issuance(u64::MAX, u64::MAX),
+            (u128::from(u64::MAX) * u128::from(u64::MAX)).into()
+        );
+    }
+
+    #[test]
+    fn rewards_are_proportional_to_stake() {
+        // An exchange rate of 2 doubles the second validator's stake.
+        let validators = [
+            validator(100, 1_0000_0000, &[]),
+            validator(100, 2_0000_0000, &[]),
+            validator(100, 1_0000_0000, &[]),
+        ];
+        let issuance = Amount::from(4_000u64);
+        let rewards = EpochRewardDistributor::compute(&validators, issuance);
+
+        let totals: Vec<Amount> = rewards.iter().map(ValidatorReward::total).collect();
+        assert_eq!(totals, [1_000u64.into(), 2_000u64.into(), 1_000u64.into()]);
+        assert!(EpochRewardDistributor::verify_conservation(
+            &rewards, issuance
+        ));
+    }
+
+    #[test]
+    fn rounding_dust_goes_to_the_largest_stake() {
+        // 1000 split three ways is 333 each, which leaves 1 over.
+        let validators = [
+            validator(10, 1_0000_0000, &[]),
+            validator(10, 1_0000_0000, &[]),
+            validator(10, 1_0000_0000, &[]),
+        ];
+        let issuance = Amount::from(1_000u64);
+        let rewards = EpochRewardDistributor::compute(&validators, issuance);
+        assert!(EpochRewardDistributor::verify_conservation(
+            &rewards, issuance
+        ));
+        let mut totals: Vec<u128> = rewards.iter().map(|r| r.total().value()).collect();
+        totals.sort();
+        assert_eq!(totals, [333, 333, 334]);
+
+        // With unequal stakes, the validator with the most stake collects it.
+        let validators = [
+            validator(1, 1_0000_0000, &[]),
+            validator(1, 1_0000_0000, &[]),
+            validator(2, 1_0000_0000, &[]),
+        ];
+        let rewards = EpochRewardDistributor::compute(&validators, 1_001u64.into());
+        let totals: Vec<u128> = rewards.iter().map(|r| r.total().value()).collect();
+        assert_eq!(totals, [250, 250, 501]);
+    }
+
+    #[test]
+    fn rewards_sum_to_the_issuance() {
+        let validators: Vec<ValidatorStake> = (1..=20u64)
+            .map(|i| {
+                validator(
+                    i * 7_919,
+                    1_0000_0000 + u128::from(i) * 1_234_567,
+                    &[i as u16 * 100],
+                )
+            })
+            .collect();
+        for issuance in [0u64, 1, 19, 20, 21, 1_000_003, 719_000_000] {
+            let issuance = Amount::from(issuance);
+            let rewards = EpochRewardDistributor::compute(&validators, issuance);
+            assert_eq!(rewards.len(), validators.len());
+            assert!(EpochRewardDistributor::verify_conservation(
+                &rewards, issuance
+            ));
+        }
+    }
+
+    #[test]
+    fn commission_is_split_by_funding_stream_rates() {
+        let validators = [
+            // 5% and 10%, so 15% in all.
+            validator(100, 1_0000_0000, &[500, 1_000]),
+            validator(100, 1_0000_0000, &[]),
+            validator(100, 1_0000_0000, &[10_000]),
+        ];
+        let rewards = EpochRewardDistributor::compute(&validators, 3_000u64.into());
+
+        assert_eq!(rewards[0].commission, 150u64.into());
+        assert_eq!(rewards[0].delegator_reward, 850u64.into());
+        assert_eq!(rewards[1].commission, Amount::zero());
+        assert_eq!(rewards[1].delegator_reward, 1_000u64.into());
+        assert_eq!(rewards[2].commission, 1_000u64.into());
+        assert_eq!(rewards[2].delegator_reward, Amount::zero());
+        for (reward, validator) in rewards.iter().zip(&validators) {
+            assert_eq!(reward.validator, validator.rate_data.identity_key);
+        }
+    }
+
+    #[test]
+    fn conservation_detects_missing_and_extra_rewards() {
+        let validators = [validator(100, 1_0000_0000, &[2_500])];
+        let mut rewards = EpochRewardDistributor::compute(&validators, 1_000u64.into());
+        assert!(EpochRewardDistributor::verify_conservation(
+            &rewards,
+            1_000u64.into()
+        ));
+        assert!(!EpochRewardDistributor::verify_conservation(
+            &rewards,
+            1_001u64.into()
+        ));
+        rewards[0].commission += Amount::from(1u64);
+        assert!(!EpochRewardDistributor::verify_conservation(
+            &rewards,
+            1_000u64.into()
+        ));
+    }
+
+    #[test]
+    fn no_stake_means_no_rewards() {
+        let validators = [validator(0, 1_0000_0000, &[])];
+        assert!(EpochRewardDistributor::compute(&validators, 1_000u64.into()).is_empty());
+        assert!(EpochRewardDistributor::compute(&[], 1_000u64.into()).is_empty());
+    }
+}
@@ -1,6 +1,10 @@
+use anyhow::Context;
 use decaf377::{Fq, Fr};
 use penumbra_asset::{asset, balance, Balance};
-use penumbra_num::Amount;
+use penumbra_num::{
+    field_utils::{bytes_to_fq, bytes_to_fr, fq_to_bytes, fr_to_bytes},
+    Amount,
+};
 use penumbra_proof_params::CONVERT_PROOF_PROVING_KEY;
 use penumbra_proto::{penumbra::core::component::stake::v1 as pb, DomainType};
 
@@ -102,9 +106,9 @@ impl From<UndelegateClaimPlan> for pb::UndelegateClaimPlan {
             penalty: Some(msg.penalty.into()),
             unbonding_amount: Some(msg.unbonding_amount.into()),
             unbonding_start_height: msg.unbonding_start_height,
-            balance_blinding: msg.balance_blinding.to_bytes().to_vec(),
-            proof_blinding_r: msg.proof_blinding_r.to_bytes().to_vec(),
-            proof_blinding_s: msg.proof_blinding_s.to_bytes().to_vec(),
+            balance_blinding: fr_to_bytes(msg.balance_blinding).to_vec(),
+            proof_blinding_r: fq_to_bytes(msg.proof_blinding_r).to_vec(),
+            proof_blinding_s: fq_to_bytes(msg.proof_blinding_s).to_vec(),
         }
     }
 }
@@ -134,17 +138,17 @@ impl TryFrom<pb::UndelegateClaimPlan> for UndelegateClaimPlan {
                 .unbonding_amount
                 .ok_or_else(|| anyhow::anyhow!("missing unbonding_amount"))?
                 .try_into()?,
-            balance_blinding: Fr::from_bytes_checked(
+            balance_blinding: bytes_to_fr(
                 msg.balance_blinding
                     .as_slice()
                     .try_into()
                     .map_err(|_| anyhow::anyhow!("expected 32 bytes"))?,
             )
             .map_err(|_| anyhow::anyhow!("invalid balance_blinding"))?,
-            proof_blinding_r: Fq::from_bytes_checked(&proof_blinding_r_bytes)
-                .expect("proof_blinding_r malformed"),
-            proof_blinding_s: Fq::from_bytes_checked(&proof_blinding_s_bytes)
-                .expect("proof_blinding_s malformed"),
+            proof_blinding_r: bytes_to_fq(&proof_blinding_r_bytes)
+                .context("proof_blinding_r malformed")?,
+            proof_blinding_s: bytes_to_fq(&proof_blinding_s_bytes)
+                .context("proof_blinding_s malformed")?,
             unbonding_start_height: msg.unbonding_start_height,
         })
     }
@@ -18,6 +18,9 @@ use sha2::{Digest, Sha256};
 mod r1cs;
 pub use r1cs::AddressVar;
 
+mod validation;
+pub use validation::{AddressError, AddressValidation};
+
 mod view;
 pub use view::AddressView;
 
@@ -319,23 +322,7 @@ impl std::str::FromStr for Address {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.starts_with(bech32str::compat_address::BECH32_PREFIX) {
-            pb::Address {
-                inner: bech32str::decode(
-                    s,
-                    bech32str::compat_address::BECH32_PREFIX,
-                    bech32str::Bech32,
-                )?,
-                alt_bech32m: String::new(),
-            }
-            .try_into()
-        } else {
-            pb::Address {
-                inner: bech32str::decode(s, bech32str::address::BECH32_PREFIX, bech32str::Bech32m)?,
-                alt_bech32m: String::new(),
-            }
-            .try_into()
-        }
+        Ok(AddressValidation::check(s)?)
     }
 }
 
@@ -0,0 +1,205 @@
+use bech32::{FromBase32, Variant};
+use penumbra_proto::serializers::bech32str::{address, compat_address};
+
+use super::{Address, ADDRESS_LEN_BYTES};
+
+/// Why a string could not be decoded as an [`Address`].
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum AddressError {
+    #[error("invalid bech32 encoding: {reason}")]
+    InvalidBech32 { reason: String },
+    #[error("wrong bech32 human readable part {got}, expected {expected}")]
+    WrongHrp { expected: String, got: String },
+    #[error("address has an invalid transmission key")]
+    InvalidTransmissionKey,
+    #[error("invalid bech32 checksum")]
+    InvalidChecksum,
+}
+
+impl AddressError {
+    /// A description of the error that a wallet can show to its user as is.
+    pub fn user_facing_message(&self) -> String {
+        match self {
+            AddressError::InvalidBech32 { .. } => {
+                "This is not a valid Penumbra address. Check that the whole address was copied."
+                    .to_string()
+            }
+            AddressError::WrongHrp { got, .. } => format!(
+                "This is not a Penumbra address: Penumbra addresses start with \"{}1\", but this one starts with \"{got}1\".",
+                address::BECH32_PREFIX
+            ),
+            AddressError::InvalidTransmissionKey => {
+                "This address is corrupted and can't receive funds. Ask the recipient for their address again."
+                    .to_string()
+            }
+            AddressError::InvalidChecksum => {
+                "This address contains a typo. Check that it was copied correctly.".to_string()
+            }
+        }
+    }
+}
+
+/// Decodes addresses, explaining what is wrong with the ones that don't decode.
+pub struct AddressValidation;
+
+impl AddressValidation {
+    /// Decode a Bech32m address, or a Bech32 compat address.
+    ///
+    /// Diversifiers and clue keys aren't checked: every diversifier has a diversified base, and
+    /// clues can be created for addresses with an invalid clue key, so neither makes an address
+    /// unusable.
+    pub fn check(bech32_str: &str) -> Result<Address, AddressError> {
+        let (hrp, data, variant) = bech32::decode(bech32_str).map_err(|e| match e {
+            bech32::Error::InvalidChecksum => AddressError::InvalidChecksum,
+            e => AddressError::InvalidBech32 {
+                reason: e.to_string(),
+            },
+        })?;
+
+        let expected_variant = if hrp == address::BECH32_PREFIX {
+            Variant::Bech32m
+        } else if hrp == compat_address::BECH32_PREFIX {
+            Variant::Bech32
+        } else {
+            return Err(AddressError::WrongHrp {
+                expected: address::BECH32_PREFIX.to_string(),
+                got: hrp,
+            });
+        };
+        if variant != expected_variant {
+            return Err(AddressError::InvalidBech32 {
+                reason: format!("expected {expected_variant:?} encoding, found {variant:?}"),
+            });
+        }
+
+        let bytes = Vec::<u8>::from_base32(&data).map_err(|e| AddressError::InvalidBech32 {
+            reason: e.to_string(),
+        })?;
+        if bytes.len() != ADDRESS_LEN_BYTES {
+            return Err(AddressError::InvalidBech32 {
+                reason: format!(
+                    "expected {ADDRESS_LEN_BYTES} bytes of address data, found {}",
+                    bytes.len()
+                ),
+            });
+        }
+
+        // With the length checked, the only way decoding the bytes can fail is a transmission
+        // key that isn't a canonical field element.
+        Address::try_from(&bytes[..]).map_err(|_| AddressError::InvalidTransmissionKey)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use f4jumble::{f4jumble, f4jumble_inv};
+    use penumbra_proto::serializers::bech32str;
+    use rand_core::OsRng;
+
+    use super::*;
+
+    fn address() -> Address {
+        Address::dummy(&mut OsRng)
+    }
+
+    #[test]
+    fn valid_addresses_are_accepted() {
+        let address = address();
+        assert_eq!(
+            AddressValidation::check(&address.to_string()),
+            Ok(address.clone())
+        );
+        assert_eq!(
+            AddressValidation::check(&address.compat_encoding()),
+            Ok(address)
+        );
+    }
+
+    #[test]
+    fn flipped_characters_fail_the_checksum() {
+        let encoded = address().to_string();
+        let mut chars: Vec<char> = encoded.chars().collect();
+        let last = chars.len() - 1;
+        chars[last] = if chars[last] == 'q' { 'p' } else { 'q' };
+        let flipped: String = chars.into_iter().collect();
+        assert_eq!(
+            AddressValidation::check(&flipped),
+            Err(AddressError::InvalidChecksum)
+        );
+    }
+
+    #[test]
+    fn non_bech32_strings_are_rejected() {
+        for bad in ["", "penumbra", "penumbra1", "not an address"] {
+            assert!(matches!(
+                AddressValidation::check(bad),
+                Err(AddressError::InvalidBech32 { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn wrong_variant_and_length_are_rejected() {
+        let bytes = address().to_vec();
+        let bech32 = bech32str::encode(&bytes, address::BECH32_PREFIX, bech32str::Bech32);
+        assert!(matches!(
+            AddressValidation::check(&bech32),
+            Err(AddressError::InvalidBech32 { .. })
+        ));
+
+        let short = bech32str::encode(&bytes[..40], address::BECH32_PREFIX, bech32str::Bech32m);
+        assert!(matches!(
+            AddressValidation::check(&short),
+            Err(AddressError::InvalidBech32 { .. })
+        ));
+    }
+
+    #[test]
+    fn other_networks_are_reported() {
+        let other = bech32str::encode(&address().to_vec(), "cosmos", bech32str::Bech32m);
+        let error = AddressValidation::check(&other).unwrap_err();
+        assert_eq!(
+            error,
+            AddressError::WrongHrp {
+                expected: "penumbra".to_string(),
+                got: "cosmos".to_string(),
+            }
+        );
+        assert!(error.user_facing_message().contains("\"cosmos1\""));
+    }
+
+    #[test]
+    fn non_canonical_transmission_keys_are_rejected() {
+        let mut bytes = f4jumble_inv(&address().to_vec()).unwrap();
+        // Setting the top bit of the transmission key puts it above the field modulus.
+        bytes[16 + 31] |= 0x80;
+        let corrupted = bech32str::encode(
+            &f4jumble(&bytes).unwrap(),
+            address::BECH32_PREFIX,
+            bech32str::Bech32m,
+        );
+        assert_eq!(
+            AddressValidation::check(&corrupted),
+            Err(AddressError::InvalidTransmissionKey)
+        );
+    }
+
+    #[test]
+    fn every_error_has_a_user_facing_message() {
+        for error in [
+            AddressError::InvalidBech32 {
+                reason: "missing separator".to_string(),
+            },
+            AddressError::WrongHrp {
+                expected: "penumbra".to_string(),
+                got: "osmo".to_string(),
+            },
+            AddressError::InvalidTransmissionKey,
+            AddressError::InvalidChecksum,
+        ] {
+            let message = error.user_facing_message();
+            assert!(message.ends_with('.'));
+            assert!(!message.contains("bech32"));
+        }
+    }
+}
@@ -11,7 +11,7 @@ mod spend;
 pub use spend::{SpendKey, SpendKeyBytes, SPENDKEY_LEN_BYTES};
 
 mod bip44;
-pub use bip44::Bip44Path;
+pub use bip44::{Bip44Path, HardenedDerivationPath};
 
 mod wallet_id;
 pub use wallet_id::WalletId;
@@ -86,6 +86,45 @@ impl Bip44Path {
     }
 }
 
+/// Identifies a spend key derived from a master seed by account and address index.
+///
+/// Penumbra keys aren't on the secp256k1 curve, so rather than BIP32, keys at this path are
+/// derived with BLAKE2b; see [`SpendKey::from_hd_path`](crate::keys::SpendKey::from_hd_path).
+/// The path is still written in BIP44 form, so that it can be displayed alongside other wallets'.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HardenedDerivationPath {
+    /// The account, which is hardened, and so must be less than 2^31.
+    pub account: u32,
+    /// The index of the key within the account.
+    pub index: u32,
+}
+
+impl HardenedDerivationPath {
+    /// The first hardened index; a hardened account is written with a `'` rather than by
+    /// adding this offset.
+    pub const HARDENED_OFFSET: u32 = 1 << 31;
+
+    /// Check that the account is in the hardened range.
+    pub fn check(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.account < Self::HARDENED_OFFSET,
+            "account {} is too large to be hardened",
+            self.account
+        );
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for HardenedDerivationPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "m/44'/{}'/{}'/0/{}",
+            PENUMBRA_COIN_TYPE, self.account, self.index
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -101,4 +140,20 @@ mod tests {
         let path = Bip44Path::new(0);
         assert_eq!(path.path(), "m/44'/6532'/0'");
     }
+
+    #[test]
+    fn hardened_derivation_path_display() {
+        let path = HardenedDerivationPath {
+            account: 3,
+            index: 17,
+        };
+        assert_eq!(path.to_string(), "m/44'/6532'/3'/0/17");
+        assert!(path.check().is_ok());
+
+        let too_large = HardenedDerivationPath {
+            account: HardenedDerivationPath::HARDENED_OFFSET,
+            index: 0,
+        };
+        assert!(too_large.check().is_err());
+    }
 }
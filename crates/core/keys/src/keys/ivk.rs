@@ -12,7 +12,9 @@ use super::{AddressIndex, Diversifier, DiversifierKey};
 use crate::{
     fmd, ka,
     keys::{AuthorizationKeyVar, NullifierKeyVar, IVK_DOMAIN_SEP},
-    prf, Address,
+    prf,
+    symmetric::MemoKey,
+    Address,
 };
 
 pub const IVK_LEN_BYTES: usize = 64;
@@ -75,6 +77,16 @@ impl IncomingViewingKey {
         self.dk.index_for_diversifier(diversifier)
     }
 
+    /// Derive the [`MemoKey`] that auditors can use to read memos encrypted to this key.
+    pub fn memo_key(&self) -> MemoKey {
+        let hash = prf::expand(b"Penumbra_AudMemo", &self.ivk.to_bytes(), &[]);
+        MemoKey(
+            hash.as_bytes()[..32]
+                .try_into()
+                .expect("blake2b output is at least 32 bytes"),
+        )
+    }
+
     /// Check whether this address is viewable by this incoming viewing key.
     pub fn views_address(&self, address: &Address) -> bool {
         self.ivk.diversified_public(address.diversified_generator()) == *address.transmission_key()
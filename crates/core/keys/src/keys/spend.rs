@@ -7,7 +7,7 @@ use penumbra_proto::{penumbra::core::keys::v1 as pb, DomainType};
 use serde::{Deserialize, Serialize};
 
 use super::{
-    bip44::Bip44Path,
+    bip44::{Bip44Path, HardenedDerivationPath},
     seed_phrase::{SeedPhrase, NUM_PBKDF2_ROUNDS},
     FullViewingKey, IncomingViewingKey, NullifierKey, OutgoingViewingKey,
 };
@@ -128,6 +128,44 @@ impl SpendKey {
         SpendKeyBytes(child_key_bytes).into()
     }
 
+    /// Deterministically derive the [`SpendKey`] at `path` from a 64-byte `master_seed`.
+    ///
+    /// Each account and index gives an independent key, derived with BLAKE2b keyed by the
+    /// master seed. Fails if the account isn't in the hardened range.
+    pub fn from_hd_path(
+        master_seed: &[u8; 64],
+        path: &HardenedDerivationPath,
+    ) -> anyhow::Result<Self> {
+        path.check()?;
+        let mut input = [0u8; 8];
+        input[..4].copy_from_slice(&path.account.to_le_bytes());
+        input[4..].copy_from_slice(&path.index.to_le_bytes());
+        let hash = prf::expand(b"Penumbra_HDSpend", master_seed, &input);
+
+        let mut seed = [0u8; SPENDKEY_LEN_BYTES];
+        seed.copy_from_slice(&hash.as_bytes()[0..SPENDKEY_LEN_BYTES]);
+        Ok(SpendKeyBytes(seed).into())
+    }
+
+    /// Derive the [`SpendKey`] at `path` from the master seed of a BIP39 `seed_phrase`.
+    ///
+    /// The master seed is computed as specified in BIP39, with an empty passphrase.
+    pub fn from_bip39_mnemonic_at_path(
+        seed_phrase: &SeedPhrase,
+        path: &HardenedDerivationPath,
+    ) -> anyhow::Result<Self> {
+        let password = format!("{seed_phrase}");
+        let mut master_seed = [0u8; 64];
+        pbkdf2::<Hmac<sha2::Sha512>>(
+            password.as_bytes(),
+            b"mnemonic",
+            NUM_PBKDF2_ROUNDS,
+            &mut master_seed,
+        )
+        .expect("seed phrase hash always succeeds");
+        Self::from_hd_path(&master_seed, path)
+    }
+
     // XXX how many of these do we need? leave them for now
     // but don't document until design is more settled
 
@@ -216,4 +254,74 @@ mod tests {
 
         assert_eq!(software_spendkey.to_bytes(), expected_spendkey);
     }
+
+    /// Regression values generated by this implementation, rather than taken from an external
+    /// source, so that the derivation can't change unnoticed.
+    #[test]
+    fn hd_path_test_vectors() {
+        let mut counting_seed = [0u8; 64];
+        for (i, byte) in counting_seed.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        let vectors: [([u8; 64], u32, u32, &str); 4] = [
+            (
+                [0; 64],
+                0,
+                0,
+                "70b747e47a268fd4a12fbaf13b32b58aa419f15661d9965fed1529d0c0d00b2e",
+            ),
+            (
+                [1; 64],
+                0,
+                1,
+                "aac8ecd77b7988808c4b5b5c02cc9d726184d11ba566dc3cbeb7fe8815728fb5",
+            ),
+            (
+                counting_seed,
+                1,
+                0,
+                "91b2b1384f76f98e79bf99833b74146f1855c7f9f75a0d9be5b2786386fd90e4",
+            ),
+            (
+                [0xff; 64],
+                7,
+                42,
+                "3ae4bf742c06f5367b4576aea9161930083f2c2c75943c977b1be00b5157b848",
+            ),
+        ];
+        for (master_seed, account, index, expected) in vectors {
+            let path = HardenedDerivationPath { account, index };
+            assert_eq!(
+                hex::encode(
+                    SpendKey::from_hd_path(&master_seed, &path)
+                        .unwrap()
+                        .to_bytes()
+                        .0
+                ),
+                expected,
+                "spend key at {path}"
+            );
+        }
+
+        // The fifth vector goes through the BIP39 master seed of a seed phrase.
+        let seed = SeedPhrase::from_str("comfort ten front cycle churn burger oak absent rice ice urge result art couple benefit cabbage frequent obscure hurry trick segment cool job debate").unwrap();
+        let path = HardenedDerivationPath {
+            account: 0,
+            index: 0,
+        };
+        let spend_key = SpendKey::from_bip39_mnemonic_at_path(&seed, &path).unwrap();
+        assert_eq!(
+            hex::encode(spend_key.to_bytes().0),
+            "c8862395788d9c7e98f128f7fd36b5759add819c40ac04225d40d2f18d5491ff"
+        );
+    }
+
+    #[test]
+    fn unhardened_accounts_are_rejected() {
+        let path = HardenedDerivationPath {
+            account: HardenedDerivationPath::HARDENED_OFFSET,
+            index: 0,
+        };
+        assert!(SpendKey::from_hd_path(&[0; 64], &path).is_err());
+    }
 }
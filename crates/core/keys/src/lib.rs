@@ -12,7 +12,7 @@ pub mod prf;
 pub mod symmetric;
 pub mod test_keys;
 
-pub use address::{Address, AddressVar, AddressView};
+pub use address::{Address, AddressError, AddressValidation, AddressVar, AddressView};
 pub use keys::FullViewingKey;
 pub use symmetric::PayloadKey;
 
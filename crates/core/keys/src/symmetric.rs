@@ -14,6 +14,35 @@ pub const PAYLOAD_KEY_LEN_BYTES: usize = 32;
 pub const OVK_WRAPPED_LEN_BYTES: usize = 48;
 pub const MEMOKEY_WRAPPED_LEN_BYTES: usize = 48;
 
+fn swap_kdf_params() -> blake2b_simd::Params {
+    let mut kdf_params = blake2b_simd::Params::new();
+    kdf_params.personal(b"Penumbra_Payswap");
+    kdf_params.hash_length(32);
+    kdf_params
+}
+
+fn ock_kdf_params() -> blake2b_simd::Params {
+    let mut kdf_params = blake2b_simd::Params::new();
+    kdf_params.hash_length(32);
+    kdf_params.personal(b"Penumbra_OutCiph");
+    kdf_params
+}
+
+/// Hash each of `inputs` with `params`, using `blake2b_simd`'s parallel API.
+///
+/// `blake2b_simd` detects at runtime whether the CPU supports AVX2, and falls back to hashing one
+/// input at a time otherwise, so this is always safe to call.
+fn hash_many<'a>(
+    params: &blake2b_simd::Params,
+    inputs: impl Iterator<Item = &'a [u8]>,
+) -> Vec<blake2b_simd::Hash> {
+    let mut jobs: Vec<blake2b_simd::many::HashManyJob> = inputs
+        .map(|input| blake2b_simd::many::HashManyJob::new(params, input))
+        .collect();
+    blake2b_simd::many::hash_many(jobs.iter_mut());
+    jobs.iter().map(|job| job.to_hash()).collect()
+}
+
 /// Represents the item to be encrypted/decrypted with the [`PayloadKey`].
 pub enum PayloadKind {
     /// Note is action-scoped.
@@ -95,10 +124,7 @@ impl PayloadKey {
     pub fn derive_swap(ovk: &OutgoingViewingKey, cm: StateCommitment) -> Self {
         let cm_bytes: [u8; 32] = cm.into();
 
-        let mut kdf_params = blake2b_simd::Params::new();
-        kdf_params.personal(b"Penumbra_Payswap");
-        kdf_params.hash_length(32);
-        let mut kdf = kdf_params.to_state();
+        let mut kdf = swap_kdf_params().to_state();
         kdf.update(&ovk.to_bytes());
         kdf.update(&cm_bytes);
 
@@ -106,6 +132,30 @@ impl PayloadKey {
         Self(*Key::from_slice(key.as_bytes()))
     }
 
+    /// Derive the swap encryption keys for many swap commitments at once.
+    ///
+    /// This produces the same keys as calling [`PayloadKey::derive_swap`] on each commitment,
+    /// but hashes several inputs in parallel using SIMD instructions where the CPU supports
+    /// them, which makes it faster when scanning a batch of swaps.
+    pub fn derive_swap_many(ovk: &OutgoingViewingKey, cms: &[StateCommitment]) -> Vec<Self> {
+        let ovk_bytes = ovk.to_bytes();
+        let inputs: Vec<[u8; 64]> = cms
+            .iter()
+            .map(|cm| {
+                let cm_bytes: [u8; 32] = (*cm).into();
+                let mut input = [0u8; 64];
+                input[..32].copy_from_slice(&ovk_bytes);
+                input[32..].copy_from_slice(&cm_bytes);
+                input
+            })
+            .collect();
+
+        hash_many(&swap_kdf_params(), inputs.iter().map(|input| &input[..]))
+            .into_iter()
+            .map(|key| Self(*Key::from_slice(key.as_bytes())))
+            .collect()
+    }
+
     /// Encrypt a swap using the `PayloadKey`.
     pub fn encrypt_swap(&self, plaintext: Vec<u8>) -> Vec<u8> {
         let cipher = ChaCha20Poly1305::new(&self.0);
@@ -188,10 +238,7 @@ impl OutgoingCipherKey {
         let cv_bytes: [u8; 32] = cv.into();
         let cm_bytes: [u8; 32] = cm.into();
 
-        let mut kdf_params = blake2b_simd::Params::new();
-        kdf_params.hash_length(32);
-        kdf_params.personal(b"Penumbra_OutCiph");
-        let mut kdf = kdf_params.to_state();
+        let mut kdf = ock_kdf_params().to_state();
         kdf.update(&ovk.to_bytes());
         kdf.update(&cv_bytes);
         kdf.update(&cm_bytes);
@@ -201,6 +248,35 @@ impl OutgoingCipherKey {
         Self(*Key::from_slice(key.as_bytes()))
     }
 
+    /// Derive many outgoing cipher keys at once, from the OVK and each output's public fields.
+    ///
+    /// This produces the same keys as calling [`OutgoingCipherKey::derive`] on each output, but
+    /// hashes several inputs in parallel using SIMD instructions where the CPU supports them.
+    pub fn derive_many(
+        ovk: &OutgoingViewingKey,
+        outputs: &[(balance::Commitment, StateCommitment, ka::Public)],
+    ) -> Vec<Self> {
+        let ovk_bytes = ovk.to_bytes();
+        let inputs: Vec<[u8; 128]> = outputs
+            .iter()
+            .map(|(cv, cm, epk)| {
+                let cv_bytes: [u8; 32] = (*cv).into();
+                let cm_bytes: [u8; 32] = (*cm).into();
+                let mut input = [0u8; 128];
+                input[..32].copy_from_slice(&ovk_bytes);
+                input[32..64].copy_from_slice(&cv_bytes);
+                input[64..96].copy_from_slice(&cm_bytes);
+                input[96..].copy_from_slice(&epk.0);
+                input
+            })
+            .collect();
+
+        hash_many(&ock_kdf_params(), inputs.iter().map(|input| &input[..]))
+            .into_iter()
+            .map(|key| Self(*Key::from_slice(key.as_bytes())))
+            .collect()
+    }
+
     /// Encrypt key material using the `OutgoingCipherKey`.
     pub fn encrypt(&self, plaintext: Vec<u8>, kind: PayloadKind) -> Vec<u8> {
         let cipher = ChaCha20Poly1305::new(&self.0);
@@ -344,3 +420,98 @@ impl TryFrom<&[u8]> for WrappedMemoKey {
         Ok(Self(bytes))
     }
 }
+
+/// A symmetric key granting read access to memos encrypted to an auditor.
+///
+/// The key is derived one-way from an [`IncomingViewingKey`], so holding it does not allow
+/// detecting or decrypting notes.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct MemoKey(pub [u8; 32]);
+
+impl MemoKey {
+    /// Encrypt `plaintext`, returning the nonce alongside the ciphertext.
+    ///
+    /// The same key is used for every memo, so the nonce is synthesized from a keyed hash of the
+    /// plaintext rather than fixed per [`PayloadKind`]. Identical plaintexts therefore produce
+    /// identical ciphertexts, but distinct plaintexts never share a nonce.
+    pub fn encrypt(&self, plaintext: &[u8]) -> ([u8; 12], Vec<u8>) {
+        let nonce_bytes: [u8; 12] = blake2b_simd::Params::new()
+            .personal(b"Penumbra_MemoNon")
+            .key(&self.0)
+            .hash_length(12)
+            .hash(plaintext)
+            .as_bytes()
+            .try_into()
+            .expect("hash length is 12 bytes");
+
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&self.0));
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce_bytes), plaintext)
+            .expect("encryption succeeded");
+
+        (nonce_bytes, ciphertext)
+    }
+
+    /// Decrypt a ciphertext produced by [`MemoKey::encrypt`].
+    pub fn decrypt(&self, nonce: &[u8; 12], ciphertext: &[u8]) -> Result<Vec<u8>> {
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&self.0));
+        cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| anyhow!("decryption error"))
+    }
+}
+
+impl std::fmt::Debug for MemoKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("MemoKey").field(&"<redacted>").finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use decaf377::{Element, Fq, Fr};
+    use rand_core::OsRng;
+
+    use super::*;
+
+    fn ovk() -> OutgoingViewingKey {
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+        OutgoingViewingKey(bytes)
+    }
+
+    #[test]
+    fn batched_swap_keys_match_scalar_derivation() {
+        let ovk = ovk();
+        // An odd count, so that the batch isn't a multiple of the SIMD width.
+        let cms: Vec<StateCommitment> = (0..67u64).map(|i| StateCommitment(Fq::from(i))).collect();
+
+        let batched = PayloadKey::derive_swap_many(&ovk, &cms);
+        let scalar: Vec<PayloadKey> = cms
+            .iter()
+            .map(|cm| PayloadKey::derive_swap(&ovk, *cm))
+            .collect();
+        assert_eq!(batched, scalar);
+        assert!(PayloadKey::derive_swap_many(&ovk, &[]).is_empty());
+    }
+
+    #[test]
+    fn batched_ocks_match_scalar_derivation() {
+        let ovk = ovk();
+        let outputs: Vec<(balance::Commitment, StateCommitment, ka::Public)> = (0..67u64)
+            .map(|i| {
+                (
+                    balance::Commitment(Fr::from(i + 1) * Element::GENERATOR),
+                    StateCommitment(Fq::from(i)),
+                    ka::Secret::new(&mut OsRng).public(),
+                )
+            })
+            .collect();
+
+        let batched = OutgoingCipherKey::derive_many(&ovk, &outputs);
+        assert_eq!(batched.len(), outputs.len());
+        for (key, (cv, cm, epk)) in batched.iter().zip(&outputs) {
+            assert_eq!(key.0, OutgoingCipherKey::derive(&ovk, *cv, *cm, epk).0);
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! Canonical byte encodings of the decaf377 base field [`Fq`] and scalar field [`Fr`].
+//!
+//! Both fields are encoded as 32 little-endian bytes. Decoding rejects any encoding of a value
+//! at or above the field modulus, so that every field element has exactly one encoding.
+
+use anyhow::{anyhow, Result};
+use decaf377::{Fq, Fr};
+
+/// Encode an element of the base field.
+pub fn fq_to_bytes(fq: Fq) -> [u8; 32] {
+    fq.to_bytes()
+}
+
+/// Decode an element of the base field, rejecting non-canonical encodings.
+pub fn bytes_to_fq(b: &[u8; 32]) -> Result<Fq> {
+    Fq::from_bytes_checked(b).map_err(|_| anyhow!("non-canonical encoding of Fq element"))
+}
+
+/// Encode an element of the scalar field.
+pub fn fr_to_bytes(fr: Fr) -> [u8; 32] {
+    fr.to_bytes()
+}
+
+/// Decode an element of the scalar field, rejecting non-canonical encodings.
+pub fn bytes_to_fr(b: &[u8; 32]) -> Result<Fr> {
+    Fr::from_bytes_checked(b).map_err(|_| anyhow!("non-canonical encoding of Fr element"))
+}
+
+/// Check whether `b` encodes a value strictly less than the base field modulus.
+///
+/// Since the scalar field is smaller than the base field, a canonical [`Fr`] encoding is always
+/// canonical here, but not the other way around; use [`bytes_to_fr`] to check those.
+pub fn check_canonical(b: &[u8; 32]) -> bool {
+    bytes_to_fq(b).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_ff::{BigInteger, PrimeField};
+
+    use super::*;
+
+    /// Encode the modulus of `F`, plus `plus`, as 32 little-endian bytes.
+    fn encode_modulus<F: PrimeField>(plus: u64) -> [u8; 32] {
+        let mut modulus = F::MODULUS;
+        modulus.add_with_carry(&F::BigInt::from(plus));
+        modulus.to_bytes_le().try_into().unwrap()
+    }
+
+    #[test]
+    fn field_elements_round_trip() {
+        let fq = Fq::from(123_456_789u64);
+        assert_eq!(bytes_to_fq(&fq_to_bytes(fq)).unwrap(), fq);
+        let fr = Fr::from(987_654_321u64);
+        assert_eq!(bytes_to_fr(&fr_to_bytes(fr)).unwrap(), fr);
+
+        let max_fq = -Fq::from(1u64);
+        assert!(check_canonical(&fq_to_bytes(max_fq)));
+        assert_eq!(bytes_to_fq(&fq_to_bytes(max_fq)).unwrap(), max_fq);
+    }
+
+    #[test]
+    fn non_canonical_fq_encodings_are_rejected() {
+        for bytes in [encode_modulus::<Fq>(0), encode_modulus::<Fq>(1), [0xff; 32]] {
+            assert!(!check_canonical(&bytes));
+            assert!(bytes_to_fq(&bytes).is_err());
+        }
+    }
+
+    #[test]
+    fn non_canonical_fr_encodings_are_rejected() {
+        for bytes in [encode_modulus::<Fr>(0), encode_modulus::<Fr>(1), [0xff; 32]] {
+            assert!(bytes_to_fr(&bytes).is_err());
+        }
+        // The Fr modulus is a canonical Fq encoding, though.
+        assert!(check_canonical(&encode_modulus::<Fr>(0)));
+    }
+}
@@ -0,0 +1,170 @@
+use penumbra_txhash::TransactionId;
+use sha2::{Digest, Sha256};
+
+/// A Merkle root over the IDs of every transaction in a block, in block order.
+///
+/// The tree follows RFC 6962, the same construction CometBFT uses for its block data hash: leaves
+/// and inner nodes are hashed with distinct prefixes, and each subtree is split at the largest
+/// power of two smaller than its size.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AggregateTransactionRoot(pub [u8; 32]);
+
+/// A proof that a [`TransactionId`] is included at a given position under an
+/// [`AggregateTransactionRoot`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerkleProof {
+    /// The position of the transaction within the block.
+    pub index: usize,
+    /// The number of transactions in the block.
+    pub total: usize,
+    /// The sibling hashes along the path from the leaf to the root, leaf first.
+    pub aunts: Vec<[u8; 32]>,
+}
+
+fn leaf_hash(tx_id: &TransactionId) -> [u8; 32] {
+    Sha256::new()
+        .chain_update([0u8])
+        .chain_update(tx_id.0)
+        .finalize()
+        .into()
+}
+
+fn inner_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    Sha256::new()
+        .chain_update([1u8])
+        .chain_update(left)
+        .chain_update(right)
+        .finalize()
+        .into()
+}
+
+/// The size of the left subtree of a tree with `total > 1` leaves.
+fn split_point(total: usize) -> usize {
+    let mut k = 1;
+    while k * 2 < total {
+        k *= 2;
+    }
+    k
+}
+
+fn subtree_root(tx_ids: &[TransactionId]) -> [u8; 32] {
+    match tx_ids {
+        [] => Sha256::digest(b"").into(),
+        [tx_id] => leaf_hash(tx_id),
+        _ => {
+            let (left, right) = tx_ids.split_at(split_point(tx_ids.len()));
+            inner_hash(&subtree_root(left), &subtree_root(right))
+        }
+    }
+}
+
+fn subtree_aunts(tx_ids: &[TransactionId], index: usize, aunts: &mut Vec<[u8; 32]>) {
+    if tx_ids.len() <= 1 {
+        return;
+    }
+    let (left, right) = tx_ids.split_at(split_point(tx_ids.len()));
+    if index < left.len() {
+        subtree_aunts(left, index, aunts);
+        aunts.push(subtree_root(right));
+    } else {
+        subtree_aunts(right, index - left.len(), aunts);
+        aunts.push(subtree_root(left));
+    }
+}
+
+fn root_from_aunts(
+    index: usize,
+    total: usize,
+    leaf: [u8; 32],
+    aunts: &[[u8; 32]],
+) -> Option<[u8; 32]> {
+    if total == 1 {
+        return aunts.is_empty().then_some(leaf);
+    }
+    let (last, rest) = aunts.split_last()?;
+    let k = split_point(total);
+    if index < k {
+        let left = root_from_aunts(index, k, leaf, rest)?;
+        Some(inner_hash(&left, last))
+    } else {
+        let right = root_from_aunts(index - k, total - k, leaf, rest)?;
+        Some(inner_hash(last, &right))
+    }
+}
+
+impl AggregateTransactionRoot {
+    /// Compute the root over the given transaction IDs, in the order they appear in the block.
+    pub fn compute(tx_ids: &[TransactionId]) -> Self {
+        Self(subtree_root(tx_ids))
+    }
+
+    /// Produce an inclusion proof for the transaction at `index`, or `None` if out of range.
+    pub fn prove(tx_ids: &[TransactionId], index: usize) -> Option<MerkleProof> {
+        if index >= tx_ids.len() {
+            return None;
+        }
+        let mut aunts = Vec::new();
+        subtree_aunts(tx_ids, index, &mut aunts);
+        Some(MerkleProof {
+            index,
+            total: tx_ids.len(),
+            aunts,
+        })
+    }
+
+    /// Check that `tx_id` is included under this root at the position given by `proof`.
+    pub fn verify_inclusion(&self, tx_id: &TransactionId, proof: &MerkleProof) -> bool {
+        if proof.index >= proof.total {
+            return false;
+        }
+        root_from_aunts(proof.index, proof.total, leaf_hash(tx_id), &proof.aunts)
+            .is_some_and(|root| root == self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx_ids(count: u8) -> Vec<TransactionId> {
+        (0..count).map(|i| TransactionId([i; 32])).collect()
+    }
+
+    #[test]
+    fn root_changes_when_a_transaction_changes() {
+        let mut ids = tx_ids(7);
+        let root = AggregateTransactionRoot::compute(&ids);
+
+        ids[3].0[0] ^= 1;
+        assert_ne!(AggregateTransactionRoot::compute(&ids), root);
+    }
+
+    #[test]
+    fn inclusion_proofs_verify_for_first_middle_and_last() {
+        for count in [1, 2, 5, 8, 13] {
+            let ids = tx_ids(count);
+            let root = AggregateTransactionRoot::compute(&ids);
+            let count = usize::from(count);
+
+            for index in [0, count / 2, count - 1] {
+                let proof = AggregateTransactionRoot::prove(&ids, index).expect("index in range");
+                assert!(root.verify_inclusion(&ids[index], &proof));
+
+                // The proof does not verify for a different transaction.
+                let other = TransactionId([0xff; 32]);
+                assert!(!root.verify_inclusion(&other, &proof));
+            }
+        }
+    }
+
+    #[test]
+    fn inclusion_proof_rejects_wrong_position() {
+        let ids = tx_ids(6);
+        let root = AggregateTransactionRoot::compute(&ids);
+
+        let mut proof = AggregateTransactionRoot::prove(&ids, 1).expect("index in range");
+        proof.index = 2;
+        assert!(!root.verify_inclusion(&ids[1], &proof));
+        assert!(AggregateTransactionRoot::prove(&ids, 6).is_none());
+    }
+}
@@ -17,6 +17,7 @@
 // Requires nightly.
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 
+mod aggregate_root;
 mod auth_data;
 mod detection_data;
 mod error;
@@ -34,6 +35,7 @@ pub mod view;
 
 pub use action::Action;
 pub use action_list::ActionList;
+pub use aggregate_root::{AggregateTransactionRoot, MerkleProof};
 pub use auth_data::AuthorizationData;
 pub use detection_data::DetectionData;
 pub use error::Error;
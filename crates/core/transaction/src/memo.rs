@@ -10,7 +10,7 @@ use penumbra_asset::balance;
 use penumbra_keys::{
     address::ADDRESS_LEN_BYTES,
     keys::OutgoingViewingKey,
-    symmetric::{OvkWrappedKey, PayloadKey, PayloadKind, WrappedMemoKey},
+    symmetric::{MemoKey, OvkWrappedKey, PayloadKey, PayloadKind, WrappedMemoKey},
     Address,
 };
 use penumbra_proto::{core::transaction::v1 as pbt, DomainType};
@@ -22,6 +22,12 @@ pub const MEMO_CIPHERTEXT_LEN_BYTES: usize = 528;
 // This is the `MEMO_CIPHERTEXT_LEN_BYTES` - MAC size (16 bytes).
 pub const MEMO_LEN_BYTES: usize = 512;
 
+// The length of the little-endian length prefix of an auditor memo's plaintext.
+const AUDITOR_MEMO_LEN_PREFIX_BYTES: usize = 2;
+
+// The length of an auditor memo's plaintext once it's prefixed and padded.
+const AUDITOR_MEMO_PADDED_LEN_BYTES: usize = AUDITOR_MEMO_LEN_PREFIX_BYTES + MEMO_LEN_BYTES;
+
 // This is the largest text length we can support
 const MAX_TEXT_LEN: usize = MEMO_LEN_BYTES - ADDRESS_LEN_BYTES;
 
@@ -194,6 +200,56 @@ impl MemoCiphertext {
     }
 }
 
+/// A memo encrypted to an auditor's [`MemoKey`], separately from the transaction's memo.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EncryptedMemo {
+    pub nonce: [u8; 12],
+    pub ciphertext: Vec<u8>,
+}
+
+impl EncryptedMemo {
+    /// Encrypt memo bytes so that they can be read by the holder of `memo_key`.
+    ///
+    /// The plaintext is prefixed with its length, and zero-padded to [`MEMO_LEN_BYTES`] after the
+    /// prefix before encryption, so every auditor memo has the same ciphertext length.
+    pub fn encrypt_to_auditor(
+        plaintext: &[u8],
+        memo_key: &MemoKey,
+    ) -> anyhow::Result<EncryptedMemo> {
+        let memo_len = plaintext.len();
+        if memo_len > MEMO_LEN_BYTES {
+            anyhow::bail!(
+                "provided memo plaintext of length {memo_len} exceeds maximum memo length of {MEMO_LEN_BYTES}"
+            );
+        }
+        let mut m = [0u8; AUDITOR_MEMO_PADDED_LEN_BYTES];
+        m[..AUDITOR_MEMO_LEN_PREFIX_BYTES].copy_from_slice(&(memo_len as u16).to_le_bytes());
+        m[AUDITOR_MEMO_LEN_PREFIX_BYTES..][..memo_len].copy_from_slice(plaintext);
+
+        let (nonce, ciphertext) = memo_key.encrypt(&m);
+        Ok(EncryptedMemo { nonce, ciphertext })
+    }
+
+    /// Decrypt the memo bytes using an auditor's [`MemoKey`], with the padding stripped.
+    pub fn decrypt_with_memo_key(&self, key: &MemoKey) -> anyhow::Result<Vec<u8>> {
+        let plaintext = key.decrypt(&self.nonce, &self.ciphertext)?;
+        if plaintext.len() != AUDITOR_MEMO_PADDED_LEN_BYTES {
+            anyhow::bail!(
+                "auditor memo plaintext has length {}, expected {AUDITOR_MEMO_PADDED_LEN_BYTES}",
+                plaintext.len()
+            );
+        }
+        let (prefix, padded) = plaintext.split_at(AUDITOR_MEMO_LEN_PREFIX_BYTES);
+        let memo_len = usize::from(u16::from_le_bytes([prefix[0], prefix[1]]));
+        if memo_len > MEMO_LEN_BYTES {
+            anyhow::bail!(
+                "auditor memo has length {memo_len}, exceeding the maximum of {MEMO_LEN_BYTES}"
+            );
+        }
+        Ok(padded[..memo_len].to_vec())
+    }
+}
+
 impl TryFrom<&[u8]> for MemoCiphertext {
     type Error = anyhow::Error;
 
@@ -364,6 +420,81 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_memo_decrypts_with_auditor_memo_key() {
+        let rng = OsRng;
+        let ivk = SpendKey::from_seed_phrase_bip44(SeedPhrase::generate(rng), &Bip44Path::new(0))
+            .full_viewing_key()
+            .incoming()
+            .clone();
+        let other_ivk =
+            SpendKey::from_seed_phrase_bip44(SeedPhrase::generate(rng), &Bip44Path::new(0))
+                .full_viewing_key()
+                .incoming()
+                .clone();
+
+        let memo = MemoPlaintext::new(Address::dummy(&mut OsRng), "for the auditor".into())
+            .expect("memo is valid");
+        let encrypted = EncryptedMemo::encrypt_to_auditor(&memo.to_vec(), &ivk.memo_key())
+            .expect("memo fits in a padded memo");
+
+        let plaintext = encrypted
+            .decrypt_with_memo_key(&ivk.memo_key())
+            .expect("memo key decrypts memo");
+        assert_eq!(
+            MemoPlaintext::try_from(plaintext).expect("valid memo"),
+            memo
+        );
+
+        assert!(encrypted
+            .decrypt_with_memo_key(&other_ivk.memo_key())
+            .is_err());
+        assert!(encrypted
+            .decrypt_with_memo_key(&MemoKey([0u8; 32]))
+            .is_err());
+    }
+
+    #[test]
+    fn test_auditor_memos_have_fixed_ciphertext_length() {
+        let memo_key = MemoKey([7u8; 32]);
+
+        let empty = EncryptedMemo::encrypt_to_auditor(&[], &memo_key).expect("empty memo fits");
+        let full = EncryptedMemo::encrypt_to_auditor(&[0xab; MEMO_LEN_BYTES], &memo_key)
+            .expect("full-length memo fits");
+        assert_eq!(empty.ciphertext.len(), full.ciphertext.len());
+        assert_eq!(
+            empty.ciphertext.len(),
+            MEMO_CIPHERTEXT_LEN_BYTES + AUDITOR_MEMO_LEN_PREFIX_BYTES
+        );
+
+        assert_eq!(
+            empty.decrypt_with_memo_key(&memo_key).expect("decrypts"),
+            Vec::<u8>::new()
+        );
+        assert_eq!(
+            full.decrypt_with_memo_key(&memo_key).expect("decrypts"),
+            vec![0xab; MEMO_LEN_BYTES]
+        );
+        assert!(EncryptedMemo::encrypt_to_auditor(&[0xab; MEMO_LEN_BYTES + 1], &memo_key).is_err());
+    }
+
+    #[test]
+    fn test_auditor_memos_keep_trailing_zeros() {
+        let memo_key = MemoKey([7u8; 32]);
+
+        // A blank memo is just its return address, whose last byte may be zero.
+        for plaintext in [vec![0xab, 0, 0], vec![0; ADDRESS_LEN_BYTES], vec![0]] {
+            let encrypted =
+                EncryptedMemo::encrypt_to_auditor(&plaintext, &memo_key).expect("memo fits");
+            assert_eq!(
+                encrypted
+                    .decrypt_with_memo_key(&memo_key)
+                    .expect("decrypts"),
+                plaintext
+            );
+        }
+    }
+
     proptest! {
         // We generate random strings, up to 10k chars long.
         // Since UTF-8 represents each char using 1 to 4 bytes,
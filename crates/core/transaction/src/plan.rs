@@ -28,12 +28,14 @@ mod build;
 mod clue;
 mod detection_data;
 mod memo;
+mod proof_budget;
 mod spend;
 
 pub use action::ActionPlan;
 pub use clue::CluePlan;
 pub use detection_data::DetectionDataPlan;
 pub use memo::MemoPlan;
+pub use proof_budget::{ProofBudget, ProofBudgetError};
 
 use crate::TransactionParameters;
 
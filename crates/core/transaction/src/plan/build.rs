@@ -1,11 +1,10 @@
 use anyhow::Result;
 use ark_ff::Zero;
 use decaf377::Fr;
-use decaf377_rdsa as rdsa;
 use penumbra_keys::FullViewingKey;
 use penumbra_txhash::AuthorizingData;
 
-use super::TransactionPlan;
+use super::{ProofBudget, TransactionPlan};
 use crate::ActionPlan;
 use crate::{action::Action, AuthorizationData, Transaction, TransactionBody, WitnessData};
 
@@ -101,13 +100,7 @@ impl TransactionPlan {
         }
 
         // Compute the binding signature and assemble the transaction.
-        let binding_signing_key = rdsa::SigningKey::from(synthetic_blinding_factor);
-        let auth_hash = transaction.transaction_body.auth_hash();
-
-        let binding_sig = binding_signing_key.sign_deterministic(auth_hash.as_bytes());
-        tracing::debug!(bvk = ?rdsa::VerificationKey::from(&binding_signing_key), ?auth_hash);
-
-        transaction.binding_sig = binding_sig;
+        transaction.binding_sig = transaction.binding_signature(synthetic_blinding_factor);
 
         Ok(transaction)
     }
@@ -145,6 +138,19 @@ impl TransactionPlan {
         Ok(tx)
     }
 
+    /// Build the serial transaction this plan describes, refusing to start proving if its
+    /// proofs wouldn't fit within `proof_budget`.
+    pub fn build_with_proof_budget(
+        self,
+        proof_budget: &ProofBudget,
+        full_viewing_key: &FullViewingKey,
+        witness_data: &WitnessData,
+        auth_data: &AuthorizationData,
+    ) -> Result<Transaction> {
+        proof_budget.check_transaction(&self)?;
+        self.build(full_viewing_key, witness_data, auth_data)
+    }
+
     #[cfg(feature = "parallel")]
     /// Build the transaction this plan describes while proving concurrently.
     /// This can be used in environments that support tokio tasks.
@@ -190,6 +196,21 @@ impl TransactionPlan {
         Ok(tx)
     }
 
+    #[cfg(feature = "parallel")]
+    /// Build the transaction this plan describes while proving concurrently, refusing to start
+    /// proving if its proofs wouldn't fit within `proof_budget`.
+    pub async fn build_concurrent_with_proof_budget(
+        self,
+        proof_budget: &ProofBudget,
+        full_viewing_key: &FullViewingKey,
+        witness_data: &WitnessData,
+        auth_data: &AuthorizationData,
+    ) -> Result<Transaction> {
+        proof_budget.check_transaction(&self)?;
+        self.build_concurrent(full_viewing_key, witness_data, auth_data)
+            .await
+    }
+
     /// Returns a [`WitnessData`], which may be used to build this transaction.
     pub fn witness_data(&self, sct: &penumbra_tct::Tree) -> Result<WitnessData, anyhow::Error> {
         let anchor = sct.root();
@@ -0,0 +1,154 @@
+use once_cell::sync::Lazy;
+use penumbra_dex::{swap::proof::SwapCircuit, swap_claim::SwapClaimCircuit};
+use penumbra_governance::DelegatorVoteCircuit;
+use penumbra_proof_params::{constraint_count, GROTH16_PROOF_LENGTH_BYTES};
+use penumbra_shielded_pool::{ConvertCircuit, OutputCircuit, SpendCircuit};
+
+use super::{ActionPlan, TransactionPlan};
+
+/// The number of constraints in each kind of proof, counted from the circuits the first time
+/// they're needed.
+static SPEND_CONSTRAINTS: Lazy<usize> = Lazy::new(constraint_count::<SpendCircuit>);
+static OUTPUT_CONSTRAINTS: Lazy<usize> = Lazy::new(constraint_count::<OutputCircuit>);
+static SWAP_CONSTRAINTS: Lazy<usize> = Lazy::new(constraint_count::<SwapCircuit>);
+static SWAP_CLAIM_CONSTRAINTS: Lazy<usize> = Lazy::new(constraint_count::<SwapClaimCircuit>);
+static DELEGATOR_VOTE_CONSTRAINTS: Lazy<usize> =
+    Lazy::new(constraint_count::<DelegatorVoteCircuit>);
+/// Undelegate claims are proven with the convert circuit.
+static UNDELEGATE_CLAIM_CONSTRAINTS: Lazy<usize> = Lazy::new(constraint_count::<ConvertCircuit>);
+
+/// The number of constraints in the proof `action` needs, if any.
+fn proof_constraints(action: &ActionPlan) -> usize {
+    match action {
+        ActionPlan::Spend(_) => *SPEND_CONSTRAINTS,
+        ActionPlan::Output(_) => *OUTPUT_CONSTRAINTS,
+        ActionPlan::Swap(_) => *SWAP_CONSTRAINTS,
+        ActionPlan::SwapClaim(_) => *SWAP_CLAIM_CONSTRAINTS,
+        ActionPlan::DelegatorVote(_) => *DELEGATOR_VOTE_CONSTRAINTS,
+        ActionPlan::UndelegateClaim(_) => *UNDELEGATE_CLAIM_CONSTRAINTS,
+        _ => 0,
+    }
+}
+
+/// The proving work a transaction may require, measured in R1CS constraints.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProofBudget {
+    /// The most constraints a transaction's proofs may have in total.
+    pub max_total_constraints: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum ProofBudgetError {
+    #[error("transaction needs {needed} constraints, more than the limit of {limit}")]
+    Exceeded { needed: usize, limit: usize },
+}
+
+impl ProofBudget {
+    /// Allow as many constraints as the most expensive proof, times as many proofs as could fit in
+    /// a transaction of `max_tx_bytes` if it were nothing but proofs, where `max_tx_bytes` should
+    /// be the mempool's `max_tx_bytes`.
+    ///
+    /// Transactions over this budget can never be included in a block, so there's no point in
+    /// proving them. Each circuit is synthesized to count its constraints the first time this is
+    /// called.
+    pub fn for_max_tx_bytes(max_tx_bytes: usize) -> Self {
+        let most_per_proof = [
+            *SPEND_CONSTRAINTS,
+            *OUTPUT_CONSTRAINTS,
+            *SWAP_CONSTRAINTS,
+            *SWAP_CLAIM_CONSTRAINTS,
+            *DELEGATOR_VOTE_CONSTRAINTS,
+            *UNDELEGATE_CLAIM_CONSTRAINTS,
+        ]
+        .into_iter()
+        .max()
+        .unwrap_or_default();
+        let most_proofs = max_tx_bytes / GROTH16_PROOF_LENGTH_BYTES;
+        Self {
+            max_total_constraints: most_per_proof.saturating_mul(most_proofs),
+        }
+    }
+
+    /// Count the constraints in the proofs of `plan`, checking that they fit within the budget.
+    ///
+    /// A transaction exactly at the limit is allowed.
+    pub fn check_transaction(&self, plan: &TransactionPlan) -> Result<usize, ProofBudgetError> {
+        let needed = plan
+            .actions
+            .iter()
+            .map(proof_constraints)
+            .fold(0usize, usize::saturating_add);
+        if needed > self.max_total_constraints {
+            return Err(ProofBudgetError::Exceeded {
+                needed,
+                limit: self.max_total_constraints,
+            });
+        }
+        Ok(needed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use penumbra_asset::{Value, STAKING_TOKEN_ASSET_ID};
+    use penumbra_keys::Address;
+    use penumbra_shielded_pool::OutputPlan;
+    use rand_core::OsRng;
+
+    use super::*;
+
+    fn plan_with_outputs(count: usize) -> TransactionPlan {
+        TransactionPlan {
+            actions: (0..count)
+                .map(|_| {
+                    OutputPlan::new(
+                        &mut OsRng,
+                        Value {
+                            amount: 1u64.into(),
+                            asset_id: *STAKING_TOKEN_ASSET_ID,
+                        },
+                        Address::dummy(&mut OsRng),
+                    )
+                    .into()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn transactions_are_checked_against_the_limit() {
+        let output = *OUTPUT_CONSTRAINTS;
+        let budget = ProofBudget {
+            max_total_constraints: 3 * output,
+        };
+
+        assert_eq!(
+            budget.check_transaction(&plan_with_outputs(2)),
+            Ok(2 * output)
+        );
+        assert_eq!(
+            budget.check_transaction(&plan_with_outputs(3)),
+            Ok(3 * output)
+        );
+        assert_eq!(
+            budget.check_transaction(&plan_with_outputs(4)),
+            Err(ProofBudgetError::Exceeded {
+                needed: 4 * output,
+                limit: 3 * output,
+            })
+        );
+    }
+
+    #[test]
+    fn the_mempool_budget_allows_any_includable_transaction() {
+        let max_tx_bytes = 30_720;
+        let budget = ProofBudget::for_max_tx_bytes(max_tx_bytes);
+        assert!(budget.max_total_constraints < usize::MAX);
+        assert!(budget
+            .check_transaction(&plan_with_outputs(
+                max_tx_bytes / GROTH16_PROOF_LENGTH_BYTES
+            ))
+            .is_ok());
+    }
+}
@@ -6,7 +6,7 @@ use std::{
 use anyhow::{Context, Error};
 use ark_ff::Zero;
 use decaf377::Fr;
-use decaf377_rdsa::{Binding, Signature, VerificationKey, VerificationKeyBytes};
+use decaf377_rdsa::{Binding, Signature, SigningKey, VerificationKey, VerificationKeyBytes};
 use penumbra_community_pool::{CommunityPoolDeposit, CommunityPoolOutput, CommunityPoolSpend};
 use penumbra_dex::{
     lp::action::{PositionClose, PositionOpen},
@@ -566,6 +566,27 @@ impl Transaction {
         TransactionId(id_bytes)
     }
 
+    /// Sign the transaction body with the binding signing key.
+    ///
+    /// The signing key is the sum of the value blinding factors of every action, so a relayer
+    /// that adds, removes, or changes any part of the transaction (including its fee) will not
+    /// be able to produce a valid signature for the result.
+    pub fn binding_signature(&self, synthetic_blinding_factor: Fr) -> Signature<Binding> {
+        let binding_signing_key = SigningKey::<Binding>::from(synthetic_blinding_factor);
+        let auth_hash = self.transaction_body.auth_hash();
+        tracing::debug!(bvk = ?VerificationKey::from(&binding_signing_key), ?auth_hash);
+
+        binding_signing_key.sign_deterministic(auth_hash.as_bytes())
+    }
+
+    /// Check `sig` against the transaction body and the binding verification key derived from
+    /// the transaction's balance commitments.
+    pub fn verify_binding_signature(&self, sig: &Signature<Binding>) -> bool {
+        self.binding_verification_key()
+            .verify(self.transaction_body.auth_hash().as_bytes(), sig)
+            .is_ok()
+    }
+
     /// Compute the binding verification key from the transaction data.
     pub fn binding_verification_key(&self) -> VerificationKey<Binding> {
         let mut balance_commitments = decaf377::Element::default();
@@ -725,3 +746,117 @@ impl From<&Transaction> for Vec<u8> {
         protobuf_serialized.encode_to_vec()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use penumbra_asset::{Value, STAKING_TOKEN_ASSET_ID};
+    use penumbra_fee::Fee;
+    use penumbra_keys::{
+        keys::{Bip44Path, SeedPhrase, SpendKey},
+        Address,
+    };
+    use penumbra_proof_params::GROTH16_PROOF_LENGTH_BYTES;
+    use penumbra_proto::core::component::shielded_pool::v1::ZkOutputProof;
+    use penumbra_shielded_pool::{OutputPlan, OutputProof};
+    use rand_core::OsRng;
+
+    use super::*;
+
+    #[test]
+    fn transaction_id_is_deterministic() {
+        let tx = Transaction::default();
+        assert_eq!(tx.id(), tx.clone().id());
+        assert_eq!(tx.id(), Transaction::default().id());
+    }
+
+    #[test]
+    fn transaction_id_changes_with_one_byte() {
+        let tx = Transaction::default();
+
+        let mut sig_bytes = [0u8; 64];
+        sig_bytes[0] = 1;
+        let modified = Transaction {
+            binding_sig: sig_bytes.into(),
+            ..tx.clone()
+        };
+
+        assert_ne!(tx.id(), modified.id());
+    }
+
+    #[test]
+    fn transaction_id_string_roundtrip() {
+        let id = Transaction::default().id();
+        let encoded = id.to_string();
+        assert!(encoded.starts_with("txid1"));
+        assert_eq!(encoded.parse::<TransactionId>().unwrap(), id);
+
+        // The hex encoding used by CometBFT is still accepted.
+        let hex_encoded = hex::encode(id.to_bytes());
+        assert_eq!(hex_encoded.parse::<TransactionId>().unwrap(), id);
+        assert_eq!(TransactionId::from_bytes(id.to_bytes()), id);
+    }
+
+    /// An output of nothing, whose balance commitment is just its blinding factor.
+    fn empty_output(rng: &mut OsRng) -> (Action, Fr) {
+        let sk =
+            SpendKey::from_seed_phrase_bip44(SeedPhrase::generate(&mut *rng), &Bip44Path::new(0));
+        let plan = OutputPlan::new(
+            rng,
+            Value {
+                amount: 0u64.into(),
+                asset_id: *STAKING_TOKEN_ASSET_ID,
+            },
+            Address::dummy(rng),
+        );
+        let body = plan.output_body(
+            sk.full_viewing_key().outgoing(),
+            &PayloadKey::random_key(rng),
+        );
+        // The binding signature doesn't depend on the proof, so there's no need for a real one.
+        let proof = OutputProof::try_from(ZkOutputProof {
+            inner: vec![0u8; GROTH16_PROOF_LENGTH_BYTES],
+        })
+        .expect("proof has the right length");
+        (Action::Output(Output { body, proof }), plan.value_blinding)
+    }
+
+    fn signed_transaction(rng: &mut OsRng) -> (Transaction, Fr) {
+        let (output, blinding) = empty_output(rng);
+        let mut tx = Transaction {
+            transaction_body: TransactionBody {
+                actions: vec![output],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        tx.binding_sig = tx.binding_signature(blinding);
+        (tx, blinding)
+    }
+
+    #[test]
+    fn binding_signature_covers_the_whole_transaction() {
+        let (tx, _) = signed_transaction(&mut OsRng);
+        assert!(tx.verify_binding_signature(tx.binding_sig()));
+    }
+
+    #[test]
+    fn binding_signature_rejects_modified_fee() {
+        let (mut tx, _) = signed_transaction(&mut OsRng);
+        tx.transaction_body.transaction_parameters.fee =
+            Fee::from_staking_token_amount(1u64.into());
+        assert!(!tx.verify_binding_signature(tx.binding_sig()));
+    }
+
+    #[test]
+    fn binding_signature_rejects_added_output() {
+        let (mut tx, blinding) = signed_transaction(&mut OsRng);
+        let (output, _) = empty_output(&mut OsRng);
+        tx.transaction_body.actions.push(output);
+        assert!(!tx.verify_binding_signature(tx.binding_sig()));
+
+        // Re-signing with the original blinding factor doesn't help either, since it no longer
+        // matches the balance commitments.
+        let resigned = tx.binding_signature(blinding);
+        assert!(!tx.verify_binding_signature(&resigned));
+    }
+}
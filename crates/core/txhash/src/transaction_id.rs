@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use penumbra_proto::{penumbra::core::txhash::v1 as pb, DomainType};
+use penumbra_proto::{penumbra::core::txhash::v1 as pb, serializers::bech32str, DomainType};
 use serde::{Deserialize, Serialize};
 
 /// A transaction ID (hash), the Sha256 hash used by Tendermint to identify transactions.
@@ -8,6 +8,16 @@ use serde::{Deserialize, Serialize};
 #[serde(try_from = "pb::TransactionId", into = "pb::TransactionId")]
 pub struct TransactionId(pub [u8; 32]);
 
+impl TransactionId {
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+}
+
 impl AsRef<[u8]> for TransactionId {
     fn as_ref(&self) -> &[u8] {
         &self.0
@@ -22,15 +32,29 @@ impl std::fmt::Debug for TransactionId {
 
 impl std::fmt::Display for TransactionId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", hex::encode(self.0))
+        f.write_str(&bech32str::encode(
+            &self.0,
+            bech32str::transaction_id::BECH32_PREFIX,
+            bech32str::Bech32m,
+        ))
     }
 }
 
 impl FromStr for TransactionId {
     type Err = anyhow::Error;
 
+    /// Parses a transaction ID from its Bech32m encoding, or from the hex encoding used by
+    /// CometBFT to identify transactions.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let bytes = hex::decode(s)?;
+        let bytes = if s.starts_with(bech32str::transaction_id::BECH32_PREFIX) {
+            bech32str::decode(
+                s,
+                bech32str::transaction_id::BECH32_PREFIX,
+                bech32str::Bech32m,
+            )?
+        } else {
+            hex::decode(s)?
+        };
         if bytes.len() != 32 {
             anyhow::bail!("invalid transaction ID length");
         }
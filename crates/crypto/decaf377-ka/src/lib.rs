@@ -88,6 +88,54 @@ impl Secret {
     }
 }
 
+/// An ephemeral secret key that can be used for exactly one key agreement.
+///
+/// Payload encryption uses a fixed nonce, which is only safe if every
+/// symmetric key is used once. Wrapping the ephemeral secret in a
+/// [`SingleUseSecret`] makes that requirement explicit: the wrapper is not
+/// [`Clone`], and [`SingleUseSecret::use_once`] consumes it, so the compiler
+/// rejects any attempt to perform a second key agreement with the same value:
+///
+/// ```compile_fail
+/// # use decaf377_ka as ka;
+/// # let rng = &mut rand_core::OsRng;
+/// let esk = ka::SingleUseSecret::new(rng);
+/// let pk_d = ka::Secret::new(rng).public();
+/// let b_d = decaf377::Element::GENERATOR;
+/// let _first = esk.use_once(&b_d, &pk_d);
+/// let _second = esk.use_once(&b_d, &pk_d); // error: use of moved value
+/// ```
+#[must_use = "a SingleUseSecret must be consumed with `use_once`"]
+#[derive(PartialEq, Eq)]
+pub struct SingleUseSecret(Secret);
+
+impl SingleUseSecret {
+    /// Generate a new single-use secret key using `rng`.
+    pub fn new<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        Self(Secret::new(rng))
+    }
+
+    /// Consume this secret, deriving the diversified ephemeral public key for
+    /// `diversified_generator` and performing key agreement with `counterparty`.
+    ///
+    /// Fails if the counterparty's public key is invalid.
+    pub fn use_once(
+        self,
+        diversified_generator: &decaf377::Element,
+        counterparty: &Public,
+    ) -> Result<(Public, SharedSecret), Error> {
+        let public = self.0.diversified_public(diversified_generator);
+        let shared_secret = self.0.key_agreement_with(counterparty)?;
+        Ok((public, shared_secret))
+    }
+}
+
+impl From<Secret> for SingleUseSecret {
+    fn from(secret: Secret) -> Self {
+        Self(secret)
+    }
+}
+
 impl std::fmt::Debug for Public {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_fmt(format_args!(
@@ -107,6 +155,12 @@ impl std::fmt::Debug for Secret {
     }
 }
 
+impl std::fmt::Debug for SingleUseSecret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("decaf377_ka::SingleUseSecret(..)")
+    }
+}
+
 impl std::fmt::Debug for SharedSecret {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_fmt(format_args!(
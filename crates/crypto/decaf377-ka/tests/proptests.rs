@@ -59,4 +59,22 @@ proptest! {
         assert_eq!(alice_ss1, bob_ss1);
         assert_eq!(alice_ss2, bob_ss2);
     }
+
+    #[test]
+    fn single_use_key_agreement_works(
+        esk in fr_strategy(),
+        bob_sk in fr_strategy(),
+        div in fq_strategy(),
+    ) {
+        let bob_sk = ka::Secret::new_from_field(bob_sk);
+        let gen = decaf377::Element::encode_to_curve(&div);
+        let bob_pk = bob_sk.diversified_public(&gen);
+
+        let esk = ka::SingleUseSecret::from(ka::Secret::new_from_field(esk));
+        let (epk, alice_ss) = esk.use_once(&gen, &bob_pk).unwrap();
+
+        let bob_ss = bob_sk.key_agreement_with(&epk).unwrap();
+
+        assert_eq!(alice_ss, bob_ss);
+    }
 }
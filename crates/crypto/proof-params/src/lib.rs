@@ -16,8 +16,8 @@ pub const GROTH16_PROOF_LENGTH_BYTES: usize = 192;
 mod traits;
 
 pub use traits::{
-    generate_constraint_matrices, generate_prepared_test_parameters, generate_test_parameters,
-    DummyWitness, ProvingKeyExt, VerifyingKeyExt,
+    constraint_count, generate_constraint_matrices, generate_prepared_test_parameters,
+    generate_test_parameters, DummyWitness, ProvingKeyExt, VerifyingKeyExt,
 };
 
 /// A wrapper around a proving key that can be lazily loaded.
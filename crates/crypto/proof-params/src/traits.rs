@@ -42,6 +42,23 @@ pub fn generate_constraint_matrices<T: DummyWitness>(
         .expect("can convert R1CS constraints into matrices")
 }
 
+/// The number of R1CS constraints in a circuit.
+///
+/// This synthesizes the circuit in setup mode, so no witness values are computed, but it still
+/// takes some time for the larger circuits.
+pub fn constraint_count<T: DummyWitness>() -> usize {
+    let circuit = T::with_dummy_witness();
+
+    let cs = r1cs::ConstraintSystem::new_ref();
+    cs.set_optimization_goal(r1cs::OptimizationGoal::Constraints);
+    cs.set_mode(r1cs::SynthesisMode::Setup);
+    circuit
+        .generate_constraints(cs.clone())
+        .expect("can generate constraints from circuit");
+    cs.finalize();
+    cs.num_constraints()
+}
+
 /// Generate parameters for proving and verifying, for *tests*.
 ///
 /// These parameters should not be used for actual production code,
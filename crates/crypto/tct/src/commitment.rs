@@ -1,6 +1,8 @@
 use decaf377::Fq;
 use penumbra_proto::{penumbra::crypto::tct::v1 as pb, DomainType};
 
+use crate::{error::proof::VerifyError, Proof, Root};
+
 /// A commitment to a note or swap.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(into = "pb::StateCommitment", try_from = "pb::StateCommitment")]
@@ -23,6 +25,19 @@ impl StateCommitment {
         let bytes = hex::decode(str)?;
         Ok(StateCommitment::try_from(&bytes[..])?)
     }
+
+    /// Verify, outside of any circuit, that `proof` shows this commitment is included in the
+    /// [`Tree`](crate::Tree) with the given [`Root`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VerifyError`] if the proof is invalid for that [`Root`], including when it is a
+    /// proof of inclusion for some other commitment.
+    pub fn verify_in_tree(&self, proof: &Proof, root: Root) -> Result<(), VerifyError> {
+        let mut proof = proof.clone();
+        proof.0.leaf = *self;
+        proof.verify(root)
+    }
 }
 
 impl DomainType for StateCommitment {
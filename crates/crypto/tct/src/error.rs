@@ -1,5 +1,5 @@
-//! Errors that can occur when inserting into a [`Tree`], deserializing [`Proof`](super::Proof)s, or
-//! checking internal invariants.
+//! Errors that can occur when inserting into a [`Tree`], deserializing [`Proof`](super::Proof)s,
+//! generating witnesses, or checking internal invariants.
 
 use crate::builder;
 #[cfg(doc)]
@@ -110,6 +110,26 @@ impl From<InsertEpochError> for builder::epoch::Finalized {
     }
 }
 
+/// A [`Proof`](super::Proof) could not be generated for a [`Commitment`], because it is not
+/// witnessed in the [`Tree`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("commitment {0:?} is not witnessed")]
+pub struct NotWitnessedError(pub crate::StateCommitment);
+
+/// A [`Proof`](super::Proof) could not be generated from a [`PrunedTree`](crate::PrunedTree).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum PruningError {
+    /// The commitment was in an epoch whose commitments have been pruned.
+    #[error("commitment is in epoch {epoch}, which has been pruned")]
+    EpochPruned {
+        /// The index of the pruned epoch.
+        epoch: u16,
+    },
+    /// The commitment was never witnessed in the tree.
+    #[error("commitment is not witnessed")]
+    NotWitnessed,
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
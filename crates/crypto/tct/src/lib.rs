@@ -55,11 +55,15 @@ extern crate async_trait;
 mod commitment;
 mod index;
 mod proof;
+mod prune;
 mod random;
 mod tree;
 mod witness;
+mod witness_cache;
 
 pub mod error;
+pub mod lru;
+pub mod snapshot;
 pub mod storage;
 pub mod structure;
 pub mod validate;
@@ -70,8 +74,11 @@ pub use {
     internal::hash::Forgotten,
     internal::hash::DOMAIN_SEPARATOR,
     proof::Proof,
+    prune::{EpochFrontier, PrunedTree},
+    snapshot::Snapshot,
     tree::{Position, Root, Tree},
     witness::Witness,
+    witness_cache::WitnessCache,
 };
 
 #[cfg(any(doc, feature = "internal"))]
@@ -0,0 +1,134 @@
+//! A map which evicts its least recently used entry when full.
+//!
+//! This backs the [`WitnessCache`](crate::WitnessCache), and is exported so that caches in crates
+//! depending on this one can share it rather than each keeping their own.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::Hash,
+};
+
+/// A map holding at most `capacity` entries, which evicts the least recently used entry to make
+/// room for a new one.
+///
+/// Looking up and inserting entries both count as using them.
+#[derive(Debug, Clone)]
+pub struct LruCache<K, V> {
+    capacity: usize,
+    /// The next recency stamp to hand out; larger stamps were used more recently.
+    clock: u64,
+    // Entries are only ever looked up by key, never iterated, so their order doesn't matter.
+    #[allow(clippy::disallowed_types)]
+    entries: HashMap<K, (V, u64)>,
+    recency: BTreeMap<u64, K>,
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    /// Create an empty cache holding at most `capacity` entries.
+    ///
+    /// A cache with a capacity of zero never holds anything.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            clock: 0,
+            entries: Default::default(),
+            recency: BTreeMap::new(),
+        }
+    }
+
+    fn tick(&mut self) -> u64 {
+        let stamp = self.clock;
+        self.clock += 1;
+        stamp
+    }
+
+    /// The number of entries in the cache.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check whether the cache has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Check whether the cache has an entry for `key`, without counting as a use of it.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// Get the entry for `key`, marking it as the most recently used.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let stamp = self.tick();
+        let (value, last_used) = self.entries.get_mut(key)?;
+        self.recency.remove(last_used);
+        self.recency.insert(stamp, key.clone());
+        *last_used = stamp;
+        Some(value)
+    }
+
+    /// Insert an entry for `key`, replacing any existing one, and evicting the least recently used
+    /// entry if the cache is over capacity.
+    pub fn insert(&mut self, key: K, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+        let stamp = self.tick();
+        if let Some((_, last_used)) = self.entries.insert(key.clone(), (value, stamp)) {
+            self.recency.remove(&last_used);
+        }
+        self.recency.insert(stamp, key);
+
+        while self.entries.len() > self.capacity {
+            let Some((_, oldest)) = self.recency.pop_first() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+    }
+
+    /// Remove the entry for `key`, returning its value if there was one.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let (value, last_used) = self.entries.remove(key)?;
+        self.recency.remove(&last_used);
+        Some(value)
+    }
+
+    /// Remove every entry.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn least_recently_used_entry_is_evicted() {
+        let mut cache = LruCache::new(2);
+        cache.insert(1, "one");
+        cache.insert(2, "two");
+        // Using 1 makes 2 the least recently used entry, so it's the one evicted for 3.
+        assert_eq!(cache.get_mut(&1), Some(&mut "one"));
+        cache.insert(3, "three");
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.contains_key(&1));
+        assert!(!cache.contains_key(&2));
+        assert!(cache.contains_key(&3));
+
+        assert_eq!(cache.remove(&1), Some("one"));
+        cache.insert(4, "four");
+        assert!(cache.contains_key(&3));
+        assert!(cache.contains_key(&4));
+    }
+
+    #[test]
+    fn zero_capacity_holds_nothing() {
+        let mut cache = LruCache::new(0);
+        cache.insert((), ());
+        assert!(cache.is_empty());
+    }
+}
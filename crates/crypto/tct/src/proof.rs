@@ -86,6 +86,12 @@ impl Proof {
         Root(self.0.root())
     }
 
+    /// Get the height of the tree from which the proof was generated, which is the length of its
+    /// authentication path.
+    pub fn height(&self) -> u32 {
+        <<frontier::Top<frontier::Tier<frontier::Tier<frontier::Item>>> as Height>::Height as IsHeight>::HEIGHT.into()
+    }
+
     /// Get the authentication path for this proof, order from root to leaf.
     pub fn auth_path(&self) -> [&[Hash; 3]; 24] {
         use crate::internal::path::{Leaf, Node};
@@ -141,3 +147,32 @@ impl TryFrom<pb::StateCommitmentProof> for Proof {
 impl penumbra_proto::DomainType for Proof {
     type Proto = pb::StateCommitmentProof;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commitments_verify_in_tree_until_tampered_with() {
+        let mut tree = Tree::new();
+        let commitments: Vec<StateCommitment> =
+            (0..50u64).map(|i| StateCommitment(Fq::from(i))).collect();
+        for commitment in &commitments {
+            tree.insert(crate::Witness::Keep, *commitment).unwrap();
+        }
+        let root = tree.root();
+
+        let commitment = commitments[25];
+        let proof = tree.witness(commitment).unwrap();
+        assert_eq!(proof.height(), 24);
+        assert!(commitment.verify_in_tree(&proof, root).is_ok());
+
+        // The proof doesn't show the inclusion of any other commitment.
+        assert!(commitments[24].verify_in_tree(&proof, root).is_err());
+
+        let mut auth_path = proof.auth_path().map(|siblings| *siblings);
+        auth_path[23][0] = Hash::new(Fq::from(1_000u64));
+        let tampered = Proof::new(commitment, proof.position(), auth_path);
+        assert!(commitment.verify_in_tree(&tampered, root).is_err());
+    }
+}
@@ -0,0 +1,245 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::builder::epoch;
+use crate::error::PruningError;
+use crate::prelude::*;
+
+/// The height in the tree of the root of an epoch.
+const EPOCH_HEIGHT: u8 = 16;
+
+/// What remains of a completed epoch of a [`Tree`] once its commitments have been pruned: its
+/// root hash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EpochFrontier {
+    /// The index of the epoch within the tree.
+    pub epoch: u16,
+    /// The root of the epoch, which the tree continues to hold in place of its commitments.
+    pub root: epoch::Root,
+}
+
+impl Tree {
+    /// Forget every commitment in completed epochs, except for the most recent `keep_last_n`
+    /// completed epochs.
+    ///
+    /// Forgetting every commitment in an epoch collapses the epoch into its root hash, so this
+    /// bounds the size of a long-running tree. The root of the tree is unchanged. Returns the
+    /// roots of the epochs that had commitments pruned, in order of epoch.
+    pub fn prune_completed_epochs(&mut self, keep_last_n: usize) -> Vec<EpochFrontier> {
+        self.prune_epochs(keep_last_n).0
+    }
+
+    /// Prune as in [`Tree::prune_completed_epochs`], also returning the pruned commitments along
+    /// with their epochs.
+    fn prune_epochs(
+        &mut self,
+        keep_last_n: usize,
+    ) -> (Vec<EpochFrontier>, Vec<(StateCommitment, u16)>) {
+        // The current epoch is the one the next commitment would be inserted into; if the tree is
+        // full, every epoch is complete.
+        let current_epoch = self
+            .position()
+            .map_or(1 << 16, |position| usize::from(position.epoch()));
+        let cutoff = current_epoch.saturating_sub(keep_last_n);
+
+        let pruned: Vec<(StateCommitment, u16)> = self
+            .commitments_unordered()
+            .filter(|(_, position)| usize::from(position.epoch()) < cutoff)
+            .map(|(commitment, position)| (commitment, position.epoch()))
+            .collect();
+        let epochs: BTreeSet<u16> = pruned.iter().map(|&(_, epoch)| epoch).collect();
+
+        // The roots must be found before forgetting anything, since once an epoch is forgotten, it
+        // may be merged with its neighbors into a single hash.
+        let roots = self.epoch_roots(&epochs);
+        let frontiers = epochs
+            .into_iter()
+            .filter_map(|epoch| {
+                Some(EpochFrontier {
+                    epoch,
+                    root: *roots.get(&epoch)?,
+                })
+            })
+            .collect();
+
+        for &(commitment, _) in &pruned {
+            self.forget(commitment);
+        }
+
+        (frontiers, pruned)
+    }
+
+    /// The roots of each of the given `epochs` of the tree.
+    fn epoch_roots(&self, epochs: &BTreeSet<u16>) -> BTreeMap<u16, epoch::Root> {
+        let Some(&last) = epochs.last() else {
+            return BTreeMap::new();
+        };
+
+        let mut roots = BTreeMap::new();
+        let mut nodes = vec![self.structure()];
+        while let Some(node) = nodes.pop() {
+            if node.height() > EPOCH_HEIGHT {
+                // Only descend into nodes beginning at or before the last epoch we're looking for.
+                nodes.extend(
+                    node.children()
+                        .into_iter()
+                        .filter(|child| child.position().epoch() <= last),
+                );
+            } else if node.height() == EPOCH_HEIGHT {
+                let epoch = node.position().epoch();
+                if epochs.contains(&epoch) {
+                    roots.insert(epoch, epoch::Root(node.hash()));
+                }
+            }
+        }
+        roots
+    }
+}
+
+/// A [`Tree`] whose completed epochs are pruned, which remembers which epoch each pruned
+/// commitment was in, so that [`PrunedTree::try_witness`] can explain why a commitment can no
+/// longer be witnessed.
+///
+/// This is meant for diagnostics: the record of pruned commitments is never itself pruned, so it
+/// grows by an entry for every commitment ever pruned, and doesn't bound memory the way pruning
+/// the tree does. A long-running node that only needs to bound the size of its tree should call
+/// [`Tree::prune_completed_epochs`] directly.
+#[derive(Debug, Clone, Default)]
+pub struct PrunedTree {
+    tree: Tree,
+    /// The epoch of every commitment pruned so far, which is unbounded.
+    pruned: HashedMap<StateCommitment, u16>,
+    frontiers: Vec<EpochFrontier>,
+}
+
+impl PrunedTree {
+    /// Wrap a tree, none of whose epochs have been pruned yet.
+    pub fn new(tree: Tree) -> Self {
+        Self {
+            tree,
+            ..Default::default()
+        }
+    }
+
+    /// The underlying tree.
+    pub fn tree(&self) -> &Tree {
+        &self.tree
+    }
+
+    /// The underlying tree, for inserting new commitments.
+    pub fn tree_mut(&mut self) -> &mut Tree {
+        &mut self.tree
+    }
+
+    /// The roots of every epoch pruned so far, in the order they were pruned.
+    pub fn frontiers(&self) -> &[EpochFrontier] {
+        &self.frontiers
+    }
+
+    /// Prune completed epochs of the tree, as in [`Tree::prune_completed_epochs`].
+    pub fn prune_completed_epochs(&mut self, keep_last_n: usize) -> Vec<EpochFrontier> {
+        let (frontiers, pruned) = self.tree.prune_epochs(keep_last_n);
+        self.pruned.extend(pruned);
+        self.frontiers.extend(frontiers.iter().copied());
+        frontiers
+    }
+
+    /// Get a [`Proof`] of inclusion for `commitment`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PruningError::EpochPruned`] if the commitment was in an epoch which has been
+    /// pruned, or [`PruningError::NotWitnessed`] if it was never witnessed in the tree at all.
+    pub fn try_witness(&self, commitment: StateCommitment) -> Result<Proof, PruningError> {
+        if let Some(proof) = self.tree.witness(commitment) {
+            return Ok(proof);
+        }
+        match self.pruned.get(&commitment) {
+            Some(&epoch) => Err(PruningError::EpochPruned { epoch }),
+            None => Err(PruningError::NotWitnessed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use decaf377::Fq;
+
+    use super::*;
+
+    /// A tree with three completed epochs of ten commitments each, and the root of each epoch.
+    fn tree() -> (Tree, Vec<Vec<StateCommitment>>, Vec<epoch::Root>) {
+        let mut tree = Tree::new();
+        let mut commitments = Vec::new();
+        let mut roots = Vec::new();
+        for epoch in 0..3u64 {
+            let epoch_commitments: Vec<StateCommitment> = (0..10u64)
+                .map(|i| StateCommitment(Fq::from(epoch * 100 + i)))
+                .collect();
+            for commitment in &epoch_commitments {
+                tree.insert(crate::Witness::Keep, *commitment).unwrap();
+            }
+            commitments.push(epoch_commitments);
+            roots.push(tree.end_epoch().unwrap());
+        }
+        (tree, commitments, roots)
+    }
+
+    #[test]
+    fn pruning_the_first_epoch_keeps_later_witnesses() {
+        let (tree, commitments, roots) = tree();
+        let root = tree.root();
+        let mut tree = PrunedTree::new(tree);
+
+        // The current epoch is 3, so keeping the two most recent completed epochs prunes epoch 0.
+        let frontiers = tree.prune_completed_epochs(2);
+        assert_eq!(
+            frontiers,
+            vec![EpochFrontier {
+                epoch: 0,
+                root: roots[0],
+            }]
+        );
+        assert_eq!(tree.frontiers(), &frontiers[..]);
+        assert_eq!(tree.tree().root(), root);
+        assert_eq!(tree.tree().witnessed_count(), 20);
+
+        for commitment in &commitments[0] {
+            assert_eq!(
+                tree.try_witness(*commitment).unwrap_err(),
+                PruningError::EpochPruned { epoch: 0 }
+            );
+        }
+        for commitment in commitments[1].iter().chain(&commitments[2]) {
+            let proof = tree.try_witness(*commitment).unwrap();
+            commitment.verify_in_tree(&proof, root).unwrap();
+        }
+        assert_eq!(
+            tree.try_witness(StateCommitment(Fq::from(12_345u64)))
+                .unwrap_err(),
+            PruningError::NotWitnessed
+        );
+
+        // Pruning again with the same limit has nothing left to do.
+        assert!(tree.prune_completed_epochs(2).is_empty());
+    }
+
+    #[test]
+    fn pruning_keeps_the_current_epoch() {
+        let (mut tree, _, roots) = tree();
+        let current = StateCommitment(Fq::from(1_000u64));
+        tree.insert(crate::Witness::Keep, current).unwrap();
+
+        let frontiers = tree.prune_completed_epochs(0);
+        assert_eq!(
+            frontiers,
+            (0..3)
+                .map(|epoch| EpochFrontier {
+                    epoch,
+                    root: roots[epoch as usize],
+                })
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(tree.witnessed_count(), 1);
+        assert!(tree.witness(current).is_some());
+    }
+}
@@ -0,0 +1,129 @@
+//! Point-in-time snapshots of a [`Tree`], sufficient to resume insertion without replaying every
+//! prior commitment.
+
+use crate::{Root, Tree};
+
+/// The frontier of a [`Tree`]: the minimal state required to continue inserting commitments.
+///
+/// A frontier retains the position, the forgotten count, and the hashes along the rightmost edge
+/// of the tree, but no witnessed commitments, so it cannot produce any proofs for commitments
+/// inserted before it was taken.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Frontier(Tree);
+
+impl Frontier {
+    /// Get the root hash of the tree this frontier was taken from.
+    pub fn root(&self) -> Root {
+        self.0.root()
+    }
+}
+
+impl From<&Tree> for Frontier {
+    fn from(tree: &Tree) -> Self {
+        let mut frontier = tree.clone();
+        let witnessed: Vec<_> = tree.commitments_unordered().map(|(c, _)| c).collect();
+        for commitment in witnessed {
+            frontier.forget(commitment);
+        }
+        Frontier(frontier)
+    }
+}
+
+impl From<Frontier> for Tree {
+    fn from(frontier: Frontier) -> Self {
+        frontier.0
+    }
+}
+
+/// A snapshot of a [`Tree`] as of the end of some block height.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// The block height at which the snapshot was taken.
+    pub height: u64,
+    /// The root of the tree at that height.
+    pub root: Root,
+    /// The frontier of the tree at that height.
+    pub frontier: Frontier,
+}
+
+impl Tree {
+    /// Take a [`Snapshot`] of this tree, labeled with the given block `height`.
+    ///
+    /// The snapshot does not include any witnessed commitments, so it is much smaller than the tree
+    /// itself, but a tree restored from it can only witness commitments inserted afterwards.
+    pub fn snapshot(&self, height: u64) -> Snapshot {
+        Snapshot {
+            height,
+            root: self.root(),
+            frontier: Frontier::from(self),
+        }
+    }
+
+    /// Restore a tree from a [`Snapshot`], such that subsequent insertions produce the same roots
+    /// as they would have in the tree the snapshot was taken from.
+    pub fn restore_from_snapshot(snapshot: Snapshot) -> Tree {
+        let tree = Tree::from(snapshot.frontier);
+        debug_assert_eq!(
+            tree.root(),
+            snapshot.root,
+            "snapshot root must match its frontier"
+        );
+        tree
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::{StateCommitment, Witness};
+
+    fn insert_block(tree: &mut Tree, rng: &mut StdRng, count: usize) {
+        for i in 0..count {
+            let witness = if i % 2 == 0 {
+                Witness::Keep
+            } else {
+                Witness::Forget
+            };
+            tree.insert(witness, StateCommitment::random(&mut *rng))
+                .expect("tree is not full");
+        }
+        tree.end_block().expect("tree is not full");
+    }
+
+    #[test]
+    fn restored_tree_matches_original_roots() {
+        let mut rng = StdRng::seed_from_u64(1312);
+        let mut original = Tree::new();
+
+        for block in 0..10 {
+            insert_block(&mut original, &mut rng, block % 4);
+            if block % 3 == 2 {
+                original.end_epoch().expect("tree is not full");
+            }
+        }
+
+        let snapshot = original.snapshot(10);
+        assert_eq!(snapshot.root, original.root());
+
+        let bytes = serde_json::to_vec(&snapshot).expect("can serialize snapshot");
+        let snapshot: Snapshot = serde_json::from_slice(&bytes).expect("can deserialize snapshot");
+
+        let mut restored = Tree::restore_from_snapshot(snapshot);
+        assert_eq!(restored.root(), original.root());
+        assert_eq!(restored.position(), original.position());
+        assert_eq!(restored.witnessed_count(), 0);
+
+        for block in 0..10 {
+            let mut replay = rng.clone();
+            insert_block(&mut original, &mut rng, block % 5);
+            insert_block(&mut restored, &mut replay, block % 5);
+            if block % 4 == 3 {
+                original.end_epoch().expect("tree is not full");
+                restored.end_epoch().expect("tree is not full");
+            }
+            assert_eq!(restored.root(), original.root());
+        }
+    }
+}
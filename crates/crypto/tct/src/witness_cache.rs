@@ -0,0 +1,163 @@
+use crate::{error::NotWitnessedError, lru::LruCache, Proof, Root, StateCommitment, Tree};
+
+/// A [`Tree`] together with a cache of the inclusion [`Proof`]s most recently generated from it.
+///
+/// Generating a proof walks the tree from the commitment up to the root, so wallets which
+/// repeatedly build transactions spending the same notes can avoid that work by asking this cache
+/// instead. Every cached proof is relative to the root of the tree at the time it was generated,
+/// so the whole cache is invalidated whenever the root changes.
+#[derive(Debug, Clone)]
+pub struct WitnessCache {
+    tct: Tree,
+    lru: LruCache<StateCommitment, Proof>,
+    anchor: Root,
+}
+
+impl WitnessCache {
+    /// Create a new cache holding at most `capacity` proofs, over an empty [`Tree`].
+    pub fn new(capacity: usize) -> Self {
+        Self::from_tree(Tree::new(), capacity)
+    }
+
+    /// Create a new cache holding at most `capacity` proofs, over an existing [`Tree`].
+    pub fn from_tree(tct: Tree, capacity: usize) -> Self {
+        let anchor = tct.root();
+        Self {
+            tct,
+            lru: LruCache::new(capacity),
+            anchor,
+        }
+    }
+
+    /// The tree from which proofs are generated.
+    pub fn tree(&self) -> &Tree {
+        &self.tct
+    }
+
+    /// Mutable access to the tree from which proofs are generated.
+    ///
+    /// Any cached proofs are discarded the next time a proof is requested, if the tree's root was
+    /// changed in the meantime.
+    pub fn tree_mut(&mut self) -> &mut Tree {
+        &mut self.tct
+    }
+
+    /// The root against which all the currently cached proofs verify.
+    pub fn anchor(&self) -> Root {
+        self.anchor
+    }
+
+    /// The number of proofs currently cached.
+    pub fn len(&self) -> usize {
+        self.lru.len()
+    }
+
+    /// Check whether there are no proofs currently cached.
+    pub fn is_empty(&self) -> bool {
+        self.lru.is_empty()
+    }
+
+    /// Get a proof of inclusion for `commitment` in the current tree, from the cache if possible.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NotWitnessedError`] if the commitment is not witnessed in the tree.
+    pub fn get_or_generate(
+        &mut self,
+        commitment: StateCommitment,
+    ) -> Result<Proof, NotWitnessedError> {
+        let root = self.tct.root();
+        if root != self.anchor {
+            self.invalidate_all();
+            self.anchor = root;
+        }
+
+        if let Some(proof) = self.lru.get_mut(&commitment) {
+            return Ok(proof.clone());
+        }
+
+        let proof = self
+            .tct
+            .witness(commitment)
+            .ok_or(NotWitnessedError(commitment))?;
+        self.lru.insert(commitment, proof.clone());
+        Ok(proof)
+    }
+
+    /// Discard every cached proof.
+    ///
+    /// This should be called when the root of the tree advances, e.g. at the end of each block;
+    /// [`WitnessCache::get_or_generate`] also does so itself if it notices the root has changed.
+    pub fn invalidate_all(&mut self) {
+        self.lru.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Witness;
+
+    fn commitment(i: u64) -> StateCommitment {
+        StateCommitment(decaf377::Fq::from(i + 1))
+    }
+
+    #[test]
+    fn cached_witnesses_remain_valid_after_appends() {
+        let mut cache = WitnessCache::new(16);
+        let tracked: Vec<StateCommitment> = (0..10).map(commitment).collect();
+        for &c in &tracked {
+            cache.tree_mut().insert(Witness::Keep, c).unwrap();
+        }
+
+        for &c in &tracked {
+            let proof = cache.get_or_generate(c).unwrap();
+            proof.verify(cache.tree().root()).unwrap();
+        }
+        assert_eq!(cache.len(), tracked.len());
+
+        for i in 100..200 {
+            cache
+                .tree_mut()
+                .insert(Witness::Forget, commitment(i))
+                .unwrap();
+        }
+
+        let root = cache.tree().root();
+        for &c in &tracked {
+            let proof = cache.get_or_generate(c).unwrap();
+            assert_eq!(proof.root(), root);
+            proof.verify(root).unwrap();
+        }
+        assert_eq!(cache.anchor(), root);
+    }
+
+    #[test]
+    fn least_recently_used_witness_is_evicted() {
+        let mut cache = WitnessCache::new(2);
+        for i in 0..3 {
+            cache
+                .tree_mut()
+                .insert(Witness::Keep, commitment(i))
+                .unwrap();
+        }
+
+        cache.get_or_generate(commitment(0)).unwrap();
+        cache.get_or_generate(commitment(1)).unwrap();
+        // Touch the first commitment so that the second is the least recently used.
+        cache.get_or_generate(commitment(0)).unwrap();
+        cache.get_or_generate(commitment(2)).unwrap();
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.lru.contains_key(&commitment(0)));
+        assert!(!cache.lru.contains_key(&commitment(1)));
+        assert!(cache.lru.contains_key(&commitment(2)));
+
+        cache.invalidate_all();
+        assert!(cache.is_empty());
+        assert_eq!(
+            cache.get_or_generate(commitment(99)),
+            Err(NotWitnessedError(commitment(99)))
+        );
+    }
+}
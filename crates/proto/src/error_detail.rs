@@ -0,0 +1,100 @@
+use prost::{Message, Name};
+
+use crate::google::rpc::Status;
+use crate::penumbra::util::error::v1::PenumbraErrorDetail;
+
+impl PenumbraErrorDetail {
+    /// A [`tonic::Status`] with this detail embedded in its `google.rpc.Status` details.
+    pub fn into_status(self, code: tonic::Code, message: impl Into<String>) -> tonic::Status {
+        let message = message.into();
+        let details = Status {
+            code: code as i32,
+            message: message.clone(),
+            details: vec![pbjson_types::Any {
+                type_url: Self::type_url(),
+                value: self.encode_to_vec().into(),
+            }],
+        };
+        tonic::Status::with_details(code, message, details.encode_to_vec().into())
+    }
+
+    /// The detail embedded in `status`, if it has one.
+    pub fn extract(status: &tonic::Status) -> Option<Self> {
+        let details = Status::decode(status.details()).ok()?;
+        let type_url = Self::type_url();
+        details
+            .details
+            .iter()
+            .filter(|any| any.type_url == type_url)
+            .find_map(|any| Self::decode(any.value.as_ref()).ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{asset::v1::AssetId, num::v1::Amount};
+    use crate::penumbra::util::error::v1::{penumbra_error_detail as detail, PenumbraErrorCode};
+
+    /// Round trip `detail` through a status as it would be sent over the wire.
+    fn round_trip(detail: PenumbraErrorDetail) {
+        let status = detail
+            .clone()
+            .into_status(tonic::Code::FailedPrecondition, "request failed");
+        let headers = status.to_header_map().unwrap();
+        let received = tonic::Status::from_header_map(&headers).unwrap();
+        assert_eq!(received.code(), tonic::Code::FailedPrecondition);
+        assert_eq!(received.message(), "request failed");
+        assert_eq!(PenumbraErrorDetail::extract(&received), Some(detail));
+    }
+
+    fn asset_id() -> Option<AssetId> {
+        Some(AssetId {
+            inner: vec![7; 32],
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn insufficient_funds_round_trips() {
+        round_trip(PenumbraErrorDetail {
+            code: PenumbraErrorCode::InsufficientFunds as i32,
+            metadata: Some(detail::Metadata::InsufficientFunds(
+                detail::InsufficientFunds {
+                    asset_id: asset_id(),
+                    available: Some(Amount { lo: 5, hi: 0 }),
+                    required: Some(Amount { lo: 10, hi: 0 }),
+                },
+            )),
+        });
+    }
+
+    #[test]
+    fn unknown_asset_round_trips() {
+        round_trip(PenumbraErrorDetail {
+            code: PenumbraErrorCode::UnknownAsset as i32,
+            metadata: Some(detail::Metadata::UnknownAsset(detail::UnknownAsset {
+                asset_id: asset_id(),
+            })),
+        });
+    }
+
+    #[test]
+    fn transaction_expired_round_trips() {
+        round_trip(PenumbraErrorDetail {
+            code: PenumbraErrorCode::TransactionExpired as i32,
+            metadata: Some(detail::Metadata::TransactionExpired(
+                detail::TransactionExpired {
+                    expiry_height: 100,
+                    current_height: 101,
+                },
+            )),
+        });
+    }
+
+    #[test]
+    fn statuses_without_details_have_none() {
+        let status = tonic::Status::not_found("no such note");
+        assert_eq!(PenumbraErrorDetail::extract(&status), None);
+    }
+}
@@ -1513,6 +1513,43 @@ impl ::prost::Name for EventArbExecution {
         ::prost::alloc::format!("penumbra.core.component.dex.v1.{}", Self::NAME)
     }
 }
+/// Summarizes the settlement of the batch swap in a trading pair.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EventSwapSettlement {
+    /// The trading pair the batch swap settled in.
+    #[prost(message, optional, tag = "1")]
+    pub trading_pair: ::core::option::Option<TradingPair>,
+    /// The total amount of asset 1 swapped into the batch.
+    #[prost(message, optional, tag = "2")]
+    pub aggregate_input_1: ::core::option::Option<super::super::super::num::v1::Amount>,
+    /// The total amount of asset 2 swapped into the batch.
+    #[prost(message, optional, tag = "3")]
+    pub aggregate_input_2: ::core::option::Option<super::super::super::num::v1::Amount>,
+    /// The total amount of asset 1 paid out of the batch, including unfilled inputs.
+    #[prost(message, optional, tag = "4")]
+    pub aggregate_output_1: ::core::option::Option<super::super::super::num::v1::Amount>,
+    /// The total amount of asset 2 paid out of the batch, including unfilled inputs.
+    #[prost(message, optional, tag = "5")]
+    pub aggregate_output_2: ::core::option::Option<super::super::super::num::v1::Amount>,
+    /// The price of asset 1 in terms of asset 2, as the bytes of a U128x128 fixed-point number.
+    /// Empty when there is no price, because nothing was filled.
+    #[prost(bytes = "vec", tag = "6")]
+    pub clearing_price: ::prost::alloc::vec::Vec<u8>,
+    /// The number of swaps included in the batch.
+    #[prost(uint32, tag = "7")]
+    pub participant_count: u32,
+    /// The height at which the batch settled.
+    #[prost(uint64, tag = "8")]
+    pub block_height: u64,
+}
+impl ::prost::Name for EventSwapSettlement {
+    const NAME: &'static str = "EventSwapSettlement";
+    const PACKAGE: &'static str = "penumbra.core.component.dex.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("penumbra.core.component.dex.v1.{}", Self::NAME)
+    }
+}
 /// Indicates that value was added to the DEX.
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -1599,6 +1636,10 @@ pub struct DexParameters {
     /// for a single pair
     #[prost(uint32, tag = "5")]
     pub max_execution_budget: u32,
+    /// The fees, in basis points, that liquidity positions may charge.
+    /// If empty, positions may charge any fee.
+    #[prost(uint32, repeated, tag = "6")]
+    pub fee_tiers: ::prost::alloc::vec::Vec<u32>,
 }
 impl ::prost::Name for DexParameters {
     const NAME: &'static str = "DexParameters";
@@ -1714,6 +1714,9 @@ impl serde::Serialize for DexParameters {
         if self.max_execution_budget != 0 {
             len += 1;
         }
+        if !self.fee_tiers.is_empty() {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("penumbra.core.component.dex.v1.DexParameters", len)?;
         if self.is_enabled {
             struct_ser.serialize_field("isEnabled", &self.is_enabled)?;
@@ -1730,6 +1733,9 @@ impl serde::Serialize for DexParameters {
         if self.max_execution_budget != 0 {
             struct_ser.serialize_field("maxExecutionBudget", &self.max_execution_budget)?;
         }
+        if !self.fee_tiers.is_empty() {
+            struct_ser.serialize_field("feeTiers", &self.fee_tiers)?;
+        }
         struct_ser.end()
     }
 }
@@ -1750,6 +1756,8 @@ impl<'de> serde::Deserialize<'de> for DexParameters {
             "maxPositionsPerPair",
             "max_execution_budget",
             "maxExecutionBudget",
+            "fee_tiers",
+            "feeTiers",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -1759,6 +1767,7 @@ impl<'de> serde::Deserialize<'de> for DexParameters {
             MaxHops,
             MaxPositionsPerPair,
             MaxExecutionBudget,
+            FeeTiers,
             __SkipField__,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
@@ -1786,6 +1795,7 @@ impl<'de> serde::Deserialize<'de> for DexParameters {
                             "maxHops" | "max_hops" => Ok(GeneratedField::MaxHops),
                             "maxPositionsPerPair" | "max_positions_per_pair" => Ok(GeneratedField::MaxPositionsPerPair),
                             "maxExecutionBudget" | "max_execution_budget" => Ok(GeneratedField::MaxExecutionBudget),
+                            "feeTiers" | "fee_tiers" => Ok(GeneratedField::FeeTiers),
                             _ => Ok(GeneratedField::__SkipField__),
                         }
                     }
@@ -1810,6 +1820,7 @@ impl<'de> serde::Deserialize<'de> for DexParameters {
                 let mut max_hops__ = None;
                 let mut max_positions_per_pair__ = None;
                 let mut max_execution_budget__ = None;
+                let mut fee_tiers__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::IsEnabled => {
@@ -1848,6 +1859,15 @@ impl<'de> serde::Deserialize<'de> for DexParameters {
                                 Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
                             ;
                         }
+                        GeneratedField::FeeTiers => {
+                            if fee_tiers__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("feeTiers"));
+                            }
+                            fee_tiers__ = 
+                                Some(map_.next_value::<Vec<::pbjson::private::NumberDeserialize<_>>>()?
+                                    .into_iter().map(|x| x.0).collect())
+                            ;
+                        }
                         GeneratedField::__SkipField__ => {
                             let _ = map_.next_value::<serde::de::IgnoredAny>()?;
                         }
@@ -1859,6 +1879,7 @@ impl<'de> serde::Deserialize<'de> for DexParameters {
                     max_hops: max_hops__.unwrap_or_default(),
                     max_positions_per_pair: max_positions_per_pair__.unwrap_or_default(),
                     max_execution_budget: max_execution_budget__.unwrap_or_default(),
+                    fee_tiers: fee_tiers__.unwrap_or_default(),
                 })
             }
         }
@@ -3388,6 +3409,236 @@ impl<'de> serde::Deserialize<'de> for EventSwapClaim {
         deserializer.deserialize_struct("penumbra.core.component.dex.v1.EventSwapClaim", FIELDS, GeneratedVisitor)
     }
 }
+impl serde::Serialize for EventSwapSettlement {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.trading_pair.is_some() {
+            len += 1;
+        }
+        if self.aggregate_input_1.is_some() {
+            len += 1;
+        }
+        if self.aggregate_input_2.is_some() {
+            len += 1;
+        }
+        if self.aggregate_output_1.is_some() {
+            len += 1;
+        }
+        if self.aggregate_output_2.is_some() {
+            len += 1;
+        }
+        if !self.clearing_price.is_empty() {
+            len += 1;
+        }
+        if self.participant_count != 0 {
+            len += 1;
+        }
+        if self.block_height != 0 {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("penumbra.core.component.dex.v1.EventSwapSettlement", len)?;
+        if let Some(v) = self.trading_pair.as_ref() {
+            struct_ser.serialize_field("tradingPair", v)?;
+        }
+        if let Some(v) = self.aggregate_input_1.as_ref() {
+            struct_ser.serialize_field("aggregateInput1", v)?;
+        }
+        if let Some(v) = self.aggregate_input_2.as_ref() {
+            struct_ser.serialize_field("aggregateInput2", v)?;
+        }
+        if let Some(v) = self.aggregate_output_1.as_ref() {
+            struct_ser.serialize_field("aggregateOutput1", v)?;
+        }
+        if let Some(v) = self.aggregate_output_2.as_ref() {
+            struct_ser.serialize_field("aggregateOutput2", v)?;
+        }
+        if !self.clearing_price.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("clearingPrice", pbjson::private::base64::encode(&self.clearing_price).as_str())?;
+        }
+        if self.participant_count != 0 {
+            struct_ser.serialize_field("participantCount", &self.participant_count)?;
+        }
+        if self.block_height != 0 {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("blockHeight", ToString::to_string(&self.block_height).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for EventSwapSettlement {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "trading_pair",
+            "tradingPair",
+            "aggregate_input_1",
+            "aggregateInput1",
+            "aggregate_input_2",
+            "aggregateInput2",
+            "aggregate_output_1",
+            "aggregateOutput1",
+            "aggregate_output_2",
+            "aggregateOutput2",
+            "clearing_price",
+            "clearingPrice",
+            "participant_count",
+            "participantCount",
+            "block_height",
+            "blockHeight",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            TradingPair,
+            AggregateInput1,
+            AggregateInput2,
+            AggregateOutput1,
+            AggregateOutput2,
+            ClearingPrice,
+            ParticipantCount,
+            BlockHeight,
+            __SkipField__,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "tradingPair" | "trading_pair" => Ok(GeneratedField::TradingPair),
+                            "aggregateInput1" | "aggregate_input_1" => Ok(GeneratedField::AggregateInput1),
+                            "aggregateInput2" | "aggregate_input_2" => Ok(GeneratedField::AggregateInput2),
+                            "aggregateOutput1" | "aggregate_output_1" => Ok(GeneratedField::AggregateOutput1),
+                            "aggregateOutput2" | "aggregate_output_2" => Ok(GeneratedField::AggregateOutput2),
+                            "clearingPrice" | "clearing_price" => Ok(GeneratedField::ClearingPrice),
+                            "participantCount" | "participant_count" => Ok(GeneratedField::ParticipantCount),
+                            "blockHeight" | "block_height" => Ok(GeneratedField::BlockHeight),
+                            _ => Ok(GeneratedField::__SkipField__),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = EventSwapSettlement;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct penumbra.core.component.dex.v1.EventSwapSettlement")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<EventSwapSettlement, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut trading_pair__ = None;
+                let mut aggregate_input_1__ = None;
+                let mut aggregate_input_2__ = None;
+                let mut aggregate_output_1__ = None;
+                let mut aggregate_output_2__ = None;
+                let mut clearing_price__ = None;
+                let mut participant_count__ = None;
+                let mut block_height__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::TradingPair => {
+                            if trading_pair__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("tradingPair"));
+                            }
+                            trading_pair__ = map_.next_value()?;
+                        }
+                        GeneratedField::AggregateInput1 => {
+                            if aggregate_input_1__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("aggregateInput1"));
+                            }
+                            aggregate_input_1__ = map_.next_value()?;
+                        }
+                        GeneratedField::AggregateInput2 => {
+                            if aggregate_input_2__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("aggregateInput2"));
+                            }
+                            aggregate_input_2__ = map_.next_value()?;
+                        }
+                        GeneratedField::AggregateOutput1 => {
+                            if aggregate_output_1__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("aggregateOutput1"));
+                            }
+                            aggregate_output_1__ = map_.next_value()?;
+                        }
+                        GeneratedField::AggregateOutput2 => {
+                            if aggregate_output_2__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("aggregateOutput2"));
+                            }
+                            aggregate_output_2__ = map_.next_value()?;
+                        }
+                        GeneratedField::ClearingPrice => {
+                            if clearing_price__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("clearingPrice"));
+                            }
+                            clearing_price__ =
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::ParticipantCount => {
+                            if participant_count__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("participantCount"));
+                            }
+                            participant_count__ =
+                                Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::BlockHeight => {
+                            if block_height__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("blockHeight"));
+                            }
+                            block_height__ =
+                                Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::__SkipField__ => {
+                            let _ = map_.next_value::<serde::de::IgnoredAny>()?;
+                        }
+                    }
+                }
+                Ok(EventSwapSettlement {
+                    trading_pair: trading_pair__,
+                    aggregate_input_1: aggregate_input_1__,
+                    aggregate_input_2: aggregate_input_2__,
+                    aggregate_output_1: aggregate_output_1__,
+                    aggregate_output_2: aggregate_output_2__,
+                    clearing_price: clearing_price__.unwrap_or_default(),
+                    participant_count: participant_count__.unwrap_or_default(),
+                    block_height: block_height__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("penumbra.core.component.dex.v1.EventSwapSettlement", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for EventValueCircuitBreakerCredit {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
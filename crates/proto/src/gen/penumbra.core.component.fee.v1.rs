@@ -247,6 +247,27 @@ impl ::prost::Name for EventBlockFees {
         ::prost::alloc::format!("penumbra.core.component.fee.v1.{}", Self::NAME)
     }
 }
+/// Emitted at the end of each block, recording the tips included in it by its proposer.
+///
+/// Like the rest of the fees, these tips are burned rather than paid to the proposer, so this
+/// measures the proposer's inclusion of fee-paying transactions, not its income.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EventBlockReward {
+    /// The consensus address of the block proposer.
+    #[prost(bytes = "vec", tag = "1")]
+    pub proposer: ::prost::alloc::vec::Vec<u8>,
+    /// The total tips, after swapping to the native token.
+    #[prost(message, optional, tag = "2")]
+    pub reward: ::core::option::Option<Fee>,
+}
+impl ::prost::Name for EventBlockReward {
+    const NAME: &'static str = "EventBlockReward";
+    const PACKAGE: &'static str = "penumbra.core.component.fee.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("penumbra.core.component.fee.v1.{}", Self::NAME)
+    }
+}
 /// Generated client implementations.
 #[cfg(feature = "rpc")]
 pub mod query_service_client {
@@ -316,6 +316,121 @@ impl<'de> serde::Deserialize<'de> for EventBlockFees {
         deserializer.deserialize_struct("penumbra.core.component.fee.v1.EventBlockFees", FIELDS, GeneratedVisitor)
     }
 }
+impl serde::Serialize for EventBlockReward {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.proposer.is_empty() {
+            len += 1;
+        }
+        if self.reward.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("penumbra.core.component.fee.v1.EventBlockReward", len)?;
+        if !self.proposer.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("proposer", pbjson::private::base64::encode(&self.proposer).as_str())?;
+        }
+        if let Some(v) = self.reward.as_ref() {
+            struct_ser.serialize_field("reward", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for EventBlockReward {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "proposer",
+            "reward",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Proposer,
+            Reward,
+            __SkipField__,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "proposer" => Ok(GeneratedField::Proposer),
+                            "reward" => Ok(GeneratedField::Reward),
+                            _ => Ok(GeneratedField::__SkipField__),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = EventBlockReward;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct penumbra.core.component.fee.v1.EventBlockReward")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<EventBlockReward, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut proposer__ = None;
+                let mut reward__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::Proposer => {
+                            if proposer__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("proposer"));
+                            }
+                            proposer__ =
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::Reward => {
+                            if reward__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("reward"));
+                            }
+                            reward__ = map_.next_value()?;
+                        }
+                        GeneratedField::__SkipField__ => {
+                            let _ = map_.next_value::<serde::de::IgnoredAny>()?;
+                        }
+                    }
+                }
+                Ok(EventBlockReward {
+                    proposer: proposer__.unwrap_or_default(),
+                    reward: reward__,
+                })
+            }
+        }
+        deserializer.deserialize_struct("penumbra.core.component.fee.v1.EventBlockReward", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for EventPaidFee {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -7,6 +7,9 @@ pub struct ShieldedPoolParameters {
     pub fixed_fmd_params: ::core::option::Option<FmdParameters>,
     #[prost(message, optional, tag = "2")]
     pub fmd_meta_params: ::core::option::Option<FmdMetaParameters>,
+    /// Caps on the total supply of assets minted by inbound ICS-20 transfers.
+    #[prost(message, repeated, tag = "3")]
+    pub supply_caps: ::prost::alloc::vec::Vec<AssetSupplyCap>,
 }
 impl ::prost::Name for ShieldedPoolParameters {
     const NAME: &'static str = "ShieldedPoolParameters";
@@ -17,6 +20,25 @@ impl ::prost::Name for ShieldedPoolParameters {
         )
     }
 }
+/// A maximum total supply for an asset.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AssetSupplyCap {
+    /// The IBC-prefixed base denom of the capped asset, such as `transfer/channel-0/uatom`.
+    #[prost(message, optional, tag = "1")]
+    pub denom: ::core::option::Option<super::super::super::asset::v1::Denom>,
+    #[prost(message, optional, tag = "2")]
+    pub max_supply: ::core::option::Option<super::super::super::num::v1::Amount>,
+}
+impl ::prost::Name for AssetSupplyCap {
+    const NAME: &'static str = "AssetSupplyCap";
+    const PACKAGE: &'static str = "penumbra.core.component.shielded_pool.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!(
+            "penumbra.core.component.shielded_pool.v1.{}", Self::NAME
+        )
+    }
+}
 /// Genesis data for the shielded pool component.
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
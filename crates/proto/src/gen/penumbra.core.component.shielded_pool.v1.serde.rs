@@ -382,6 +382,119 @@ impl<'de> serde::Deserialize<'de> for AssetMetadataByIdsResponse {
         deserializer.deserialize_struct("penumbra.core.component.shielded_pool.v1.AssetMetadataByIdsResponse", FIELDS, GeneratedVisitor)
     }
 }
+impl serde::Serialize for AssetSupplyCap {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.denom.is_some() {
+            len += 1;
+        }
+        if self.max_supply.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("penumbra.core.component.shielded_pool.v1.AssetSupplyCap", len)?;
+        if let Some(v) = self.denom.as_ref() {
+            struct_ser.serialize_field("denom", v)?;
+        }
+        if let Some(v) = self.max_supply.as_ref() {
+            struct_ser.serialize_field("maxSupply", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for AssetSupplyCap {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "denom",
+            "max_supply",
+            "maxSupply",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Denom,
+            MaxSupply,
+            __SkipField__,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "denom" => Ok(GeneratedField::Denom),
+                            "maxSupply" | "max_supply" => Ok(GeneratedField::MaxSupply),
+                            _ => Ok(GeneratedField::__SkipField__),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = AssetSupplyCap;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct penumbra.core.component.shielded_pool.v1.AssetSupplyCap")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<AssetSupplyCap, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut denom__ = None;
+                let mut max_supply__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::Denom => {
+                            if denom__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("denom"));
+                            }
+                            denom__ = map_.next_value()?;
+                        }
+                        GeneratedField::MaxSupply => {
+                            if max_supply__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("maxSupply"));
+                            }
+                            max_supply__ = map_.next_value()?;
+                        }
+                        GeneratedField::__SkipField__ => {
+                            let _ = map_.next_value::<serde::de::IgnoredAny>()?;
+                        }
+                    }
+                }
+                Ok(AssetSupplyCap {
+                    denom: denom__,
+                    max_supply: max_supply__,
+                })
+            }
+        }
+        deserializer.deserialize_struct("penumbra.core.component.shielded_pool.v1.AssetSupplyCap", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for EventBroadcastClue {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -3542,6 +3655,9 @@ impl serde::Serialize for ShieldedPoolParameters {
         if self.fmd_meta_params.is_some() {
             len += 1;
         }
+        if !self.supply_caps.is_empty() {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("penumbra.core.component.shielded_pool.v1.ShieldedPoolParameters", len)?;
         if let Some(v) = self.fixed_fmd_params.as_ref() {
             struct_ser.serialize_field("fixedFmdParams", v)?;
@@ -3549,6 +3665,9 @@ impl serde::Serialize for ShieldedPoolParameters {
         if let Some(v) = self.fmd_meta_params.as_ref() {
             struct_ser.serialize_field("fmdMetaParams", v)?;
         }
+        if !self.supply_caps.is_empty() {
+            struct_ser.serialize_field("supplyCaps", &self.supply_caps)?;
+        }
         struct_ser.end()
     }
 }
@@ -3563,12 +3682,15 @@ impl<'de> serde::Deserialize<'de> for ShieldedPoolParameters {
             "fixedFmdParams",
             "fmd_meta_params",
             "fmdMetaParams",
+            "supply_caps",
+            "supplyCaps",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
             FixedFmdParams,
             FmdMetaParams,
+            SupplyCaps,
             __SkipField__,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
@@ -3593,6 +3715,7 @@ impl<'de> serde::Deserialize<'de> for ShieldedPoolParameters {
                         match value {
                             "fixedFmdParams" | "fixed_fmd_params" => Ok(GeneratedField::FixedFmdParams),
                             "fmdMetaParams" | "fmd_meta_params" => Ok(GeneratedField::FmdMetaParams),
+                            "supplyCaps" | "supply_caps" => Ok(GeneratedField::SupplyCaps),
                             _ => Ok(GeneratedField::__SkipField__),
                         }
                     }
@@ -3614,6 +3737,7 @@ impl<'de> serde::Deserialize<'de> for ShieldedPoolParameters {
             {
                 let mut fixed_fmd_params__ = None;
                 let mut fmd_meta_params__ = None;
+                let mut supply_caps__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::FixedFmdParams => {
@@ -3628,6 +3752,12 @@ impl<'de> serde::Deserialize<'de> for ShieldedPoolParameters {
                             }
                             fmd_meta_params__ = map_.next_value()?;
                         }
+                        GeneratedField::SupplyCaps => {
+                            if supply_caps__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("supplyCaps"));
+                            }
+                            supply_caps__ = Some(map_.next_value()?);
+                        }
                         GeneratedField::__SkipField__ => {
                             let _ = map_.next_value::<serde::de::IgnoredAny>()?;
                         }
@@ -3636,6 +3766,7 @@ impl<'de> serde::Deserialize<'de> for ShieldedPoolParameters {
                 Ok(ShieldedPoolParameters {
                     fixed_fmd_params: fixed_fmd_params__,
                     fmd_meta_params: fmd_meta_params__,
+                    supply_caps: supply_caps__.unwrap_or_default(),
                 })
             }
         }
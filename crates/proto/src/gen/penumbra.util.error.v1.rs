@@ -0,0 +1,137 @@
+/// Structured details about an error returned by a Penumbra gRPC service.
+///
+/// Services embed this in the `details` of the `google.rpc.Status` describing a failed call, so
+/// that clients can act on the error without parsing its message.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PenumbraErrorDetail {
+    /// What went wrong.
+    #[prost(enumeration = "PenumbraErrorCode", tag = "1")]
+    pub code: i32,
+    /// Metadata specific to the error code, if any.
+    #[prost(oneof = "penumbra_error_detail::Metadata", tags = "2, 3, 4")]
+    pub metadata: ::core::option::Option<penumbra_error_detail::Metadata>,
+}
+/// Nested message and enum types in `PenumbraErrorDetail`.
+pub mod penumbra_error_detail {
+    /// There isn't enough of an asset available to fund a transaction.
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct InsufficientFunds {
+        /// The asset there isn't enough of.
+        #[prost(message, optional, tag = "1")]
+        pub asset_id: ::core::option::Option<
+            super::super::super::super::core::asset::v1::AssetId,
+        >,
+        /// The amount of the asset available to spend.
+        #[prost(message, optional, tag = "2")]
+        pub available: ::core::option::Option<
+            super::super::super::super::core::num::v1::Amount,
+        >,
+        /// The amount of the asset the transaction requires.
+        #[prost(message, optional, tag = "3")]
+        pub required: ::core::option::Option<
+            super::super::super::super::core::num::v1::Amount,
+        >,
+    }
+    impl ::prost::Name for InsufficientFunds {
+        const NAME: &'static str = "InsufficientFunds";
+        const PACKAGE: &'static str = "penumbra.util.error.v1";
+        fn full_name() -> ::prost::alloc::string::String {
+            ::prost::alloc::format!(
+                "penumbra.util.error.v1.PenumbraErrorDetail.{}", Self::NAME
+            )
+        }
+    }
+    /// A request referred to an asset whose metadata isn't known.
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct UnknownAsset {
+        #[prost(message, optional, tag = "1")]
+        pub asset_id: ::core::option::Option<
+            super::super::super::super::core::asset::v1::AssetId,
+        >,
+    }
+    impl ::prost::Name for UnknownAsset {
+        const NAME: &'static str = "UnknownAsset";
+        const PACKAGE: &'static str = "penumbra.util.error.v1";
+        fn full_name() -> ::prost::alloc::string::String {
+            ::prost::alloc::format!(
+                "penumbra.util.error.v1.PenumbraErrorDetail.{}", Self::NAME
+            )
+        }
+    }
+    /// A transaction's expiry height passed before it could be included in a block.
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct TransactionExpired {
+        #[prost(uint64, tag = "1")]
+        pub expiry_height: u64,
+        #[prost(uint64, tag = "2")]
+        pub current_height: u64,
+    }
+    impl ::prost::Name for TransactionExpired {
+        const NAME: &'static str = "TransactionExpired";
+        const PACKAGE: &'static str = "penumbra.util.error.v1";
+        fn full_name() -> ::prost::alloc::string::String {
+            ::prost::alloc::format!(
+                "penumbra.util.error.v1.PenumbraErrorDetail.{}", Self::NAME
+            )
+        }
+    }
+    /// Metadata specific to the error code, if any.
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Metadata {
+        #[prost(message, tag = "2")]
+        InsufficientFunds(InsufficientFunds),
+        #[prost(message, tag = "3")]
+        UnknownAsset(UnknownAsset),
+        #[prost(message, tag = "4")]
+        TransactionExpired(TransactionExpired),
+    }
+}
+impl ::prost::Name for PenumbraErrorDetail {
+    const NAME: &'static str = "PenumbraErrorDetail";
+    const PACKAGE: &'static str = "penumbra.util.error.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("penumbra.util.error.v1.{}", Self::NAME)
+    }
+}
+/// The kinds of errors described by a `PenumbraErrorDetail`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum PenumbraErrorCode {
+    Unspecified = 0,
+    InsufficientFunds = 1,
+    UnknownAsset = 2,
+    TransactionExpired = 3,
+}
+impl PenumbraErrorCode {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            PenumbraErrorCode::Unspecified => "PENUMBRA_ERROR_CODE_UNSPECIFIED",
+            PenumbraErrorCode::InsufficientFunds => {
+                "PENUMBRA_ERROR_CODE_INSUFFICIENT_FUNDS"
+            }
+            PenumbraErrorCode::UnknownAsset => "PENUMBRA_ERROR_CODE_UNKNOWN_ASSET",
+            PenumbraErrorCode::TransactionExpired => {
+                "PENUMBRA_ERROR_CODE_TRANSACTION_EXPIRED"
+            }
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "PENUMBRA_ERROR_CODE_UNSPECIFIED" => Some(Self::Unspecified),
+            "PENUMBRA_ERROR_CODE_INSUFFICIENT_FUNDS" => Some(Self::InsufficientFunds),
+            "PENUMBRA_ERROR_CODE_UNKNOWN_ASSET" => Some(Self::UnknownAsset),
+            "PENUMBRA_ERROR_CODE_TRANSACTION_EXPIRED" => Some(Self::TransactionExpired),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,571 @@
+impl serde::Serialize for PenumbraErrorCode {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let variant = match self {
+            Self::Unspecified => "PENUMBRA_ERROR_CODE_UNSPECIFIED",
+            Self::InsufficientFunds => "PENUMBRA_ERROR_CODE_INSUFFICIENT_FUNDS",
+            Self::UnknownAsset => "PENUMBRA_ERROR_CODE_UNKNOWN_ASSET",
+            Self::TransactionExpired => "PENUMBRA_ERROR_CODE_TRANSACTION_EXPIRED",
+        };
+        serializer.serialize_str(variant)
+    }
+}
+impl<'de> serde::Deserialize<'de> for PenumbraErrorCode {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "PENUMBRA_ERROR_CODE_UNSPECIFIED",
+            "PENUMBRA_ERROR_CODE_INSUFFICIENT_FUNDS",
+            "PENUMBRA_ERROR_CODE_UNKNOWN_ASSET",
+            "PENUMBRA_ERROR_CODE_TRANSACTION_EXPIRED",
+        ];
+
+        struct GeneratedVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = PenumbraErrorCode;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(formatter, "expected one of: {:?}", &FIELDS)
+            }
+
+            fn visit_i64<E>(self, v: i64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                i32::try_from(v)
+                    .ok()
+                    .and_then(|x| x.try_into().ok())
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Signed(v), &self)
+                    })
+            }
+
+            fn visit_u64<E>(self, v: u64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                i32::try_from(v)
+                    .ok()
+                    .and_then(|x| x.try_into().ok())
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Unsigned(v), &self)
+                    })
+            }
+
+            fn visit_str<E>(self, value: &str) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                match value {
+                    "PENUMBRA_ERROR_CODE_UNSPECIFIED" => Ok(PenumbraErrorCode::Unspecified),
+                    "PENUMBRA_ERROR_CODE_INSUFFICIENT_FUNDS" => Ok(PenumbraErrorCode::InsufficientFunds),
+                    "PENUMBRA_ERROR_CODE_UNKNOWN_ASSET" => Ok(PenumbraErrorCode::UnknownAsset),
+                    "PENUMBRA_ERROR_CODE_TRANSACTION_EXPIRED" => Ok(PenumbraErrorCode::TransactionExpired),
+                    _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
+                }
+            }
+        }
+        deserializer.deserialize_any(GeneratedVisitor)
+    }
+}
+impl serde::Serialize for PenumbraErrorDetail {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.code != 0 {
+            len += 1;
+        }
+        if self.metadata.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("penumbra.util.error.v1.PenumbraErrorDetail", len)?;
+        if self.code != 0 {
+            let v = PenumbraErrorCode::try_from(self.code)
+                .map_err(|_| serde::ser::Error::custom(format!("Invalid variant {}", self.code)))?;
+            struct_ser.serialize_field("code", &v)?;
+        }
+        if let Some(v) = self.metadata.as_ref() {
+            match v {
+                penumbra_error_detail::Metadata::InsufficientFunds(v) => {
+                    struct_ser.serialize_field("insufficientFunds", v)?;
+                }
+                penumbra_error_detail::Metadata::UnknownAsset(v) => {
+                    struct_ser.serialize_field("unknownAsset", v)?;
+                }
+                penumbra_error_detail::Metadata::TransactionExpired(v) => {
+                    struct_ser.serialize_field("transactionExpired", v)?;
+                }
+            }
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for PenumbraErrorDetail {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "code",
+            "insufficient_funds",
+            "insufficientFunds",
+            "unknown_asset",
+            "unknownAsset",
+            "transaction_expired",
+            "transactionExpired",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Code,
+            InsufficientFunds,
+            UnknownAsset,
+            TransactionExpired,
+            __SkipField__,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "code" => Ok(GeneratedField::Code),
+                            "insufficientFunds" | "insufficient_funds" => Ok(GeneratedField::InsufficientFunds),
+                            "unknownAsset" | "unknown_asset" => Ok(GeneratedField::UnknownAsset),
+                            "transactionExpired" | "transaction_expired" => Ok(GeneratedField::TransactionExpired),
+                            _ => Ok(GeneratedField::__SkipField__),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = PenumbraErrorDetail;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct penumbra.util.error.v1.PenumbraErrorDetail")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<PenumbraErrorDetail, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut code__ = None;
+                let mut metadata__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::Code => {
+                            if code__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("code"));
+                            }
+                            code__ = Some(map_.next_value::<PenumbraErrorCode>()? as i32);
+                        }
+                        GeneratedField::InsufficientFunds => {
+                            if metadata__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("insufficientFunds"));
+                            }
+                            metadata__ = map_.next_value::<::std::option::Option<_>>()?.map(penumbra_error_detail::Metadata::InsufficientFunds)
+;
+                        }
+                        GeneratedField::UnknownAsset => {
+                            if metadata__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("unknownAsset"));
+                            }
+                            metadata__ = map_.next_value::<::std::option::Option<_>>()?.map(penumbra_error_detail::Metadata::UnknownAsset)
+;
+                        }
+                        GeneratedField::TransactionExpired => {
+                            if metadata__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("transactionExpired"));
+                            }
+                            metadata__ = map_.next_value::<::std::option::Option<_>>()?.map(penumbra_error_detail::Metadata::TransactionExpired)
+;
+                        }
+                        GeneratedField::__SkipField__ => {
+                            let _ = map_.next_value::<serde::de::IgnoredAny>()?;
+                        }
+                    }
+                }
+                Ok(PenumbraErrorDetail {
+                    code: code__.unwrap_or_default(),
+                    metadata: metadata__,
+                })
+            }
+        }
+        deserializer.deserialize_struct("penumbra.util.error.v1.PenumbraErrorDetail", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for penumbra_error_detail::InsufficientFunds {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.asset_id.is_some() {
+            len += 1;
+        }
+        if self.available.is_some() {
+            len += 1;
+        }
+        if self.required.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("penumbra.util.error.v1.PenumbraErrorDetail.InsufficientFunds", len)?;
+        if let Some(v) = self.asset_id.as_ref() {
+            struct_ser.serialize_field("assetId", v)?;
+        }
+        if let Some(v) = self.available.as_ref() {
+            struct_ser.serialize_field("available", v)?;
+        }
+        if let Some(v) = self.required.as_ref() {
+            struct_ser.serialize_field("required", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for penumbra_error_detail::InsufficientFunds {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "asset_id",
+            "assetId",
+            "available",
+            "required",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            AssetId,
+            Available,
+            Required,
+            __SkipField__,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "assetId" | "asset_id" => Ok(GeneratedField::AssetId),
+                            "available" => Ok(GeneratedField::Available),
+                            "required" => Ok(GeneratedField::Required),
+                            _ => Ok(GeneratedField::__SkipField__),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = penumbra_error_detail::InsufficientFunds;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct penumbra.util.error.v1.PenumbraErrorDetail.InsufficientFunds")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<penumbra_error_detail::InsufficientFunds, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut asset_id__ = None;
+                let mut available__ = None;
+                let mut required__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::AssetId => {
+                            if asset_id__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("assetId"));
+                            }
+                            asset_id__ = map_.next_value()?;
+                        }
+                        GeneratedField::Available => {
+                            if available__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("available"));
+                            }
+                            available__ = map_.next_value()?;
+                        }
+                        GeneratedField::Required => {
+                            if required__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("required"));
+                            }
+                            required__ = map_.next_value()?;
+                        }
+                        GeneratedField::__SkipField__ => {
+                            let _ = map_.next_value::<serde::de::IgnoredAny>()?;
+                        }
+                    }
+                }
+                Ok(penumbra_error_detail::InsufficientFunds {
+                    asset_id: asset_id__,
+                    available: available__,
+                    required: required__,
+                })
+            }
+        }
+        deserializer.deserialize_struct("penumbra.util.error.v1.PenumbraErrorDetail.InsufficientFunds", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for penumbra_error_detail::TransactionExpired {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.expiry_height != 0 {
+            len += 1;
+        }
+        if self.current_height != 0 {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("penumbra.util.error.v1.PenumbraErrorDetail.TransactionExpired", len)?;
+        if self.expiry_height != 0 {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("expiryHeight", ToString::to_string(&self.expiry_height).as_str())?;
+        }
+        if self.current_height != 0 {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("currentHeight", ToString::to_string(&self.current_height).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for penumbra_error_detail::TransactionExpired {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "expiry_height",
+            "expiryHeight",
+            "current_height",
+            "currentHeight",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            ExpiryHeight,
+            CurrentHeight,
+            __SkipField__,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "expiryHeight" | "expiry_height" => Ok(GeneratedField::ExpiryHeight),
+                            "currentHeight" | "current_height" => Ok(GeneratedField::CurrentHeight),
+                            _ => Ok(GeneratedField::__SkipField__),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = penumbra_error_detail::TransactionExpired;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct penumbra.util.error.v1.PenumbraErrorDetail.TransactionExpired")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<penumbra_error_detail::TransactionExpired, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut expiry_height__ = None;
+                let mut current_height__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::ExpiryHeight => {
+                            if expiry_height__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("expiryHeight"));
+                            }
+                            expiry_height__ =
+                                Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::CurrentHeight => {
+                            if current_height__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("currentHeight"));
+                            }
+                            current_height__ =
+                                Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::__SkipField__ => {
+                            let _ = map_.next_value::<serde::de::IgnoredAny>()?;
+                        }
+                    }
+                }
+                Ok(penumbra_error_detail::TransactionExpired {
+                    expiry_height: expiry_height__.unwrap_or_default(),
+                    current_height: current_height__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("penumbra.util.error.v1.PenumbraErrorDetail.TransactionExpired", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for penumbra_error_detail::UnknownAsset {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.asset_id.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("penumbra.util.error.v1.PenumbraErrorDetail.UnknownAsset", len)?;
+        if let Some(v) = self.asset_id.as_ref() {
+            struct_ser.serialize_field("assetId", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for penumbra_error_detail::UnknownAsset {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "asset_id",
+            "assetId",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            AssetId,
+            __SkipField__,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "assetId" | "asset_id" => Ok(GeneratedField::AssetId),
+                            _ => Ok(GeneratedField::__SkipField__),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = penumbra_error_detail::UnknownAsset;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct penumbra.util.error.v1.PenumbraErrorDetail.UnknownAsset")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<penumbra_error_detail::UnknownAsset, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut asset_id__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::AssetId => {
+                            if asset_id__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("assetId"));
+                            }
+                            asset_id__ = map_.next_value()?;
+                        }
+                        GeneratedField::__SkipField__ => {
+                            let _ = map_.next_value::<serde::de::IgnoredAny>()?;
+                        }
+                    }
+                }
+                Ok(penumbra_error_detail::UnknownAsset {
+                    asset_id: asset_id__,
+                })
+            }
+        }
+        deserializer.deserialize_struct("penumbra.util.error.v1.PenumbraErrorDetail.UnknownAsset", FIELDS, GeneratedVisitor)
+    }
+}
@@ -233,6 +233,42 @@ impl ::prost::Name for BroadcastTransactionResponse {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DryRunTransactionRequest {
+    /// The encoded transaction to check.
+    #[prost(bytes = "vec", tag = "1")]
+    pub tx_bytes: ::prost::alloc::vec::Vec<u8>,
+}
+impl ::prost::Name for DryRunTransactionRequest {
+    const NAME: &'static str = "DryRunTransactionRequest";
+    const PACKAGE: &'static str = "penumbra.view.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("penumbra.view.v1.{}", Self::NAME)
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DryRunTransactionResponse {
+    /// Whether the transaction passed every check.
+    #[prost(bool, tag = "1")]
+    pub success: bool,
+    /// The gas the transaction would use.
+    #[prost(message, optional, tag = "2")]
+    pub gas_used: ::core::option::Option<
+        super::super::core::component::fee::v1::Gas,
+    >,
+    /// Why the transaction failed, if it did.
+    #[prost(string, tag = "3")]
+    pub error_message: ::prost::alloc::string::String,
+}
+impl ::prost::Name for DryRunTransactionResponse {
+    const NAME: &'static str = "DryRunTransactionResponse";
+    const PACKAGE: &'static str = "penumbra.view.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("penumbra.view.v1.{}", Self::NAME)
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct TransactionPlannerRequest {
     /// The expiry height for the requested TransactionPlan
     #[prost(uint64, tag = "1")]
@@ -2592,6 +2628,39 @@ pub mod view_service_client {
                 );
             self.inner.server_streaming(req, path, codec).await
         }
+        /// Check a transaction against the view service's knowledge of the chain, without broadcasting it.
+        ///
+        /// This checks the transaction's proofs and signatures, its chain ID and expiry height, and
+        /// whether it spends notes the view service has seen spent. It can't execute the transaction,
+        /// since the view service doesn't have the chain state, so a transaction which passes may still
+        /// fail on chain.
+        pub async fn dry_run_transaction(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DryRunTransactionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::DryRunTransactionResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/penumbra.view.v1.ViewService/DryRunTransaction",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("penumbra.view.v1.ViewService", "DryRunTransaction"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
         /// Get delegation tokens for a given address index. Each delegation token will
         /// be represented by a `ValueView` with the given address index's balance of
         /// that token. Each `ValueView`'s `extended_metadata` field will contain the
@@ -2998,6 +3067,19 @@ pub mod view_service_server {
             tonic::Response<Self::BroadcastTransactionStream>,
             tonic::Status,
         >;
+        /// Check a transaction against the view service's knowledge of the chain, without broadcasting it.
+        ///
+        /// This checks the transaction's proofs and signatures, its chain ID and expiry height, and
+        /// whether it spends notes the view service has seen spent. It can't execute the transaction,
+        /// since the view service doesn't have the chain state, so a transaction which passes may still
+        /// fail on chain.
+        async fn dry_run_transaction(
+            &self,
+            request: tonic::Request<super::DryRunTransactionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::DryRunTransactionResponse>,
+            tonic::Status,
+        >;
         /// Server streaming response type for the DelegationsByAddressIndex method.
         type DelegationsByAddressIndexStream: tonic::codegen::tokio_stream::Stream<
                 Item = std::result::Result<
@@ -4354,6 +4436,53 @@ pub mod view_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/penumbra.view.v1.ViewService/DryRunTransaction" => {
+                    #[allow(non_camel_case_types)]
+                    struct DryRunTransactionSvc<T: ViewService>(pub Arc<T>);
+                    impl<
+                        T: ViewService,
+                    > tonic::server::UnaryService<super::DryRunTransactionRequest>
+                    for DryRunTransactionSvc<T> {
+                        type Response = super::DryRunTransactionResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DryRunTransactionRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ViewService>::dry_run_transaction(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DryRunTransactionSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/penumbra.view.v1.ViewService/DelegationsByAddressIndex" => {
                     #[allow(non_camel_case_types)]
                     struct DelegationsByAddressIndexSvc<T: ViewService>(pub Arc<T>);
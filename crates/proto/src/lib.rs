@@ -43,6 +43,9 @@ pub use state::StateReadProto;
 #[cfg(feature = "cnidarium")]
 pub use state::StateWriteProto;
 
+#[cfg(feature = "rpc")]
+mod error_detail;
+
 pub use penumbra::*;
 
 pub mod penumbra {
@@ -233,6 +236,20 @@ pub mod penumbra {
     }
 
     pub mod util {
+        pub mod error {
+            pub mod v1 {
+                include!("gen/penumbra.util.error.v1.rs");
+                include!("gen/penumbra.util.error.v1.serde.rs");
+            }
+        }
+
+        pub mod cometindex {
+            pub mod v1 {
+                include!("gen/penumbra.util.cometindex.v1.rs");
+                include!("gen/penumbra.util.cometindex.v1.serde.rs");
+            }
+        }
+
         pub mod tendermint_proxy {
             pub mod v1 {
                 include!("gen/penumbra.util.tendermint_proxy.v1.rs");
@@ -348,6 +365,12 @@ pub mod cosmos {
     }
 }
 
+pub mod google {
+    pub mod rpc {
+        include!("gen/google.rpc.rs");
+    }
+}
+
 #[cfg(feature = "rpc")]
 // https://github.com/penumbra-zone/penumbra/issues/3038#issuecomment-1722534133
 pub const FILE_DESCRIPTOR_SET: &[u8] = include_bytes!("gen/proto_descriptor.bin.no_lfs");
@@ -0,0 +1,87 @@
+use anyhow::Result;
+use clap::Parser;
+use cometindex::{
+    async_trait, opt::Options, AppView, BlockEvents, ContextualizedEvent, Indexer, PgTransaction,
+};
+use sqlx::PgPool;
+
+// An example of aggregating over each block in `post_block`: rather than updating a running
+// total for every event, which costs a write per event, the view writes each block's total
+// once, after all of the block's events have been indexed.
+
+const SPEND_EVENT: &str = "penumbra.core.component.shielded_pool.v1.EventSpend";
+const OUTPUT_EVENT: &str = "penumbra.core.component.shielded_pool.v1.EventOutput";
+
+/// The number of shielded pool spends and outputs in each block.
+#[derive(Debug)]
+struct BlockSummaryView {}
+
+#[async_trait]
+impl AppView for BlockSummaryView {
+    async fn init_chain(
+        &self,
+        dbtx: &mut PgTransaction,
+        _app_state: &serde_json::Value,
+    ) -> Result<(), anyhow::Error> {
+        sqlx::query(
+            "
+CREATE TABLE IF NOT EXISTS block_summary_example (
+    height BIGINT PRIMARY KEY,
+    shielded_actions BIGINT NOT NULL
+);
+",
+        )
+        .execute(dbtx.as_mut())
+        .await?;
+        Ok(())
+    }
+
+    fn is_relevant(&self, type_str: &str) -> bool {
+        type_str == SPEND_EVENT || type_str == OUTPUT_EVENT
+    }
+
+    async fn index_event(
+        &self,
+        _dbtx: &mut PgTransaction,
+        _event: &ContextualizedEvent,
+        _src_db: &PgPool,
+    ) -> Result<(), anyhow::Error> {
+        // Everything this view needs is in the block's events passed to `post_block`.
+        Ok(())
+    }
+
+    async fn post_block(
+        &self,
+        dbtx: &mut PgTransaction,
+        height: u64,
+        events: BlockEvents<'_>,
+    ) -> Result<(), anyhow::Error> {
+        let shielded_actions =
+            events.count_of_type(SPEND_EVENT) + events.count_of_type(OUTPUT_EVENT);
+        if shielded_actions == 0 {
+            return Ok(());
+        }
+        sqlx::query(
+            "
+            INSERT INTO block_summary_example (height, shielded_actions)
+            VALUES ($1, $2)
+            ",
+        )
+        .bind(i64::try_from(height)?)
+        .bind(i64::try_from(shielded_actions)?)
+        .execute(dbtx.as_mut())
+        .await?;
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    Indexer::new(Options::parse())
+        .with_default_tracing()
+        .with_index(BlockSummaryView {})
+        .run()
+        .await?;
+
+    Ok(())
+}
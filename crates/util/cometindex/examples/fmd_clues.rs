@@ -1,6 +1,8 @@
 use anyhow::Result;
 use clap::Parser;
-use cometindex::{async_trait, opt::Options, AppView, ContextualizedEvent, Indexer, PgTransaction};
+use cometindex::{
+    async_trait, opt::Options, AppView, BlockEvents, ContextualizedEvent, Indexer, PgTransaction,
+};
 use sqlx::PgPool;
 
 // This example is silly because it doesn't do any "compilation" of the raw
@@ -9,6 +11,35 @@ use sqlx::PgPool;
 // any event) and has its own app specific logic. But it doesn't have to
 // reimplement the binary handling / arg parsing / etc
 
+const CLUE_EVENT: &str = "penumbra.core.component.shielded_pool.v1.EventBroadcastClue";
+
+/// A clue broadcast in a transaction.
+struct BroadcastClue {
+    tx_hash: Vec<u8>,
+    clue: String,
+}
+
+impl TryFrom<&ContextualizedEvent> for BroadcastClue {
+    type Error = anyhow::Error;
+
+    fn try_from(event: &ContextualizedEvent) -> Result<Self, Self::Error> {
+        let clue = event
+            .event
+            .attributes
+            .iter()
+            .find(|attr| attr.key == "clue")
+            .ok_or_else(|| anyhow::anyhow!("fmd_clue attribute not found"))?
+            .value
+            .clone();
+        let tx_hash = event
+            .tx_hash
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("tx_hash not found"))?
+            .to_vec();
+        Ok(Self { tx_hash, clue })
+    }
+}
+
 #[derive(Debug)]
 struct FmdCluesExample {}
 
@@ -34,40 +65,42 @@ CREATE TABLE IF NOT EXISTS fmd_clues_example (
     }
 
     fn is_relevant(&self, type_str: &str) -> bool {
-        type_str == "penumbra.core.component.shielded_pool.v1.EventBroadcastClue"
+        type_str == CLUE_EVENT
     }
 
     async fn index_event(
         &self,
-        dbtx: &mut PgTransaction,
-        event: &ContextualizedEvent,
+        _dbtx: &mut PgTransaction,
+        _event: &ContextualizedEvent,
         _src_db: &PgPool,
+    ) -> Result<(), anyhow::Error> {
+        // The clues are picked out of each block's events in `post_block`.
+        Ok(())
+    }
+
+    async fn post_block(
+        &self,
+        dbtx: &mut PgTransaction,
+        _height: u64,
+        events: BlockEvents<'_>,
     ) -> Result<(), anyhow::Error> {
         // this is just an example in the integration tests, so we don't want to do any
         // - queries against existing table state
-        // - parsing of the event data into structured data
         // - computations of derived data
         // but these should all be possible
-        let clue = event
-            .event
-            .attributes
-            .iter()
-            .find(|attr| attr.key == "clue")
-            .expect("fmd_clue attribute not found")
-            .value
-            .clone();
-        let tx_hash = event.tx_hash.as_ref().expect("tx_hash not found").to_vec();
-
-        sqlx::query(
-            "
-            INSERT INTO fmd_clues (tx_hash, fmd_clue)
-            VALUES ($1, $2)
-            ",
-        )
-        .bind(&tx_hash)
-        .bind(&clue)
-        .execute(dbtx.as_mut())
-        .await?;
+        for clue in events.try_of_type(CLUE_EVENT) {
+            let BroadcastClue { tx_hash, clue } = clue?;
+            sqlx::query(
+                "
+                INSERT INTO fmd_clues (tx_hash, fmd_clue)
+                VALUES ($1, $2)
+                ",
+            )
+            .bind(&tx_hash)
+            .bind(&clue)
+            .execute(dbtx.as_mut())
+            .await?;
+        }
 
         Ok(())
     }
@@ -0,0 +1,158 @@
+use crate::ContextualizedEvent;
+
+/// The events of a single block, with helpers for picking out events of one type.
+#[derive(Clone, Copy, Debug)]
+pub struct BlockEvents<'a> {
+    events: &'a [ContextualizedEvent],
+}
+
+impl<'a> BlockEvents<'a> {
+    pub fn new(events: &'a [ContextualizedEvent]) -> Self {
+        Self { events }
+    }
+
+    fn matching(&self, type_str: &'a str) -> impl Iterator<Item = &'a ContextualizedEvent> + 'a {
+        let events = self.events;
+        events
+            .iter()
+            .filter(move |event| event.event.kind == type_str)
+    }
+
+    /// Decode every event of type `type_str` as a `T`, yielding an error for each event which
+    /// fails to decode.
+    pub fn try_of_type<T, E>(
+        &self,
+        type_str: &'a str,
+    ) -> impl Iterator<Item = anyhow::Result<T>> + 'a
+    where
+        T: for<'e> TryFrom<&'e ContextualizedEvent, Error = E> + 'a,
+        E: Into<anyhow::Error> + 'a,
+    {
+        self.matching(type_str).map(move |event| {
+            T::try_from(event).map_err(|e| {
+                e.into().context(format!(
+                    "failed to decode {type_str} event at height {}, rowid {}",
+                    event.block_height, event.local_rowid
+                ))
+            })
+        })
+    }
+
+    /// Decode every event of type `type_str` as a `T`.
+    ///
+    /// Events which fail to decode are logged and skipped, so that one malformed event doesn't
+    /// stop a view from processing the rest of the block. Use [`BlockEvents::try_of_type`] to
+    /// handle them instead.
+    pub fn of_type<T, E>(&self, type_str: &'a str) -> impl Iterator<Item = T> + 'a
+    where
+        T: for<'e> TryFrom<&'e ContextualizedEvent, Error = E> + 'a,
+        E: Into<anyhow::Error> + 'a,
+    {
+        self.try_of_type(type_str)
+            .filter_map(|decoded| match decoded {
+                Ok(decoded) => Some(decoded),
+                Err(e) => {
+                    tracing::error!("{e:#}");
+                    None
+                }
+            })
+    }
+
+    /// The number of events in the block.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Check whether the block has no events.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// The number of events of type `type_str`, whether or not they decode.
+    pub fn count_of_type(&self, type_str: &str) -> usize {
+        self.events
+            .iter()
+            .filter(|event| event.event.kind == type_str)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::anyhow;
+    use tendermint::abci::Event;
+
+    use super::*;
+
+    const CLUE: &str = "penumbra.core.component.shielded_pool.v1.EventBroadcastClue";
+    const OTHER: &str = "penumbra.core.component.dex.v1.EventSwap";
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct Clue(String);
+
+    impl TryFrom<&ContextualizedEvent> for Clue {
+        type Error = anyhow::Error;
+
+        fn try_from(event: &ContextualizedEvent) -> Result<Self, Self::Error> {
+            event
+                .event
+                .attributes
+                .iter()
+                .find(|attr| attr.key == "clue")
+                .map(|attr| Clue(attr.value.clone()))
+                .ok_or_else(|| anyhow!("missing clue attribute"))
+        }
+    }
+
+    fn event(kind: &str, attributes: &[(&str, &str)], local_rowid: i64) -> ContextualizedEvent {
+        ContextualizedEvent {
+            event: Event {
+                kind: kind.to_string(),
+                attributes: attributes
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), value.to_string()).into())
+                    .collect(),
+            },
+            block_height: 1,
+            tx_hash: None,
+            local_rowid,
+        }
+    }
+
+    #[test]
+    fn only_decodable_events_of_the_requested_type_are_returned() {
+        let events = vec![
+            event(CLUE, &[("clue", "a")], 1),
+            event(OTHER, &[("clue", "not a clue")], 2),
+            event(CLUE, &[("tx", "no clue here")], 3),
+            event(CLUE, &[("clue", "b")], 4),
+            event(OTHER, &[], 5),
+        ];
+        let block = BlockEvents::new(&events);
+
+        let clues: Vec<Clue> = block.of_type(CLUE).collect();
+        assert_eq!(clues, vec![Clue("a".to_string()), Clue("b".to_string())]);
+
+        assert_eq!(block.count_of_type(CLUE), 3);
+        assert_eq!(block.count_of_type(OTHER), 2);
+        assert_eq!(block.count_of_type("unknown"), 0);
+        assert_eq!(block.of_type::<Clue, _>("unknown").count(), 0);
+        assert_eq!(block.len(), 5);
+    }
+
+    #[test]
+    fn failed_decodes_can_be_handled() {
+        let events = vec![
+            event(CLUE, &[("clue", "a")], 1),
+            event(CLUE, &[("tx", "no clue here")], 3),
+        ];
+        let block = BlockEvents::new(&events);
+
+        let clues: Vec<anyhow::Result<Clue>> = block.try_of_type(CLUE).collect();
+        assert_eq!(clues.len(), 2);
+        assert_eq!(clues[0].as_ref().unwrap(), &Clue("a".to_string()));
+        let error = clues[1].as_ref().unwrap_err();
+        assert!(format!("{error:#}").contains("rowid 3"));
+        assert!(format!("{error:#}").contains("missing clue attribute"));
+    }
+}
@@ -0,0 +1,319 @@
+//! Reloading [`AppView`]s from a dynamic library as their source changes, for development.
+//!
+//! # ABI requirements
+//!
+//! Rust has no stable ABI, so a view library only works if it was built by the same compiler, with
+//! the same versions of `cometindex` and its dependencies, as the indexer that loads it. In
+//! practice, this means building both with the same lockfile: either from the same workspace,
+//! or, for a view library outside of the indexer's workspace, with a copy of the workspace's
+//! lockfile. A mismatch is undefined behavior, not an error, so this is only suitable for trusted
+//! code during development, never for production indexing.
+//!
+//! A view library is a crate with `crate-type = ["cdylib"]`, whose library exports a constructor
+//! for its view under the symbol [`DynAppView::CONSTRUCTOR_SYMBOL`]:
+//!
+//! ```ignore
+//! #[no_mangle]
+//! pub fn new() -> Box<dyn cometindex::AppView + Send + Sync> {
+//!     Box::new(MyView::default())
+//! }
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+    time::SystemTime,
+};
+
+use anyhow::{anyhow, Context as _, Result};
+use async_trait::async_trait;
+use libloading::Library;
+use tokio::process::Command;
+
+use crate::{AppView, BlockEvents, ContextualizedEvent, PgPool, PgTransaction};
+
+/// An [`AppView`] loaded from a dynamic library.
+///
+/// The library stays loaded for as long as the view does.
+pub struct DynAppView {
+    // Fields are dropped in declaration order, so the view is dropped before its code is unloaded.
+    view: Box<dyn AppView + Send + Sync>,
+    _library: Library,
+    _copy: LibraryCopy,
+}
+
+/// The copy of a view library that was loaded, deleted once it's unloaded.
+struct LibraryCopy(PathBuf);
+
+impl Drop for LibraryCopy {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_file(&self.0) {
+            tracing::warn!(?e, path = %self.0.display(), "could not delete view library copy");
+        }
+    }
+}
+
+impl DynAppView {
+    /// The symbol a view library exports its constructor under, with the signature
+    /// `fn() -> Box<dyn AppView + Send + Sync>`.
+    pub const CONSTRUCTOR_SYMBOL: &'static [u8] = b"new";
+
+    /// Load the library at `path`, and construct its view.
+    ///
+    /// # Safety
+    ///
+    /// The library must meet the [ABI requirements](self) of this module.
+    pub unsafe fn load(path: &Path) -> Result<Self> {
+        // Libraries are cached by path, so loading a rebuilt library from the same path would
+        // return the old one. Loading each build from a fresh copy avoids this.
+        static LOADED: AtomicU64 = AtomicU64::new(0);
+        let generation = LOADED.fetch_add(1, Ordering::Relaxed);
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| anyhow!("{} is not a file", path.display()))?;
+        let copy = std::env::temp_dir().join(format!(
+            "cometindex-{}-{generation}-{}",
+            std::process::id(),
+            file_name.to_string_lossy()
+        ));
+        std::fs::copy(path, &copy)
+            .with_context(|| format!("could not copy view library {}", path.display()))?;
+        let copy = LibraryCopy(copy);
+
+        let library = Library::new(&copy.0)
+            .with_context(|| format!("could not load view library {}", copy.0.display()))?;
+        let view = {
+            let constructor = library
+                .get::<fn() -> Box<dyn AppView + Send + Sync>>(Self::CONSTRUCTOR_SYMBOL)
+                .context("view library does not export a constructor")?;
+            constructor()
+        };
+        Ok(Self {
+            view,
+            _library: library,
+            _copy: copy,
+        })
+    }
+}
+
+impl std::fmt::Debug for DynAppView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("DynAppView").field(&self.view).finish()
+    }
+}
+
+#[async_trait]
+impl AppView for DynAppView {
+    async fn init_chain(
+        &self,
+        dbtx: &mut PgTransaction,
+        app_state: &serde_json::Value,
+    ) -> Result<(), anyhow::Error> {
+        self.view.init_chain(dbtx, app_state).await
+    }
+
+    fn is_relevant(&self, type_str: &str) -> bool {
+        self.view.is_relevant(type_str)
+    }
+
+    fn expected_attributes(&self, type_str: &str) -> Option<&'static [&'static str]> {
+        self.view.expected_attributes(type_str)
+    }
+
+    async fn index_event(
+        &self,
+        dbtx: &mut PgTransaction,
+        event: &ContextualizedEvent,
+        src_db: &PgPool,
+    ) -> Result<(), anyhow::Error> {
+        self.view.index_event(dbtx, event, src_db).await
+    }
+
+    async fn post_block(
+        &self,
+        dbtx: &mut PgTransaction,
+        height: u64,
+        events: BlockEvents<'_>,
+    ) -> Result<(), anyhow::Error> {
+        self.view.post_block(dbtx, height, events).await
+    }
+
+    async fn rollback(&self) {
+        self.view.rollback().await
+    }
+}
+
+/// The modification time of each Rust source file in `dir`, outside of build output.
+///
+/// Comparing these between polls catches files being created and removed, as well as modified.
+fn source_fingerprint(dir: &Path) -> std::io::Result<BTreeMap<PathBuf, SystemTime>> {
+    let mut sources = BTreeMap::new();
+    let mut dirs = vec![dir.to_owned()];
+    while let Some(dir) = dirs.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                if entry.file_name() != "target" {
+                    dirs.push(path);
+                }
+            } else if file_type.is_file() && path.extension().is_some_and(|ext| ext == "rs") {
+                sources.insert(path, entry.metadata()?.modified()?);
+            }
+        }
+    }
+    Ok(sources)
+}
+
+/// Find the dynamic library produced by a `cargo build --message-format=json`.
+fn library_from_build_output(output: &str) -> Option<PathBuf> {
+    output
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|message| message["reason"] == "compiler-artifact")
+        .filter_map(|message| {
+            message["filenames"]
+                .as_array()?
+                .iter()
+                .filter_map(|name| name.as_str())
+                .find(|name| name.ends_with(std::env::consts::DLL_SUFFIX))
+                .map(PathBuf::from)
+        })
+        .last()
+}
+
+/// Watches the source of a view library, rebuilding and reloading it when it changes.
+///
+/// The sources are polled rather than watched, so a change is only noticed when
+/// [`HotReload::take_change`] is called.
+pub struct HotReload {
+    crate_dir: PathBuf,
+    sources: BTreeMap<PathBuf, SystemTime>,
+}
+
+impl HotReload {
+    /// Watch the crate at `crate_dir` for changes to its Rust sources.
+    pub async fn new(crate_dir: &Path) -> Result<Self> {
+        let crate_dir = crate_dir.to_owned();
+        let sources = Self::fingerprint(&crate_dir).await?;
+        Ok(Self { crate_dir, sources })
+    }
+
+    /// Whether the sources have changed since this was last called.
+    pub async fn take_change(&mut self) -> Result<bool> {
+        let sources = Self::fingerprint(&self.crate_dir).await?;
+        let changed = sources != self.sources;
+        self.sources = sources;
+        Ok(changed)
+    }
+
+    /// Build the view library and load a fresh instance of its view.
+    pub async fn load(&self) -> Result<DynAppView> {
+        tracing::info!(crate_dir = %self.crate_dir.display(), "building view library");
+        let output = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned()))
+            .args(["build", "--lib", "--message-format=json", "--manifest-path"])
+            .arg(self.crate_dir.join("Cargo.toml"))
+            .current_dir(&self.crate_dir)
+            .output()
+            .await
+            .context("could not run cargo")?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "building view library failed:\n{}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+        let library = library_from_build_output(&String::from_utf8_lossy(&output.stdout))
+            .ok_or_else(|| anyhow!("build did not produce a dynamic library; is it a cdylib?"))?;
+        tracing::info!(library = %library.display(), "loading view library");
+        // Safety: hot reloading is opt-in, and documented as requiring a view library which
+        // meets the ABI requirements.
+        tokio::task::spawn_blocking(move || unsafe { DynAppView::load(&library) }).await?
+    }
+
+    async fn fingerprint(crate_dir: &Path) -> Result<BTreeMap<PathBuf, SystemTime>> {
+        let dir = crate_dir.to_owned();
+        tokio::task::spawn_blocking(move || source_fingerprint(&dir))
+            .await?
+            .with_context(|| format!("could not read sources in {}", crate_dir.display()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn only_source_changes_change_the_fingerprint() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |path: &str| {
+            let path = dir.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, "").unwrap();
+            path
+        };
+        let lib = write("src/lib.rs");
+        let lock = write("Cargo.lock");
+        let build_output = write("target/debug/build/out.rs");
+
+        let before = source_fingerprint(dir.path()).unwrap();
+        assert_eq!(before.keys().collect::<Vec<_>>(), vec![&lib]);
+
+        let later = SystemTime::now() + Duration::from_secs(60);
+        for path in [&lock, &build_output] {
+            std::fs::File::options()
+                .write(true)
+                .open(path)
+                .unwrap()
+                .set_modified(later)
+                .unwrap();
+        }
+        assert_eq!(source_fingerprint(dir.path()).unwrap(), before);
+
+        std::fs::File::options()
+            .write(true)
+            .open(&lib)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        assert_ne!(source_fingerprint(dir.path()).unwrap(), before);
+
+        let new_view = write("src/new_view.rs");
+        let after = source_fingerprint(dir.path()).unwrap();
+        assert!(after.contains_key(&new_view));
+        std::fs::remove_file(&new_view).unwrap();
+        assert_ne!(source_fingerprint(dir.path()).unwrap(), after);
+    }
+
+    #[test]
+    fn the_library_is_found_in_cargo_output() {
+        let library = format!(
+            "/views/target/debug/libviews{}",
+            std::env::consts::DLL_SUFFIX
+        );
+        let output = [
+            r#"{"reason":"compiler-artifact","filenames":["/views/target/debug/deps/libdep.rlib"]}"#
+                .to_string(),
+            serde_json::json!({
+                "reason": "compiler-artifact",
+                "filenames": [library],
+            })
+            .to_string(),
+            r#"{"reason":"build-finished","success":true}"#.to_string(),
+            "not json".to_string(),
+        ]
+        .join("\n");
+        assert_eq!(
+            library_from_build_output(&output),
+            Some(PathBuf::from(&library))
+        );
+        assert_eq!(
+            library_from_build_output(r#"{"reason":"build-finished","success":true}"#),
+            None
+        );
+    }
+}
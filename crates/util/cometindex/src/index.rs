@@ -2,7 +2,7 @@ use async_trait::async_trait;
 pub use sqlx::PgPool;
 use sqlx::{Postgres, Transaction};
 
-use crate::ContextualizedEvent;
+use crate::{BlockEvents, ContextualizedEvent};
 
 pub type PgTransaction<'a> = Transaction<'a, Postgres>;
 
@@ -17,10 +17,42 @@ pub trait AppView: std::fmt::Debug {
 
     fn is_relevant(&self, type_str: &str) -> bool;
 
+    /// The attribute keys every event of type `type_str` this view processes must have.
+    ///
+    /// When this returns `Some`, the [`Indexer`](crate::Indexer) checks relevant events of that
+    /// type for these attributes before calling [`AppView::index_event`], so that malformed
+    /// events are caught before they reach the view. The default, `None`, skips validation.
+    fn expected_attributes(&self, _type_str: &str) -> Option<&'static [&'static str]> {
+        None
+    }
+
     async fn index_event(
         &self,
         dbtx: &mut PgTransaction,
         event: &ContextualizedEvent,
         src_db: &PgPool,
     ) -> Result<(), anyhow::Error>;
+
+    /// Called once all the events of the block at `height` have been indexed, in the same
+    /// transaction, before it's committed.
+    ///
+    /// `events` are all of the block's events, including those which aren't relevant to this
+    /// view. Views can use them to aggregate over a block once, rather than updating running
+    /// totals for each event. The default does nothing.
+    async fn post_block(
+        &self,
+        _dbtx: &mut PgTransaction,
+        _height: u64,
+        _events: BlockEvents<'_>,
+    ) -> Result<(), anyhow::Error> {
+        Ok(())
+    }
+
+    /// Called when a transaction this view wrote to was rolled back, before the events in it
+    /// are indexed again.
+    ///
+    /// Views which keep in-memory state derived from the events they index must discard any
+    /// changes made since the last commit, for example by reloading the state from the database.
+    /// The default does nothing, which is right for views that only keep state in the database.
+    async fn rollback(&self) {}
 }
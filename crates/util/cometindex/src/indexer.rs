@@ -1,4 +1,4 @@
-use std::pin::Pin;
+use std::{future::Future, pin::Pin};
 
 use anyhow::{Context as _, Result};
 use futures::{Stream, StreamExt, TryStreamExt};
@@ -7,11 +7,19 @@ use tap::{Tap, TapFallible, TapOptional};
 use tendermint::abci;
 use tracing::{debug, info};
 
-use crate::{opt::Options, AppView, ContextualizedEvent, PgTransaction};
+use crate::{
+    opt::Options, validate::check_required_attributes, AppView, BlockEvents, ContextualizedEvent,
+    PgTransaction, StrictEventValidator,
+};
 
 pub struct Indexer {
     opts: Options,
     indexes: Vec<Box<dyn AppView>>,
+    per_block_transactions: bool,
+    max_block_retries: u32,
+    on_block_commit: Option<Box<dyn Fn(u64) + Send>>,
+    #[cfg(feature = "hot-reload")]
+    hot_reload_dir: Option<std::path::PathBuf>,
 }
 
 impl Indexer {
@@ -19,6 +27,11 @@ impl Indexer {
         Self {
             opts,
             indexes: Vec::new(),
+            per_block_transactions: false,
+            max_block_retries: 3,
+            on_block_commit: None,
+            #[cfg(feature = "hot-reload")]
+            hot_reload_dir: None,
         }
     }
 
@@ -27,6 +40,44 @@ impl Indexer {
         self
     }
 
+    /// Index each block in a single transaction, so that a block's events are either all
+    /// indexed or not indexed at all.
+    ///
+    /// If indexing any event of a block fails, the whole block is rolled back and retried, up to
+    /// [`Indexer::with_max_block_retries`] times, before the indexer halts. Without this, blocks
+    /// are committed in batches of about 1000 events, which is faster when catching up.
+    pub fn with_per_block_transactions(mut self, enabled: bool) -> Self {
+        self.per_block_transactions = enabled;
+        self
+    }
+
+    /// How many times to retry a block which fails to be indexed, with per-block transactions.
+    /// Defaults to 3.
+    pub fn with_max_block_retries(mut self, max_retries: u32) -> Self {
+        self.max_block_retries = max_retries;
+        self
+    }
+
+    /// Call `hook` with the height of each block once it has been committed, with per-block
+    /// transactions.
+    pub fn on_block_commit(mut self, hook: impl Fn(u64) + Send + 'static) -> Self {
+        self.on_block_commit = Some(Box::new(hook));
+        self
+    }
+
+    /// Load an additional view from the view library crate at `watch_path`, rebuilding and
+    /// reloading it whenever its Rust sources change.
+    ///
+    /// After a reload, indexing continues from the watermark with the new view, so events from
+    /// then on are indexed by the new code, without re-indexing from genesis. The indexer pauses
+    /// while the library is rebuilt. See [`hot_reload`](crate::hot_reload) for the requirements
+    /// on the library; this is meant for development only.
+    #[cfg(feature = "hot-reload")]
+    pub fn with_hot_reload_dir(mut self, watch_path: &std::path::Path) -> Self {
+        self.hot_reload_dir = Some(watch_path.to_owned());
+        self
+    }
+
     pub fn with_default_tracing(self) -> Self {
         tracing_subscriber::fmt::init();
         self
@@ -55,10 +106,32 @@ impl Indexer {
                     chain_id: _,
                     poll_ms,
                     genesis_json,
+                    strict_events,
+                    #[cfg(feature = "event-subscription")]
+                    grpc_event_port,
+                    #[cfg(feature = "event-subscription")]
+                    grpc_event_host,
                 },
             indexes,
+            per_block_transactions,
+            max_block_retries,
+            on_block_commit,
+            #[cfg(feature = "hot-reload")]
+            hot_reload_dir,
         } = self;
 
+        #[cfg(feature = "hot-reload")]
+        let mut indexes = indexes;
+        #[cfg(feature = "hot-reload")]
+        let mut hot_reload = match hot_reload_dir {
+            Some(dir) => {
+                let hot_reload = crate::hot_reload::HotReload::new(&dir).await?;
+                indexes.push(Box::new(hot_reload.load().await?));
+                Some(hot_reload)
+            }
+            None => None,
+        };
+
         // Create a source db, with, for sanity, some read only settings.
         // These will be overrideable by a consumer who knows what they're doing,
         // but prevents basic mistakes.
@@ -75,17 +148,24 @@ impl Indexer {
             .connect(&src_database_url)
             .await?;
 
+        #[cfg(feature = "event-subscription")]
+        if let Some(port) = grpc_event_port {
+            serve_event_subscriptions(
+                std::net::SocketAddr::new(grpc_event_host, port),
+                src_db.clone(),
+                poll_ms,
+            );
+        }
+
         let dst_db = PgPool::connect(&dst_database_url).await?;
 
         // Check if the destination db is initialized
-        let dst_db_initialized: bool = sqlx::query_scalar(
-            "SELECT EXISTS (
-            SELECT FROM information_schema.tables 
-            WHERE table_name = 'index_watermark'
-        )",
-        )
-        .fetch_one(&dst_db)
-        .await?;
+        // The table is looked up on the search path, like the queries which use it, rather than in
+        // every schema.
+        let dst_db_initialized: bool =
+            sqlx::query_scalar("SELECT to_regclass('index_watermark') IS NOT NULL")
+                .fetch_one(&dst_db)
+                .await?;
 
         if !dst_db_initialized {
             tracing::info!("no watermark found, initializing with genesis data");
@@ -111,16 +191,49 @@ impl Indexer {
         }
 
         loop {
-            Self::tick(&src_db, &dst_db, &indexes).await?;
+            #[cfg(feature = "hot-reload")]
+            if let Some(hot_reload) = &mut hot_reload {
+                let changed = hot_reload.take_change().await.unwrap_or_else(|e| {
+                    tracing::warn!(?e, "could not check view library sources for changes");
+                    false
+                });
+                if changed {
+                    match hot_reload.load().await {
+                        Ok(view) => {
+                            // The reloadable view is always the last one, and the old instance
+                            // is dropped as it's replaced.
+                            indexes.pop();
+                            indexes.push(Box::new(view));
+                            tracing::info!("reloaded view library, continuing from the watermark");
+                        }
+                        Err(e) => {
+                            tracing::error!(
+                                ?e,
+                                "could not reload view library, keeping the old one"
+                            )
+                        }
+                    }
+                }
+            }
+            if per_block_transactions {
+                Self::tick_per_block(
+                    &src_db,
+                    &dst_db,
+                    &indexes,
+                    strict_events,
+                    max_block_retries,
+                    on_block_commit.as_deref(),
+                )
+                .await?;
+            } else {
+                Self::tick(&src_db, &dst_db, &indexes, strict_events).await?;
+            }
             tokio::time::sleep(poll_ms).await;
         }
     }
 
-    async fn tick(
-        src_db: &PgPool,
-        dst_db: &PgPool,
-        indexes: &[Box<dyn AppView>],
-    ) -> Result<(), anyhow::Error> {
+    /// Fetch the watermark, the rowid of the last event processed, initializing it if needed.
+    async fn fetch_watermark(src_db: &PgPool, dst_db: &PgPool) -> Result<i64> {
         // Fetch the highest rowid processed so far (the watermark)
         let current_watermark: Option<i64> =
             sqlx::query_as("SELECT events_rowid FROM index_watermark")
@@ -148,50 +261,131 @@ impl Indexer {
             .map(|(count,)| count)?
             .tap(|count| info!(%count, %watermark, "new events since last watermark"));
 
-        let mut scanned_events = 0usize;
-        let mut relevant_events = 0usize;
+        Ok(watermark)
+    }
 
-        let mut es = read_events(&src_db, watermark);
-        let mut dbtx = dst_db.begin().await?;
-        while let Some(event) = es.next().await.transpose()? {
-            if scanned_events % 1000 == 0 {
-                tracing::info!(scanned_events, relevant_events);
-            } else {
-                tracing::debug!(
-                    block_height = %event.block_height,
-                    kind = %event.event.kind,
-                    scanned_events,
-                    relevant_events,
-                    "processing event"
-                );
+    /// Pass `event` to every view it's relevant to, returning whether it was relevant to any.
+    async fn index_event(
+        dbtx: &mut PgTransaction<'_>,
+        src_db: &PgPool,
+        indexes: &[Box<dyn AppView>],
+        event: &ContextualizedEvent,
+        strict_events: bool,
+    ) -> Result<bool> {
+        // if not relevant then skip making a db tx for the dst db
+        if !indexes
+            .iter()
+            .any(|index| index.is_relevant(&event.as_ref().kind))
+        {
+            tracing::trace!(kind = %event.as_ref().kind, "event is not relevant to any views");
+            return Ok(false);
+        }
+
+        for index in indexes {
+            if index.is_relevant(&event.as_ref().kind) {
+                tracing::debug!(?event, ?index, "relevant to index");
+                Self::validate_event(index.as_ref(), event, strict_events)?;
+                index.index_event(dbtx, event, src_db).await?;
             }
+        }
+        Ok(true)
+    }
 
-            scanned_events += 1;
+    /// Check that `event` has the attributes `index` expects of events of its type.
+    fn validate_event(
+        index: &dyn AppView,
+        event: &ContextualizedEvent,
+        strict_events: bool,
+    ) -> Result<()> {
+        let Some(expected) = index.expected_attributes(&event.as_ref().kind) else {
+            return Ok(());
+        };
+        if strict_events {
+            StrictEventValidator.validate(event.as_ref(), expected)
+        } else {
+            check_required_attributes(event.as_ref(), expected)
+        }
+        .with_context(|| {
+            format!(
+                "invalid event at rowid {} for {:?}",
+                event.local_rowid, index
+            )
+        })?;
+        Ok(())
+    }
 
-            // if not relevant then skip making a db tx for the dst db
-            if !indexes
-                .iter()
-                .any(|index| index.is_relevant(&event.as_ref().kind))
-            {
-                tracing::trace!(kind = %event.as_ref().kind, "event is not relevant to any views");
-                continue;
+    /// Index the events of a complete block, then call every view's [`AppView::post_block`],
+    /// returning the number of events which were relevant to any view.
+    async fn index_block(
+        dbtx: &mut PgTransaction<'_>,
+        src_db: &PgPool,
+        indexes: &[Box<dyn AppView>],
+        events: &[ContextualizedEvent],
+        strict_events: bool,
+    ) -> Result<usize> {
+        let Some(last) = events.last() else {
+            return Ok(0);
+        };
+        let mut relevant_events = 0usize;
+        for event in events {
+            if Self::index_event(dbtx, src_db, indexes, event, strict_events).await? {
+                relevant_events += 1;
             }
+        }
+        for index in indexes {
+            index
+                .post_block(dbtx, last.block_height, BlockEvents::new(events))
+                .await
+                .with_context(|| {
+                    format!(
+                        "post_block failed at height {} for {:?}",
+                        last.block_height, index
+                    )
+                })?;
+        }
+        // Mark that we got to at least this block
+        update_watermark(dbtx, last.local_rowid).await?;
+        Ok(relevant_events)
+    }
 
-            relevant_events += 1;
+    async fn tick(
+        src_db: &PgPool,
+        dst_db: &PgPool,
+        indexes: &[Box<dyn AppView>],
+        strict_events: bool,
+    ) -> Result<(), anyhow::Error> {
+        let watermark = Self::fetch_watermark(src_db, dst_db).await?;
 
-            for index in indexes {
-                if index.is_relevant(&event.as_ref().kind) {
-                    tracing::debug!(?event, ?index, "relevant to index");
-                    index.index_event(&mut dbtx, &event, &src_db).await?;
-                }
+        let mut scanned_events = 0usize;
+        let mut relevant_events = 0usize;
+        let mut uncommitted_events = 0usize;
+
+        let mut blocks = CompleteBlocks::new(read_events(src_db, watermark));
+        let mut dbtx = dst_db.begin().await?;
+        while let Some(events) = blocks.next().await? {
+            tracing::debug!(
+                block_height = %events[0].block_height,
+                events = events.len(),
+                scanned_events,
+                relevant_events,
+                "processing block"
+            );
+            if scanned_events / 1000 != (scanned_events + events.len()) / 1000 {
+                tracing::info!(scanned_events, relevant_events);
             }
-            // Mark that we got to at least this event
-            update_watermark(&mut dbtx, event.local_rowid).await?;
-            // Only commit in batches of <= 1000 events, for about a 5x performance increase when
-            // catching up.
-            if relevant_events % 1000 == 0 {
+            scanned_events += events.len();
+
+            let relevant =
+                Self::index_block(&mut dbtx, src_db, indexes, &events, strict_events).await?;
+            relevant_events += relevant;
+            uncommitted_events += relevant;
+
+            // Only commit in batches of about 1000 events, for about a 5x performance increase
+            // when catching up.
+            if uncommitted_events >= 1000 {
                 dbtx.commit().await?;
                 dbtx = dst_db.begin().await?;
+                uncommitted_events = 0;
             }
         }
         // Flush out the remaining changes.
@@ -199,6 +393,111 @@ impl Indexer {
 
         Ok(())
     }
+
+    /// Like [`Indexer::tick`], but committing each block in its own transaction.
+    async fn tick_per_block(
+        src_db: &PgPool,
+        dst_db: &PgPool,
+        indexes: &[Box<dyn AppView>],
+        strict_events: bool,
+        max_retries: u32,
+        on_block_commit: Option<&(dyn Fn(u64) + Send)>,
+    ) -> Result<(), anyhow::Error> {
+        let watermark = Self::fetch_watermark(src_db, dst_db).await?;
+
+        let mut blocks = CompleteBlocks::new(read_events(src_db, watermark));
+        while let Some(events) = blocks.next().await? {
+            let height = events[0].block_height;
+            let events = &events;
+            with_retries(max_retries, height, indexes, move || async move {
+                let mut dbtx = dst_db.begin().await?;
+                Self::index_block(&mut dbtx, src_db, indexes, events, strict_events).await?;
+                dbtx.commit().await?;
+                Ok(())
+            })
+            .await?;
+            tracing::debug!(height, events = events.len(), "committed block");
+            if let Some(hook) = on_block_commit {
+                hook(height);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Groups a stream of events, in order, into the events of each block.
+///
+/// The events of the last block read may not all have been written to the source database yet,
+/// so a block is only complete once an event from a later block has been read. The events of
+/// the block in progress are left for the next tick, which reads them again from the watermark.
+struct CompleteBlocks<S> {
+    events: S,
+    pending: Vec<ContextualizedEvent>,
+}
+
+impl<S> CompleteBlocks<S>
+where
+    S: Stream<Item = Result<ContextualizedEvent>> + Unpin,
+{
+    fn new(events: S) -> Self {
+        Self {
+            events,
+            pending: Vec::new(),
+        }
+    }
+
+    /// The events of the next complete block, which are never empty.
+    async fn next(&mut self) -> Result<Option<Vec<ContextualizedEvent>>> {
+        while let Some(event) = self.events.next().await.transpose()? {
+            let block_ended = self
+                .pending
+                .last()
+                .is_some_and(|last| last.block_height != event.block_height);
+            if block_ended {
+                return Ok(Some(std::mem::replace(&mut self.pending, vec![event])));
+            }
+            self.pending.push(event);
+        }
+        Ok(None)
+    }
+}
+
+/// Run `attempt` until it succeeds, retrying the block at `height` up to `max_retries` times.
+///
+/// A failed attempt's transaction is rolled back, so every view is told to roll back its
+/// in-memory state before the block is retried.
+async fn with_retries<F, Fut>(
+    max_retries: u32,
+    height: u64,
+    indexes: &[Box<dyn AppView>],
+    mut attempt: F,
+) -> Result<()>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let mut retries = 0;
+    loop {
+        let result = attempt().await;
+        if result.is_err() {
+            for index in indexes {
+                index.rollback().await;
+            }
+        }
+        match result {
+            Ok(()) => return Ok(()),
+            Err(e) if retries < max_retries => {
+                retries += 1;
+                tracing::warn!(?e, height, retries, "indexing block failed, retrying");
+            }
+            Err(e) => {
+                return Err(e.context(format!(
+                    "indexing block {height} failed after {max_retries} retries"
+                )))
+            }
+        }
+    }
 }
 
 async fn update_watermark(dbtx: &mut PgTransaction<'_>, watermark: i64) -> Result<()> {
@@ -218,7 +517,30 @@ async fn update_watermark(dbtx: &mut PgTransaction<'_>, watermark: i64) -> Resul
         .map_err(anyhow::Error::from)
 }
 
-fn read_events(
+/// Serve subscriptions to the events in `src_db` on `addr`, in the background.
+#[cfg(feature = "event-subscription")]
+fn serve_event_subscriptions(
+    addr: std::net::SocketAddr,
+    src_db: PgPool,
+    poll_ms: std::time::Duration,
+) {
+    use penumbra_proto::util::cometindex::v1::event_subscription_service_server::EventSubscriptionServiceServer;
+
+    let service =
+        EventSubscriptionServiceServer::new(crate::EventSubscriptionServer::new(src_db, poll_ms));
+    tracing::info!(%addr, "serving event subscriptions");
+    tokio::spawn(async move {
+        if let Err(e) = tonic::transport::Server::builder()
+            .add_service(service)
+            .serve(addr)
+            .await
+        {
+            tracing::error!(?e, "event subscription server failed");
+        }
+    });
+}
+
+pub(crate) fn read_events(
     src_db: &PgPool,
     watermark: i64,
 ) -> Pin<Box<dyn Stream<Item = Result<ContextualizedEvent>> + Send + '_>> {
@@ -315,3 +637,243 @@ ORDER BY
 
     event_stream.boxed()
 }
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use anyhow::anyhow;
+    use async_trait::async_trait;
+
+    use super::*;
+
+    fn event(block_height: u64, local_rowid: i64, kind: &str) -> ContextualizedEvent {
+        ContextualizedEvent {
+            event: abci::Event {
+                kind: kind.to_string(),
+                attributes: Vec::new(),
+            },
+            block_height,
+            tx_hash: None,
+            local_rowid,
+        }
+    }
+
+    #[tokio::test]
+    async fn post_block_gets_the_events_of_each_block() {
+        let events = [
+            event(1, 1, "a"),
+            event(1, 2, "b"),
+            event(1, 3, "a"),
+            event(2, 4, "b"),
+            event(4, 5, "c"),
+            event(5, 6, "a"),
+        ];
+        let mut blocks = CompleteBlocks::new(futures::stream::iter(events.map(Ok)));
+        let mut counts = Vec::new();
+        while let Some(block) = blocks.next().await.unwrap() {
+            let rowids: Vec<i64> = block.iter().map(|event| event.local_rowid).collect();
+            counts.push((
+                block[0].block_height,
+                rowids,
+                ["a", "b"].map(|kind| BlockEvents::new(&block).count_of_type(kind)),
+            ));
+        }
+
+        // The block at height 5 may still be being written, so it's left for the next tick.
+        assert_eq!(
+            counts,
+            [
+                (1, vec![1, 2, 3], [2, 1]),
+                (2, vec![4], [0, 1]),
+                (4, vec![5], [0, 0]),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn blocks_stop_at_read_errors() {
+        let events = [
+            Ok(event(1, 1, "a")),
+            Ok(event(2, 2, "a")),
+            Err(anyhow!("connection lost")),
+            Ok(event(3, 3, "a")),
+        ];
+        let mut blocks = CompleteBlocks::new(futures::stream::iter(events));
+        assert_eq!(blocks.next().await.unwrap().unwrap().len(), 1);
+        assert!(blocks.next().await.is_err());
+    }
+
+    /// A view of two kinds of event, each with its own attributes.
+    #[derive(Debug)]
+    struct TwoKindView;
+
+    #[async_trait]
+    impl AppView for TwoKindView {
+        async fn init_chain(
+            &self,
+            _dbtx: &mut PgTransaction,
+            _app_state: &serde_json::Value,
+        ) -> Result<(), anyhow::Error> {
+            Ok(())
+        }
+
+        fn is_relevant(&self, type_str: &str) -> bool {
+            type_str == "a" || type_str == "b"
+        }
+
+        fn expected_attributes(&self, type_str: &str) -> Option<&'static [&'static str]> {
+            match type_str {
+                "a" => Some(&["x"]),
+                "b" => Some(&["y", "z"]),
+                _ => None,
+            }
+        }
+
+        async fn index_event(
+            &self,
+            _dbtx: &mut PgTransaction,
+            _event: &ContextualizedEvent,
+            _src_db: &PgPool,
+        ) -> Result<(), anyhow::Error> {
+            Ok(())
+        }
+    }
+
+    fn event_with(kind: &str, keys: &[&str]) -> ContextualizedEvent {
+        let mut event = event(1, 1, kind);
+        event.event.attributes = keys
+            .iter()
+            .map(|key| (key.to_string(), "value".to_string()).into())
+            .collect();
+        event
+    }
+
+    #[test]
+    fn events_are_validated_against_the_attributes_of_their_type() {
+        let validate = |event: &ContextualizedEvent, strict_events: bool| {
+            Indexer::validate_event(&TwoKindView, event, strict_events).is_ok()
+        };
+        for strict_events in [false, true] {
+            assert!(validate(&event_with("a", &["x"]), strict_events));
+            assert!(validate(&event_with("b", &["y", "z"]), strict_events));
+            assert!(!validate(&event_with("a", &["y", "z"]), strict_events));
+            assert!(!validate(&event_with("b", &["x"]), strict_events));
+        }
+        // Attributes of the view's other kind of event are still unexpected in strict mode.
+        assert!(validate(&event_with("a", &["x", "y"]), false));
+        assert!(!validate(&event_with("a", &["x", "y"]), true));
+    }
+
+    /// A view which keeps a running list of the events it has seen in memory, like a view
+    /// maintaining a tree, and discards the events since the last commit on rollback.
+    #[derive(Debug, Default)]
+    struct StatefulView {
+        committed: Mutex<Vec<i64>>,
+        seen: Mutex<Vec<i64>>,
+    }
+
+    impl StatefulView {
+        fn see(&self, event: i64) {
+            self.seen.lock().unwrap().push(event);
+        }
+
+        fn commit(&self) {
+            *self.committed.lock().unwrap() = self.seen.lock().unwrap().clone();
+        }
+    }
+
+    #[async_trait]
+    impl AppView for Arc<StatefulView> {
+        async fn init_chain(
+            &self,
+            _dbtx: &mut PgTransaction,
+            _app_state: &serde_json::Value,
+        ) -> Result<(), anyhow::Error> {
+            Ok(())
+        }
+
+        fn is_relevant(&self, _type_str: &str) -> bool {
+            true
+        }
+
+        async fn index_event(
+            &self,
+            _dbtx: &mut PgTransaction,
+            _event: &ContextualizedEvent,
+            _src_db: &PgPool,
+        ) -> Result<(), anyhow::Error> {
+            Ok(())
+        }
+
+        async fn rollback(&self) {
+            *self.seen.lock().unwrap() = self.committed.lock().unwrap().clone();
+        }
+    }
+
+    /// Index the events of a block into `committed`, as a transaction would, failing on the
+    /// third event of the first `failures` attempts.
+    ///
+    /// Events are also passed to `view`, which keeps them in memory.
+    async fn index_block(
+        events: &[i64],
+        attempts: &Mutex<usize>,
+        failures: usize,
+        committed: &Mutex<Vec<i64>>,
+        view: &StatefulView,
+    ) -> Result<()> {
+        let attempt = {
+            let mut attempts = attempts.lock().unwrap();
+            *attempts += 1;
+            *attempts
+        };
+        let mut written = Vec::new();
+        for (i, event) in events.iter().enumerate() {
+            if i == 2 && attempt <= failures {
+                // Returning drops `written`, like rolling back a transaction.
+                return Err(anyhow!("injected failure"));
+            }
+            view.see(*event);
+            written.push(*event);
+        }
+        committed.lock().unwrap().extend(written);
+        view.commit();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn a_failed_block_is_retried_and_committed_once() {
+        let events = [1, 2, 3, 4, 5];
+        let attempts = Mutex::new(0);
+        let committed = Mutex::new(Vec::new());
+        let view = Arc::new(StatefulView::default());
+        let indexes: Vec<Box<dyn AppView>> = vec![Box::new(view.clone())];
+        with_retries(3, 7, &indexes, || {
+            index_block(&events, &attempts, 2, &committed, &view)
+        })
+        .await
+        .unwrap();
+        assert_eq!(*attempts.lock().unwrap(), 3);
+        assert_eq!(*committed.lock().unwrap(), events);
+        // The events seen by the failed attempts were rolled back, rather than seen again.
+        assert_eq!(*view.seen.lock().unwrap(), events);
+    }
+
+    #[tokio::test]
+    async fn a_block_which_keeps_failing_halts_indexing() {
+        let events = [1, 2, 3, 4, 5];
+        let attempts = Mutex::new(0);
+        let committed = Mutex::new(Vec::new());
+        let view = Arc::new(StatefulView::default());
+        let indexes: Vec<Box<dyn AppView>> = vec![Box::new(view.clone())];
+        let error = with_retries(3, 7, &indexes, || {
+            index_block(&events, &attempts, 10, &committed, &view)
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(*attempts.lock().unwrap(), 4);
+        assert!(committed.lock().unwrap().is_empty());
+        assert!(view.seen.lock().unwrap().is_empty());
+        assert_eq!(error.to_string(), "indexing block 7 failed after 3 retries");
+    }
+}
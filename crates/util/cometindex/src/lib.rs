@@ -1,12 +1,24 @@
+mod block_events;
 mod contextualized;
 pub mod engine;
+#[cfg(feature = "hot-reload")]
+pub mod hot_reload;
 pub mod index;
 pub mod indexer;
 pub mod opt;
+#[cfg(feature = "event-subscription")]
+pub mod subscription;
+pub mod validate;
 
+pub use block_events::BlockEvents;
 pub use contextualized::ContextualizedEvent;
+#[cfg(feature = "hot-reload")]
+pub use hot_reload::DynAppView;
 pub use index::{AppView, PgPool, PgTransaction};
 pub use indexer::Indexer;
+#[cfg(feature = "event-subscription")]
+pub use subscription::{EventFilter, EventSubscriptionServer};
+pub use validate::{EventValidationError, StrictEventValidator};
 
 pub use async_trait::async_trait;
 
@@ -30,6 +30,28 @@ pub struct Options {
     /// A file path for the genesis file to use when initializing the indexer.
     #[clap(short, long)]
     pub genesis_json: PathBuf,
+
+    /// Reject events with attributes that the views processing them don't expect.
+    ///
+    /// Missing attributes are always rejected, for views which declare the attributes they expect.
+    #[clap(long)]
+    pub strict_events: bool,
+
+    /// If set, serve an event subscription gRPC service on this port.
+    ///
+    /// Subscribers can stream the raw events from the source database without running an indexer
+    /// of their own.
+    #[cfg(feature = "event-subscription")]
+    #[clap(long)]
+    pub grpc_event_port: Option<u16>,
+
+    /// The address to serve the event subscription gRPC service on.
+    ///
+    /// Defaults to localhost, so that the source database isn't exposed to the network unless
+    /// asked for.
+    #[cfg(feature = "event-subscription")]
+    #[clap(long, default_value = "127.0.0.1")]
+    pub grpc_event_host: std::net::IpAddr,
 }
 
 /// Parses a string containing a [`Duration`], represented as a number of milliseconds.
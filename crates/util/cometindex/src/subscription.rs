@@ -0,0 +1,300 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use futures::{Stream, StreamExt};
+use penumbra_proto::{
+    tendermint::abci as pb_abci,
+    util::cometindex::v1::{
+        self as pb, event_subscription_service_server::EventSubscriptionService,
+    },
+};
+use sqlx::PgPool;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::Status;
+
+use crate::{indexer::read_events, ContextualizedEvent};
+
+/// The number of events buffered for each subscriber before reading from the database pauses.
+const SUBSCRIBER_BUFFER: usize = 1000;
+
+/// Selects the events sent to a subscriber.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EventFilter {
+    /// The event types to send, or every type if empty.
+    pub type_strings: Vec<String>,
+    /// The height of the first block whose events should be sent.
+    pub start_height: u64,
+}
+
+impl EventFilter {
+    pub fn matches(&self, event: &ContextualizedEvent) -> bool {
+        event.block_height >= self.start_height
+            && (self.type_strings.is_empty()
+                || self
+                    .type_strings
+                    .iter()
+                    .any(|kind| *kind == event.event.kind))
+    }
+}
+
+impl From<pb::EventFilter> for EventFilter {
+    fn from(msg: pb::EventFilter) -> Self {
+        Self {
+            type_strings: msg.type_strings,
+            start_height: msg.start_height,
+        }
+    }
+}
+
+impl From<EventFilter> for pb::EventFilter {
+    fn from(filter: EventFilter) -> Self {
+        Self {
+            type_strings: filter.type_strings,
+            start_height: filter.start_height,
+        }
+    }
+}
+
+impl From<&ContextualizedEvent> for pb::ContextualizedEvent {
+    fn from(event: &ContextualizedEvent) -> Self {
+        Self {
+            event: Some(pb_abci::Event {
+                r#type: event.event.kind.clone(),
+                attributes: event
+                    .event
+                    .attributes
+                    .iter()
+                    .map(|attr| pb_abci::EventAttribute {
+                        key: attr.key.clone(),
+                        value: attr.value.clone(),
+                        index: attr.index,
+                    })
+                    .collect(),
+            }),
+            block_height: event.block_height,
+            tx_hash: event.tx_hash.map(Vec::from).unwrap_or_default(),
+        }
+    }
+}
+
+/// Serves an [`EventSubscriptionService`] from the raw events in a CometBFT source database.
+#[derive(Clone, Debug)]
+pub struct EventSubscriptionServer {
+    src_db: PgPool,
+    poll_ms: Duration,
+}
+
+impl EventSubscriptionServer {
+    /// Serve events from `src_db`, checking it for new events every `poll_ms`.
+    pub fn new(src_db: PgPool, poll_ms: Duration) -> Self {
+        Self { src_db, poll_ms }
+    }
+}
+
+#[async_trait]
+impl EventSubscriptionService for EventSubscriptionServer {
+    type SubscribeEventsStream = ReceiverStream<Result<pb::EventResponse, Status>>;
+
+    async fn subscribe_events(
+        &self,
+        request: tonic::Request<pb::EventFilter>,
+    ) -> Result<tonic::Response<Self::SubscribeEventsStream>, Status> {
+        let filter = EventFilter::from(request.into_inner());
+        let (tx, rx) = mpsc::channel(SUBSCRIBER_BUFFER);
+        let src_db = self.src_db.clone();
+        let poll_ms = self.poll_ms;
+        tokio::spawn(async move {
+            if let Err(e) = stream_events(&src_db, &filter, poll_ms, &tx).await {
+                tracing::warn!(?e, "event subscription failed");
+                let _ = tx.send(Err(Status::internal(format!("{e:#}")))).await;
+            }
+        });
+        Ok(tonic::Response::new(ReceiverStream::new(rx)))
+    }
+}
+
+/// Send every event matching `filter` to `tx`, including new events as they are recorded, until
+/// the subscriber goes away.
+async fn stream_events(
+    src_db: &PgPool,
+    filter: &EventFilter,
+    poll_ms: Duration,
+    tx: &mpsc::Sender<Result<pb::EventResponse, Status>>,
+) -> Result<()> {
+    // Events are numbered in the order they were recorded, so every event from the start height
+    // onwards comes after the last event of the blocks before it.
+    let mut watermark: i64 = sqlx::query_scalar(
+        "SELECT COALESCE(MAX(events.rowid), 0) FROM events JOIN blocks ON events.block_id = blocks.rowid WHERE blocks.height < $1",
+    )
+    .bind(i64::try_from(filter.start_height)?)
+    .fetch_one(src_db)
+    .await?;
+
+    loop {
+        match forward_events(read_events(src_db, watermark), filter, tx, watermark).await? {
+            Some(last_rowid) => watermark = last_rowid,
+            None => return Ok(()),
+        }
+        tokio::time::sleep(poll_ms).await;
+    }
+}
+
+/// Send the events matching `filter` to `tx`, in the order they appear in `events`.
+///
+/// Returns the rowid of the last event read, or `watermark` if there were none, so that the next
+/// batch can pick up where this one left off. Returns `None` if the subscriber has gone away.
+///
+/// Sending waits for the subscriber to make room in the channel, so a slow subscriber slows down
+/// reading rather than having events pile up in memory.
+async fn forward_events(
+    events: impl Stream<Item = Result<ContextualizedEvent>>,
+    filter: &EventFilter,
+    tx: &mpsc::Sender<Result<pb::EventResponse, Status>>,
+    mut watermark: i64,
+) -> Result<Option<i64>> {
+    let mut events = std::pin::pin!(events);
+    while let Some(event) = events.next().await.transpose()? {
+        watermark = event.local_rowid;
+        if !filter.matches(&event) {
+            continue;
+        }
+        let response = pb::EventResponse {
+            event: Some((&event).into()),
+            // CometBFT has instant finality, so every recorded event is final.
+            finalized: true,
+        };
+        if tx.send(Ok(response)).await.is_err() {
+            return Ok(None);
+        }
+    }
+    Ok(Some(watermark))
+}
+
+#[cfg(test)]
+mod tests {
+    use penumbra_proto::{
+        core::component::shielded_pool::v1::EventBroadcastClue, crypto::decaf377_fmd::v1::Clue,
+        event::ProtoEvent,
+    };
+    use tendermint::abci;
+
+    use super::*;
+
+    const CLUE_KIND: &str = "penumbra.core.component.shielded_pool.v1.EventBroadcastClue";
+
+    /// Three blocks, each with two clues interleaved with events of another kind.
+    fn blocks() -> Vec<ContextualizedEvent> {
+        let mut events = Vec::new();
+        for block_height in 1..=3u64 {
+            for i in 0..2u8 {
+                for event in [
+                    EventBroadcastClue {
+                        clue: Some(Clue {
+                            inner: vec![block_height as u8, i],
+                        }),
+                        tx: None,
+                    }
+                    .into_event(),
+                    abci::Event::new(
+                        "penumbra.core.component.sct.v1.EventAnchor",
+                        Vec::<abci::EventAttribute>::new(),
+                    ),
+                ] {
+                    events.push(ContextualizedEvent {
+                        event,
+                        block_height,
+                        tx_hash: Some([block_height as u8; 32]),
+                        local_rowid: events.len() as i64 + 1,
+                    });
+                }
+            }
+        }
+        events
+    }
+
+    /// The height and `clue` attribute of an event.
+    fn clue(event: &pb_abci::Event, block_height: u64) -> (u64, String) {
+        let value = event
+            .attributes
+            .iter()
+            .find(|attr| attr.key == "clue")
+            .expect("clue events have a clue attribute")
+            .value
+            .clone();
+        (block_height, value)
+    }
+
+    #[tokio::test]
+    async fn broadcast_clues_are_received_in_order() {
+        let events = blocks();
+        let expected: Vec<_> = events
+            .iter()
+            .filter(|event| event.event.kind == CLUE_KIND)
+            .map(|event| {
+                let pb::ContextualizedEvent {
+                    event: pb_event,
+                    block_height,
+                    ..
+                } = event.into();
+                clue(&pb_event.unwrap(), block_height)
+            })
+            .collect();
+        assert_eq!(expected.len(), 6);
+
+        let filter = EventFilter {
+            type_strings: vec![CLUE_KIND.to_string()],
+            start_height: 0,
+        };
+        // A single slot forces the forwarder to wait on the subscriber after every event.
+        let (tx, rx) = mpsc::channel(1);
+        let forwarder = tokio::spawn(async move {
+            forward_events(
+                futures::stream::iter(events.into_iter().map(Ok)),
+                &filter,
+                &tx,
+                0,
+            )
+            .await
+        });
+
+        let received: Vec<_> = ReceiverStream::new(rx)
+            .map(|response| {
+                let response = response.expect("no errors are sent");
+                assert!(response.finalized);
+                let event = response.event.expect("responses carry an event");
+                assert_eq!(event.tx_hash, vec![event.block_height as u8; 32]);
+                clue(&event.event.unwrap(), event.block_height)
+            })
+            .collect()
+            .await;
+        assert_eq!(received, expected);
+        assert_eq!(forwarder.await.unwrap().unwrap(), Some(12));
+    }
+
+    #[test]
+    fn filters_select_by_type_and_height() {
+        let events = blocks();
+        let from_height_2 = EventFilter {
+            type_strings: vec![],
+            start_height: 2,
+        };
+        assert_eq!(
+            events.iter().filter(|e| from_height_2.matches(e)).count(),
+            8
+        );
+
+        let clues_from_height_3 = EventFilter {
+            type_strings: vec![CLUE_KIND.to_string()],
+            start_height: 3,
+        };
+        assert_eq!(
+            events
+                .iter()
+                .filter(|e| clues_from_height_3.matches(e))
+                .count(),
+            2
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use tendermint::abci::Event;
+
+/// An event that does not have the shape an [`AppView`](crate::AppView) expects.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum EventValidationError {
+    #[error("{event_type} event is missing required attribute {key:?}")]
+    MissingAttribute {
+        key: &'static str,
+        event_type: String,
+    },
+    #[error("{event_type} event has unexpected attribute {key:?}")]
+    UnexpectedAttribute { key: String, event_type: String },
+}
+
+/// Check that `event` has every attribute in `expected`.
+///
+/// Extra attributes are allowed, since applications may add attributes to events over time
+/// without breaking existing views.
+pub fn check_required_attributes(
+    event: &Event,
+    expected: &'static [&'static str],
+) -> Result<(), EventValidationError> {
+    for &key in expected {
+        if !event.attributes.iter().any(|attr| attr.key == key) {
+            return Err(EventValidationError::MissingAttribute {
+                key,
+                event_type: event.kind.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// A validator which, in addition to requiring the expected attributes, rejects events with
+/// attributes a view doesn't know about.
+///
+/// This is enabled by `--strict-events`, and is useful for catching event schema changes during
+/// development, rather than having them silently ignored.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StrictEventValidator;
+
+impl StrictEventValidator {
+    pub fn validate(
+        &self,
+        event: &Event,
+        expected: &'static [&'static str],
+    ) -> Result<(), EventValidationError> {
+        check_required_attributes(event, expected)?;
+        if let Some(attr) = event
+            .attributes
+            .iter()
+            .find(|attr| !expected.contains(&attr.key.as_str()))
+        {
+            return Err(EventValidationError::UnexpectedAttribute {
+                key: attr.key.clone(),
+                event_type: event.kind.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const EXPECTED: &[&str] = &["client_id", "client_type", "consensus_height"];
+
+    fn event(keys: &[String]) -> Event {
+        Event {
+            kind: "update_client".to_string(),
+            attributes: keys
+                .iter()
+                .map(|key| (key.clone(), "value".to_string()).into())
+                .collect(),
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn missing_attributes_are_rejected(present in proptest::collection::vec(any::<bool>(), EXPECTED.len())) {
+            let keys: Vec<String> = EXPECTED
+                .iter()
+                .zip(&present)
+                .filter(|(_, present)| **present)
+                .map(|(key, _)| key.to_string())
+                .collect();
+            let event = event(&keys);
+
+            let result = check_required_attributes(&event, EXPECTED);
+            match EXPECTED.iter().zip(&present).find(|(_, present)| !**present) {
+                Some((key, _)) => prop_assert_eq!(
+                    result,
+                    Err(EventValidationError::MissingAttribute {
+                        key: *key,
+                        event_type: "update_client".to_string(),
+                    })
+                ),
+                None => prop_assert!(result.is_ok()),
+            }
+            prop_assert_eq!(
+                StrictEventValidator.validate(&event, EXPECTED).is_ok(),
+                present.iter().all(|present| *present)
+            );
+        }
+
+        #[test]
+        fn extra_attributes_are_rejected_only_in_strict_mode(extra in "[a-z_]{1,16}") {
+            prop_assume!(!EXPECTED.contains(&extra.as_str()));
+            let mut keys: Vec<String> = EXPECTED.iter().map(|key| key.to_string()).collect();
+            keys.push(extra.clone());
+            let event = event(&keys);
+
+            prop_assert!(check_required_attributes(&event, EXPECTED).is_ok());
+            prop_assert_eq!(
+                StrictEventValidator.validate(&event, EXPECTED),
+                Err(EventValidationError::UnexpectedAttribute {
+                    key: extra,
+                    event_type: "update_client".to_string(),
+                })
+            );
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//! A view library for the hot reload test.
+//!
+//! The version of the view's logic is picked when the library is built, from the
+//! `RELOAD_VIEW_VERSION` environment variable, so that the test can change the logic without
+//! editing this file.
+
+use cometindex::{async_trait, AppView, ContextualizedEvent, PgPool, PgTransaction};
+
+/// The type of the events the view takes.
+const EVENT_KIND: &str = "reload_view.block";
+
+/// The version of the view's logic this library was built with.
+const VERSION: &str = match option_env!("RELOAD_VIEW_VERSION") {
+    Some(version) => version,
+    None => "1",
+};
+
+/// A view which records the version of its logic that indexed each block.
+#[derive(Debug)]
+struct VersionedView;
+
+#[async_trait]
+impl AppView for VersionedView {
+    async fn init_chain(
+        &self,
+        dbtx: &mut PgTransaction,
+        _app_state: &serde_json::Value,
+    ) -> Result<(), anyhow::Error> {
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS reload_view_blocks (
+                height BIGINT PRIMARY KEY,
+                version TEXT NOT NULL
+            )",
+        )
+        .execute(dbtx.as_mut())
+        .await?;
+        Ok(())
+    }
+
+    fn is_relevant(&self, type_str: &str) -> bool {
+        type_str == EVENT_KIND
+    }
+
+    async fn index_event(
+        &self,
+        dbtx: &mut PgTransaction,
+        event: &ContextualizedEvent,
+        _src_db: &PgPool,
+    ) -> Result<(), anyhow::Error> {
+        sqlx::query("INSERT INTO reload_view_blocks (height, version) VALUES ($1, $2)")
+            .bind(i64::try_from(event.block_height)?)
+            .bind(VERSION)
+            .execute(dbtx.as_mut())
+            .await?;
+        Ok(())
+    }
+}
+
+#[no_mangle]
+pub fn new() -> Box<dyn AppView + Send + Sync> {
+    Box::new(VersionedView)
+}
@@ -0,0 +1,203 @@
+//! Reloading a view library while indexing, with the `hot-reload` feature.
+#![cfg(feature = "hot-reload")]
+
+use std::{
+    future::Future,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{Context as _, Result};
+use clap::Parser as _;
+use cometindex::{opt::Options, Indexer};
+use sqlx::{Executor as _, PgPool};
+
+/// The environment variable holding the URL of a scratch database for the test.
+const TEST_DATABASE_URL: &str = "COMETINDEX_TEST_DATABASE_URL";
+
+/// The height of the last block indexed by the original view.
+const RELOAD_AFTER: u64 = 50;
+
+/// The height of the last block indexed by the test.
+const LAST_BLOCK: u64 = 100;
+
+/// How long to wait for the indexer, which includes building the view library from scratch.
+const TIMEOUT: Duration = Duration::from_secs(600);
+
+fn fixture_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/reload-view")
+}
+
+/// The view library as built by the indexer.
+fn fixture_library(target_dir: &Path) -> PathBuf {
+    target_dir.join("debug").join(format!(
+        "{}cometindex_reload_view{}",
+        std::env::consts::DLL_PREFIX,
+        std::env::consts::DLL_SUFFIX
+    ))
+}
+
+/// Mark `path` as modified.
+fn touch(path: &Path) {
+    std::fs::File::options()
+        .write(true)
+        .open(path)
+        .unwrap()
+        .set_modified(SystemTime::now() + Duration::from_secs(1))
+        .unwrap();
+}
+
+async fn within_timeout<T>(what: &str, future: impl Future<Output = T>) -> Result<T> {
+    tokio::time::timeout(TIMEOUT, future)
+        .await
+        .with_context(|| format!("timed out waiting for {what}"))
+}
+
+/// Create a fresh schema in the test database, returning a URL which uses it.
+async fn schema(url: &str, name: &str) -> Result<String> {
+    let schema = format!(
+        "cometindex_test_{}_{}_{name}",
+        std::process::id(),
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)?
+            .as_micros()
+    );
+    PgPool::connect(url)
+        .await?
+        .execute(format!("CREATE SCHEMA {schema}").as_str())
+        .await?;
+    let separator = if url.contains('?') { '&' } else { '?' };
+    Ok(format!(
+        "{url}{separator}options=-c%20search_path%3D{schema}"
+    ))
+}
+
+/// Insert a block into the raw event database, with a single event for the fixture view.
+async fn insert_block(src_db: &PgPool, height: u64) -> Result<()> {
+    let block_id: i64 = sqlx::query_scalar(
+        "INSERT INTO blocks (height, chain_id, created_at)
+         VALUES ($1, 'cometindex-test', NOW())
+         RETURNING rowid",
+    )
+    .bind(i64::try_from(height)?)
+    .fetch_one(src_db)
+    .await?;
+    let event_id: i64 =
+        sqlx::query_scalar("INSERT INTO events (block_id, type) VALUES ($1, $2) RETURNING rowid")
+            .bind(block_id)
+            .bind("reload_view.block")
+            .fetch_one(src_db)
+            .await?;
+    sqlx::query(
+        "INSERT INTO attributes (event_id, key, composite_key, value)
+         VALUES ($1, 'height', 'reload_view.block.height', $2)",
+    )
+    .bind(event_id)
+    .bind(height.to_string())
+    .execute(src_db)
+    .await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "needs a scratch database in COMETINDEX_TEST_DATABASE_URL"]
+async fn blocks_after_a_reload_are_indexed_by_the_new_view() -> Result<()> {
+    let url = std::env::var(TEST_DATABASE_URL)
+        .with_context(|| format!("{TEST_DATABASE_URL} must be set to run this test"))?;
+    let src_url = schema(&url, "src").await?;
+    let dst_url = schema(&url, "dst").await?;
+
+    let src_db = PgPool::connect(&src_url).await?;
+    src_db.execute(include_str!("../vendor/schema.sql")).await?;
+    // The last block is only indexed once a later one exists, so inserting one block past the
+    // reload leaves the indexer waiting there.
+    for height in 1..=RELOAD_AFTER + 1 {
+        insert_block(&src_db, height).await?;
+    }
+
+    // The fixture isn't a member of the workspace, so that its features aren't unified into it;
+    // building it with the workspace's lockfile and its own target directory keeps its
+    // dependencies the same as this test's.
+    let workspace_lock = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../../Cargo.lock");
+    std::fs::copy(&workspace_lock, fixture_dir().join("Cargo.lock"))
+        .context("could not copy the workspace's lockfile to the fixture")?;
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("reload-view");
+    std::env::set_var("CARGO_TARGET_DIR", &target_dir);
+    std::env::set_var("RELOAD_VIEW_VERSION", "1");
+
+    let genesis = tempfile::NamedTempFile::new()?;
+    std::fs::write(genesis.path(), r#"{"app_state": {}}"#)?;
+    let genesis_json = genesis.path().display().to_string();
+    let opts = Options::try_parse_from([
+        "cometindex",
+        "--src-database-url",
+        src_url.as_str(),
+        "--dst-database-url",
+        dst_url.as_str(),
+        "--genesis-json",
+        genesis_json.as_str(),
+        "--poll-ms",
+        "10",
+    ])?;
+
+    let (committed_tx, mut committed) = tokio::sync::watch::channel(0);
+    let indexer = Indexer::new(opts)
+        .with_per_block_transactions(true)
+        .on_block_commit(move |height| {
+            committed_tx.send_replace(height);
+        })
+        .with_hot_reload_dir(&fixture_dir())
+        .run();
+
+    let reload = async {
+        within_timeout(
+            "the original view",
+            committed.wait_for(|&height| height >= RELOAD_AFTER),
+        )
+        .await??;
+
+        // Rebuilding with a different version of the logic stands in for editing the view.
+        let built = std::fs::metadata(fixture_library(&target_dir))?.modified()?;
+        std::env::set_var("RELOAD_VIEW_VERSION", "2");
+        touch(&fixture_dir().join("src/lib.rs"));
+        // The indexer reloads the library as soon as it's rebuilt, before indexing again, so the
+        // rest of the blocks are only inserted once the rebuild is done.
+        within_timeout("the view library to be rebuilt", async {
+            while std::fs::metadata(fixture_library(&target_dir))?.modified()? == built {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+            anyhow::Ok(())
+        })
+        .await??;
+        for height in RELOAD_AFTER + 2..=LAST_BLOCK + 1 {
+            insert_block(&src_db, height).await?;
+        }
+
+        within_timeout(
+            "the reloaded view",
+            committed.wait_for(|&height| height >= LAST_BLOCK),
+        )
+        .await??;
+        anyhow::Ok(())
+    };
+    // The indexer never stops by itself, so it's dropped once the test is done with it.
+    tokio::select! {
+        result = indexer => anyhow::bail!("the indexer stopped: {result:?}"),
+        result = reload => result?,
+    }
+
+    let dst_db = PgPool::connect(&dst_url).await?;
+    let versions: Vec<(i64, String)> =
+        sqlx::query_as("SELECT height, version FROM reload_view_blocks ORDER BY height")
+            .fetch_all(&dst_db)
+            .await?;
+    let expected: Vec<(i64, String)> = (1..=LAST_BLOCK)
+        .map(|height| {
+            let version = if height <= RELOAD_AFTER { "1" } else { "2" };
+            (i64::try_from(height).unwrap(), version.to_string())
+        })
+        .collect();
+    assert_eq!(versions, expected);
+
+    Ok(())
+}
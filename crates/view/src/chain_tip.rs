@@ -0,0 +1,297 @@
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use async_trait::async_trait;
+use futures::{stream::BoxStream, Stream, StreamExt};
+use penumbra_compact_block::CompactBlock;
+use penumbra_proto::core::component::compact_block::v1::{
+    query_service_client::QueryServiceClient as CompactBlockQueryServiceClient, CompactBlockRequest,
+};
+use tendermint_rpc::{event::EventData, query::EventType, SubscriptionClient, WebSocketClient};
+use tonic::transport::Channel;
+
+/// A source of the heights of newly committed blocks.
+#[async_trait]
+pub trait BlockHeightSubscription: Send {
+    /// Open a subscription to new block heights. The stream ends when the connection is lost.
+    async fn subscribe(&mut self) -> anyhow::Result<BoxStream<'static, anyhow::Result<u64>>>;
+}
+
+/// Subscribes to `NewBlock` events over a CometBFT node's websocket RPC.
+#[derive(Clone, Debug)]
+pub struct CometBftNewBlocks {
+    rpc_url: String,
+}
+
+impl CometBftNewBlocks {
+    /// Subscribe to the node whose websocket RPC is served at `rpc_url`, e.g.
+    /// `ws://localhost:26657/websocket`.
+    pub fn new(rpc_url: &str) -> Self {
+        Self {
+            rpc_url: rpc_url.to_owned(),
+        }
+    }
+}
+
+fn new_block_height(data: &EventData) -> Option<u64> {
+    match data {
+        EventData::NewBlock {
+            block: Some(block), ..
+        }
+        | EventData::LegacyNewBlock {
+            block: Some(block), ..
+        } => Some(block.header.height.value()),
+        _ => None,
+    }
+}
+
+#[async_trait]
+impl BlockHeightSubscription for CometBftNewBlocks {
+    async fn subscribe(&mut self) -> anyhow::Result<BoxStream<'static, anyhow::Result<u64>>> {
+        let (client, driver) = WebSocketClient::new(self.rpc_url.as_str())
+            .await
+            .with_context(|| format!("could not connect to cometbft at {}", self.rpc_url))?;
+        tokio::spawn(async move {
+            if let Err(e) = driver.run().await {
+                tracing::warn!(?e, "cometbft websocket connection closed");
+            }
+        });
+        let mut subscription = client
+            .subscribe(EventType::NewBlock.into())
+            .await
+            .context("could not subscribe to new blocks")?;
+
+        Ok(async_stream::stream! {
+            // The connection is closed when the client is dropped, so keep it alive for as long
+            // as the subscription is being read.
+            let _client = client;
+            while let Some(event) = subscription.next().await {
+                match event {
+                    Ok(event) => {
+                        if let Some(height) = new_block_height(&event.data) {
+                            yield Ok(height);
+                        }
+                    }
+                    Err(e) => {
+                        yield Err(anyhow::Error::from(e));
+                        break;
+                    }
+                }
+            }
+        }
+        .boxed())
+    }
+}
+
+/// A source of compact blocks by height.
+#[async_trait]
+pub trait CompactBlockSource: Send {
+    async fn fetch_compact_block(&mut self, height: u64) -> anyhow::Result<CompactBlock>;
+}
+
+#[async_trait]
+impl CompactBlockSource for CompactBlockQueryServiceClient<Channel> {
+    async fn fetch_compact_block(&mut self, height: u64) -> anyhow::Result<CompactBlock> {
+        self.compact_block(CompactBlockRequest { height })
+            .await?
+            .into_inner()
+            .compact_block
+            .ok_or_else(|| anyhow!("missing compact block at height {height}"))?
+            .try_into()
+    }
+}
+
+/// Follows the chain tip, yielding the compact block of every new block.
+///
+/// The subscriber learns about new blocks from a [`BlockHeightSubscription`], by default
+/// CometBFT's websocket `subscribe` endpoint, and fetches each compact block from pd. When the
+/// subscription drops, it reconnects with exponential backoff, and fills in any blocks committed
+/// while it was disconnected, so that blocks are always yielded in order and without gaps.
+pub struct ChainTipSubscriber<S = CometBftNewBlocks, B = CompactBlockQueryServiceClient<Channel>> {
+    heights: S,
+    blocks: B,
+    subscription: Option<BoxStream<'static, anyhow::Result<u64>>>,
+    /// The height of the next block to yield, or `None` to start at the next new block.
+    next_height: Option<u64>,
+    /// The highest block height the subscription has reported.
+    tip: Option<u64>,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl ChainTipSubscriber {
+    /// Follow the chain served by the CometBFT websocket RPC at `rpc_url`, fetching compact
+    /// blocks from pd with `compact_blocks`.
+    pub fn new(rpc_url: &str, compact_blocks: CompactBlockQueryServiceClient<Channel>) -> Self {
+        Self::with_sources(CometBftNewBlocks::new(rpc_url), compact_blocks)
+    }
+}
+
+impl<S: BlockHeightSubscription, B: CompactBlockSource> ChainTipSubscriber<S, B> {
+    pub fn with_sources(heights: S, blocks: B) -> Self {
+        Self {
+            heights,
+            blocks,
+            subscription: None,
+            next_height: None,
+            tip: None,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+        }
+    }
+
+    /// Start from the block at `height`, rather than from the next block to be committed.
+    pub fn starting_at(mut self, height: u64) -> Self {
+        self.next_height = Some(height);
+        self
+    }
+
+    /// Wait `initial` before the first reconnection attempt, doubling the wait on each further
+    /// failure, up to `max`.
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Wait for the subscription to report a new block height, reconnecting as needed.
+    async fn next_tip(&mut self) -> u64 {
+        let mut backoff = self.initial_backoff;
+        loop {
+            if self.subscription.is_none() {
+                match self.heights.subscribe().await {
+                    Ok(subscription) => self.subscription = Some(subscription),
+                    Err(e) => tracing::warn!(?e, ?backoff, "could not subscribe to new blocks"),
+                }
+            }
+            if let Some(subscription) = self.subscription.as_mut() {
+                match subscription.next().await {
+                    Some(Ok(height)) => return height,
+                    Some(Err(e)) => tracing::warn!(?e, "new block subscription failed"),
+                    None => tracing::info!("new block subscription ended"),
+                }
+                self.subscription = None;
+            }
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(self.max_backoff);
+        }
+    }
+
+    /// Wait for the next block and fetch its compact block.
+    ///
+    /// If fetching the compact block fails, the error is returned and the same block is retried
+    /// on the next call.
+    pub async fn next_block(&mut self) -> anyhow::Result<CompactBlock> {
+        loop {
+            if let (Some(next), Some(tip)) = (self.next_height, self.tip) {
+                if next <= tip {
+                    let block = self.blocks.fetch_compact_block(next).await?;
+                    self.next_height = Some(next + 1);
+                    return Ok(block);
+                }
+            }
+            let tip = self.next_tip().await;
+            self.tip = Some(self.tip.map_or(tip, |known| known.max(tip)));
+            self.next_height.get_or_insert(tip);
+        }
+    }
+
+    /// Turn the subscriber into a stream of every new compact block.
+    pub fn subscribe_stream(mut self) -> impl Stream<Item = anyhow::Result<CompactBlock>> {
+        async_stream::stream! {
+            loop {
+                yield self.next_block().await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, VecDeque};
+
+    use futures::stream;
+
+    use super::*;
+
+    /// A mock node, whose subscription connections each report some heights before dropping.
+    ///
+    /// A `None` connection fails to connect at all, and once every connection has been used,
+    /// further subscriptions never report anything.
+    struct MockNode {
+        connections: VecDeque<Option<Vec<u64>>>,
+    }
+
+    #[async_trait]
+    impl BlockHeightSubscription for MockNode {
+        async fn subscribe(&mut self) -> anyhow::Result<BoxStream<'static, anyhow::Result<u64>>> {
+            match self.connections.pop_front() {
+                Some(Some(heights)) => Ok(stream::iter(heights.into_iter().map(Ok)).boxed()),
+                Some(None) => Err(anyhow!("connection refused")),
+                None => Ok(stream::pending().boxed()),
+            }
+        }
+    }
+
+    struct MockBlocks(BTreeMap<u64, CompactBlock>);
+
+    #[async_trait]
+    impl CompactBlockSource for MockBlocks {
+        async fn fetch_compact_block(&mut self, height: u64) -> anyhow::Result<CompactBlock> {
+            self.0
+                .get(&height)
+                .cloned()
+                .ok_or_else(|| anyhow!("no block at height {height}"))
+        }
+    }
+
+    fn subscriber(connections: Vec<Option<Vec<u64>>>) -> ChainTipSubscriber<MockNode, MockBlocks> {
+        let blocks = (1..=5)
+            .map(|height| {
+                (
+                    height,
+                    CompactBlock {
+                        height,
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect();
+        ChainTipSubscriber::with_sources(
+            MockNode {
+                connections: connections.into(),
+            },
+            MockBlocks(blocks),
+        )
+        .with_backoff(Duration::from_millis(1), Duration::from_millis(4))
+    }
+
+    async fn heights(subscriber: ChainTipSubscriber<MockNode, MockBlocks>) -> Vec<u64> {
+        subscriber
+            .subscribe_stream()
+            .take(5)
+            .map(|block| block.unwrap().height)
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn every_published_block_is_received() {
+        let subscriber = subscriber(vec![Some(vec![1, 2, 3, 4, 5])]);
+        assert_eq!(heights(subscriber).await, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[tokio::test]
+    async fn blocks_missed_while_reconnecting_are_filled_in() {
+        // The first connection drops after block 2, the node refuses the next attempt, and by
+        // the time we reconnect, block 3 has already been committed.
+        let subscriber = subscriber(vec![Some(vec![1, 2]), None, Some(vec![4, 5])]);
+        assert_eq!(heights(subscriber).await, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[tokio::test]
+    async fn a_start_height_is_caught_up_from() {
+        let subscriber = subscriber(vec![Some(vec![3]), Some(vec![5])]).starting_at(1);
+        assert_eq!(heights(subscriber).await, vec![1, 2, 3, 4, 5]);
+    }
+}
@@ -0,0 +1,159 @@
+use penumbra_sct::Nullifier;
+use penumbra_transaction::Transaction;
+
+/// Why a transaction failed a dry run.
+#[derive(Debug, thiserror::Error)]
+pub enum DryRunError {
+    #[error(
+        "transaction is for chain {transaction:?}, but the view service is synced to {chain:?}"
+    )]
+    WrongChain { transaction: String, chain: String },
+    #[error("transaction expired at height {expiry_height}, and the view service is synced to height {sync_height}")]
+    Expired {
+        expiry_height: u64,
+        sync_height: u64,
+    },
+    #[error("the note with nullifier {0} has already been spent")]
+    NoteAlreadySpent(Nullifier),
+    #[error("transaction failed stateless checks: {0:#}")]
+    Stateless(anyhow::Error),
+}
+
+/// What the view service knows of the chain's state, for dry-running transactions against.
+///
+/// The view service can't execute transactions, since it only has the state of the wallet's own
+/// notes, so a dry run only checks what can be checked from here.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DryRunSnapshot {
+    /// The height the view service had synced to.
+    pub sync_height: u64,
+    pub chain_id: String,
+}
+
+impl DryRunSnapshot {
+    /// Check `transaction`'s parameters against the snapshot, and that it spends no note for which
+    /// `is_spent` is true.
+    pub fn check(
+        &self,
+        transaction: &Transaction,
+        is_spent: impl Fn(&Nullifier) -> bool,
+    ) -> Result<(), DryRunError> {
+        let parameters = transaction.transaction_parameters();
+        if parameters.chain_id != self.chain_id {
+            return Err(DryRunError::WrongChain {
+                transaction: parameters.chain_id,
+                chain: self.chain_id.clone(),
+            });
+        }
+
+        // The earliest the transaction could be included is the block after the sync height, and
+        // a zero expiry height means it never expires.
+        if parameters.expiry_height != 0 && parameters.expiry_height <= self.sync_height {
+            return Err(DryRunError::Expired {
+                expiry_height: parameters.expiry_height,
+                sync_height: self.sync_height,
+            });
+        }
+
+        match transaction
+            .spent_nullifiers()
+            .find(|nullifier| is_spent(nullifier))
+        {
+            Some(nullifier) => Err(DryRunError::NoteAlreadySpent(nullifier)),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use decaf377::{Element, Fq};
+    use penumbra_asset::balance;
+    use penumbra_proto::core::component::shielded_pool::v1 as pb;
+    use penumbra_shielded_pool::{spend, Spend, SpendProof};
+    use penumbra_transaction::{Action, TransactionParameters};
+
+    use super::*;
+
+    fn snapshot() -> DryRunSnapshot {
+        DryRunSnapshot {
+            sync_height: 100,
+            chain_id: "penumbra-testnet".to_string(),
+        }
+    }
+
+    /// A spend of the note with `nullifier`.
+    ///
+    /// Its proof and signature are placeholders, since the snapshot doesn't check them.
+    fn spend_action(nullifier: Nullifier) -> Action {
+        let point = Element::GENERATOR.vartime_compress().0;
+        Action::Spend(Spend {
+            body: spend::Body {
+                balance_commitment: balance::Commitment(Element::GENERATOR),
+                nullifier,
+                rk: point.try_into().unwrap(),
+            },
+            auth_sig: [0u8; 64].into(),
+            proof: SpendProof::try_from(pb::ZkSpendProof {
+                inner: vec![0; 192],
+            })
+            .unwrap(),
+        })
+    }
+
+    fn transaction(expiry_height: u64, nullifiers: &[Nullifier]) -> Transaction {
+        let mut transaction = Transaction::default();
+        transaction.transaction_body.actions =
+            nullifiers.iter().copied().map(spend_action).collect();
+        transaction.transaction_body.transaction_parameters = TransactionParameters {
+            expiry_height,
+            chain_id: "penumbra-testnet".to_string(),
+            ..Default::default()
+        };
+        transaction
+    }
+
+    #[test]
+    fn unspent_notes_pass() {
+        let spent = [Nullifier(Fq::from(1u64))];
+        let tx = transaction(0, &[Nullifier(Fq::from(2u64)), Nullifier(Fq::from(3u64))]);
+        snapshot().check(&tx, |nf| spent.contains(nf)).unwrap();
+
+        // The transaction could still be included in the block after the sync height.
+        let tx = transaction(101, &[Nullifier(Fq::from(2u64))]);
+        snapshot().check(&tx, |nf| spent.contains(nf)).unwrap();
+    }
+
+    #[test]
+    fn double_spends_fail() {
+        let spent = [Nullifier(Fq::from(2u64))];
+        let tx = transaction(0, &[Nullifier(Fq::from(1u64)), Nullifier(Fq::from(2u64))]);
+        let error = snapshot().check(&tx, |nf| spent.contains(nf)).unwrap_err();
+        assert!(
+            matches!(error, DryRunError::NoteAlreadySpent(nf) if nf == spent[0]),
+            "unexpected error {error}"
+        );
+    }
+
+    #[test]
+    fn expired_transactions_fail() {
+        let tx = transaction(100, &[]);
+        assert!(matches!(
+            snapshot().check(&tx, |_| false),
+            Err(DryRunError::Expired {
+                expiry_height: 100,
+                sync_height: 100
+            })
+        ));
+    }
+
+    #[test]
+    fn transactions_for_other_chains_fail() {
+        let mut tx = transaction(0, &[]);
+        tx.transaction_body.transaction_parameters.chain_id = "penumbra-1".to_string();
+        assert!(matches!(
+            snapshot().check(&tx, |_| false),
+            Err(DryRunError::WrongChain { .. })
+        ));
+    }
+}
@@ -13,10 +13,15 @@
 #![recursion_limit = "512"]
 // Requires nightly.
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
+mod chain_tip;
 mod client;
+mod dry_run;
 mod metrics;
+mod node_client;
 mod note_record;
 mod planner;
+mod query_cache;
+mod scan_budget;
 mod service;
 mod status;
 mod storage;
@@ -25,10 +30,16 @@ mod sync;
 mod transaction_info;
 mod worker;
 
+pub use crate::chain_tip::{
+    BlockHeightSubscription, ChainTipSubscriber, CometBftNewBlocks, CompactBlockSource,
+};
 pub use crate::client::ViewClient;
 pub use crate::metrics::register_metrics;
+pub use crate::node_client::{GenesisHashMismatch, NodeClient};
 pub use crate::note_record::SpendableNoteRecord;
 pub use crate::planner::Planner;
+pub use crate::query_cache::QueryCache;
+pub use crate::scan_budget::{ScanBudget, ScanScheduler};
 pub use crate::service::ViewServer;
 pub use crate::status::StatusStreamResponse;
 pub use crate::storage::Storage;
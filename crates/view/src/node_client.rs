@@ -0,0 +1,120 @@
+use anyhow::Context;
+use penumbra_app::genesis::{AppState, GenesisHash};
+use tendermint_rpc::{Client, HttpClient};
+use url::Url;
+
+/// The genesis state served by a node did not match the one the client expected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("genesis hash mismatch: expected {expected}, node serves {actual}")]
+pub struct GenesisHashMismatch {
+    pub expected: GenesisHash,
+    pub actual: GenesisHash,
+}
+
+/// A client for checking the identity of the chain served by a node.
+///
+/// This talks to the node's CometBFT RPC endpoint, which serves the genesis
+/// file the chain was started from.
+#[derive(Clone, Debug)]
+pub struct NodeClient {
+    cometbft_url: Url,
+    expected_genesis: Option<GenesisHash>,
+}
+
+impl NodeClient {
+    /// Creates a client for the node whose CometBFT RPC is served at `cometbft_url`.
+    pub fn new(cometbft_url: Url) -> Self {
+        Self {
+            cometbft_url,
+            expected_genesis: None,
+        }
+    }
+
+    /// Pins the genesis hash that [`NodeClient::verify_expected_genesis`] checks against.
+    pub fn with_expected_genesis(mut self, hash: GenesisHash) -> Self {
+        self.expected_genesis = Some(hash);
+        self
+    }
+
+    /// Fetches the genesis state from the node and computes its [`GenesisHash`].
+    pub async fn genesis_hash(&self) -> anyhow::Result<GenesisHash> {
+        let client = HttpClient::new(self.cometbft_url.as_str())
+            .context("could not create cometbft rpc client")?;
+        let genesis = client
+            .genesis::<AppState>()
+            .await
+            .with_context(|| format!("could not fetch genesis from {}", self.cometbft_url))?;
+        Ok(GenesisHash::compute(&genesis.app_state))
+    }
+
+    /// Checks that the node serves the chain identified by `expected`.
+    ///
+    /// Returns a [`GenesisHashMismatch`] error if the node serves a different genesis.
+    pub async fn verify_genesis(&self, expected: &GenesisHash) -> anyhow::Result<()> {
+        let actual = self.genesis_hash().await?;
+        check_genesis_hash(expected, &actual)?;
+        Ok(())
+    }
+
+    /// Checks the node against the hash pinned with [`NodeClient::with_expected_genesis`],
+    /// if any.
+    pub async fn verify_expected_genesis(&self) -> anyhow::Result<()> {
+        match &self.expected_genesis {
+            Some(expected) => self.verify_genesis(expected).await,
+            None => Ok(()),
+        }
+    }
+}
+
+fn check_genesis_hash(
+    expected: &GenesisHash,
+    actual: &GenesisHash,
+) -> Result<(), GenesisHashMismatch> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(GenesisHashMismatch {
+            expected: *expected,
+            actual: *actual,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use penumbra_app::genesis::Content;
+
+    use super::*;
+
+    fn genesis_hash_for(chain_id: &str) -> GenesisHash {
+        GenesisHash::compute(&AppState::Content(
+            Content::default().with_chain_id(chain_id.to_owned()),
+        ))
+    }
+
+    #[test]
+    fn matching_genesis_hash_is_accepted() {
+        let expected = genesis_hash_for("penumbra-1");
+        let actual = genesis_hash_for("penumbra-1");
+        assert!(check_genesis_hash(&expected, &actual).is_ok());
+    }
+
+    #[test]
+    fn mismatching_genesis_hash_is_rejected() {
+        let expected = genesis_hash_for("penumbra-1");
+        let actual = genesis_hash_for("penumbra-2");
+        assert_eq!(
+            check_genesis_hash(&expected, &actual),
+            Err(GenesisHashMismatch { expected, actual })
+        );
+    }
+
+    #[tokio::test]
+    async fn unreachable_node_is_an_error() {
+        // Nothing listens on port 1, so the genesis fetch fails before any comparison.
+        let client = NodeClient::new("http://127.0.0.1:1".parse().unwrap())
+            .with_expected_genesis(genesis_hash_for("penumbra-1"));
+        let error = client.verify_expected_genesis().await.unwrap_err();
+        assert!(error.downcast_ref::<GenesisHashMismatch>().is_none());
+    }
+}
@@ -0,0 +1,184 @@
+use std::{
+    future::Future,
+    hash::Hash,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use parking_lot::Mutex;
+use penumbra_tct::lru::LruCache;
+
+/// The default number of entries retained by a [`QueryCache`].
+const DEFAULT_CAPACITY: usize = 128;
+
+/// A small time-bounded cache for responses served by the view service.
+///
+/// Each entry is stamped with the time it was fetched, and is considered fresh
+/// for `ttl`. Stale entries are refetched on the next lookup. A `ttl` of zero
+/// disables caching entirely, so every lookup goes to the underlying storage.
+///
+/// Once the cache is full, inserting a new entry evicts the least recently used one.
+///
+/// The [`QueryCache`] can be cloned; each clone shares the same entries.
+#[derive(Clone)]
+pub struct QueryCache<K: Hash + Eq + Clone, V: Clone> {
+    cache: Arc<Mutex<LruCache<K, (V, Instant)>>>,
+    ttl: Duration,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> QueryCache<K, V> {
+    /// Creates a new cache whose entries remain fresh for `ttl`.
+    pub fn new(ttl: Duration) -> Self {
+        Self::with_capacity(ttl, DEFAULT_CAPACITY)
+    }
+
+    /// Creates a new cache whose entries remain fresh for `ttl`, retaining at
+    /// most `capacity` entries.
+    pub fn with_capacity(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            cache: Arc::new(Mutex::new(LruCache::new(capacity.max(1)))),
+            ttl,
+        }
+    }
+
+    /// Returns the time-to-live for entries in this cache.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Returns the cached value for `key` if it is still fresh, otherwise
+    /// awaits `fetch` and caches its result.
+    ///
+    /// Errors returned by `fetch` are passed through and are not cached.
+    pub async fn get_or_fetch<E>(
+        &self,
+        key: K,
+        fetch: impl Future<Output = Result<V, E>>,
+    ) -> Result<V, E> {
+        if let Some(value) = self.get(&key) {
+            return Ok(value);
+        }
+
+        // We don't hold the lock across the fetch, so concurrent misses may both
+        // go to storage; the last writer wins, which is fine for idempotent reads.
+        let value = fetch.await?;
+        if !self.ttl.is_zero() {
+            self.cache
+                .lock()
+                .insert(key, (value.clone(), Instant::now()));
+        }
+
+        Ok(value)
+    }
+
+    /// Drops every entry in the cache.
+    pub fn clear(&self) {
+        self.cache.lock().clear();
+    }
+
+    fn get(&self, key: &K) -> Option<V> {
+        let mut cache = self.cache.lock();
+        match cache.get_mut(key) {
+            Some((value, fetched_at)) if fetched_at.elapsed() < self.ttl => {
+                tracing::trace!("query cache hit");
+                Some(value.clone())
+            }
+            Some(_) => {
+                tracing::trace!("query cache entry is stale");
+                cache.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> std::fmt::Debug for QueryCache<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QueryCache")
+            .field("len", &self.cache.lock().len())
+            .field("ttl", &self.ttl)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn fresh_entries_are_served_from_cache() {
+        let cache = QueryCache::<(), usize>::new(Duration::from_secs(60));
+        let fetches = AtomicUsize::new(0);
+
+        for _ in 0..3 {
+            let value = cache
+                .get_or_fetch((), async {
+                    Ok::<_, anyhow::Error>(fetches.fetch_add(1, Ordering::SeqCst))
+                })
+                .await
+                .unwrap();
+            assert_eq!(value, 0);
+        }
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn stale_entries_are_refreshed() {
+        let cache = QueryCache::<(), usize>::new(Duration::from_millis(20));
+        let fetches = AtomicUsize::new(0);
+        let fetch = || async { Ok::<_, anyhow::Error>(fetches.fetch_add(1, Ordering::SeqCst)) };
+
+        assert_eq!(cache.get_or_fetch((), fetch()).await.unwrap(), 0);
+        assert_eq!(cache.get_or_fetch((), fetch()).await.unwrap(), 0);
+
+        tokio::time::sleep(Duration::from_millis(40)).await;
+
+        assert_eq!(cache.get_or_fetch((), fetch()).await.unwrap(), 1);
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn zero_ttl_disables_caching() {
+        let cache = QueryCache::<(), usize>::new(Duration::ZERO);
+        let fetches = AtomicUsize::new(0);
+        let fetch = || async { Ok::<_, anyhow::Error>(fetches.fetch_add(1, Ordering::SeqCst)) };
+
+        assert_eq!(cache.get_or_fetch((), fetch()).await.unwrap(), 0);
+        assert_eq!(cache.get_or_fetch((), fetch()).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn least_recently_used_entries_are_evicted() {
+        let cache = QueryCache::<u8, usize>::with_capacity(Duration::from_secs(60), 2);
+        let fetches = AtomicUsize::new(0);
+        let fetch = || async { Ok::<_, anyhow::Error>(fetches.fetch_add(1, Ordering::SeqCst)) };
+
+        assert_eq!(cache.get_or_fetch(1, fetch()).await.unwrap(), 0);
+        assert_eq!(cache.get_or_fetch(2, fetch()).await.unwrap(), 1);
+        // Using 1 again makes 2 the least recently used entry, so it's the one evicted for 3.
+        assert_eq!(cache.get_or_fetch(1, fetch()).await.unwrap(), 0);
+        assert_eq!(cache.get_or_fetch(3, fetch()).await.unwrap(), 2);
+
+        assert_eq!(cache.get_or_fetch(1, fetch()).await.unwrap(), 0);
+        assert_eq!(cache.get_or_fetch(2, fetch()).await.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn errors_are_not_cached() {
+        let cache = QueryCache::<(), usize>::new(Duration::from_secs(60));
+
+        let result = cache
+            .get_or_fetch((), async { Err(anyhow::anyhow!("storage unavailable")) })
+            .await;
+        assert!(result.is_err());
+
+        let value = cache
+            .get_or_fetch((), async { Ok::<_, anyhow::Error>(7) })
+            .await
+            .unwrap();
+        assert_eq!(value, 7);
+    }
+}
@@ -0,0 +1,224 @@
+use std::time::{Duration, Instant};
+
+use penumbra_compact_block::{CompactBlock, StatePayload};
+
+/// Limits on how much trial decryption a wallet will do, so that a flood of payloads can't
+/// monopolize it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScanBudget {
+    /// The number of trial decryptions an ordinary block is expected to need. Blocks needing more
+    /// are charged as several blocks.
+    pub max_decrypt_attempts_per_block: usize,
+    /// The sustained rate at which blocks may be scanned.
+    pub max_blocks_per_second: f64,
+}
+
+impl ScanBudget {
+    /// Check that the budget allows scanning to make progress: the block rate must be finite and
+    /// positive.
+    pub fn check(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.max_blocks_per_second.is_finite() && self.max_blocks_per_second > 0.0,
+            "scan budget must allow a finite, positive number of blocks per second, not {}",
+            self.max_blocks_per_second
+        );
+        Ok(())
+    }
+}
+
+/// Schedules block scanning within a [`ScanBudget`], using a token bucket.
+///
+/// The bucket holds up to a second's worth of blocks, and scanning a block spends one token for
+/// every `max_decrypt_attempts_per_block` trial decryptions it needed. Oversized blocks can put
+/// the bucket into debt, which is paid off by waiting before the next block.
+#[derive(Clone, Debug)]
+pub struct ScanScheduler {
+    budget: ScanBudget,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl ScanScheduler {
+    /// Create a scheduler for `budget`, failing if the budget fails [`ScanBudget::check`].
+    pub fn new(budget: ScanBudget) -> anyhow::Result<Self> {
+        Self::new_at(budget, Instant::now())
+    }
+
+    fn new_at(budget: ScanBudget, now: Instant) -> anyhow::Result<Self> {
+        budget.check()?;
+        let mut scheduler = Self {
+            budget,
+            tokens: 0.0,
+            last_refill: now,
+        };
+        scheduler.tokens = scheduler.capacity();
+        Ok(scheduler)
+    }
+
+    fn capacity(&self) -> f64 {
+        self.budget.max_blocks_per_second.max(1.0)
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens =
+            (self.tokens + elapsed * self.budget.max_blocks_per_second).min(self.capacity());
+        self.last_refill = now;
+    }
+
+    /// Whether the block at `height` may be scanned now.
+    pub fn can_process_block(&mut self, height: u64) -> bool {
+        self.can_process_block_at(height, Instant::now())
+    }
+
+    fn can_process_block_at(&mut self, height: u64, now: Instant) -> bool {
+        self.refill(now);
+        let allowed = self.tokens >= 1.0;
+        if !allowed {
+            tracing::debug!(height, tokens = self.tokens, "scan budget exhausted");
+        }
+        allowed
+    }
+
+    /// Wait until the budget allows `block` to be scanned, then charge the budget for it.
+    pub async fn wait_to_scan(&mut self, block: &CompactBlock) {
+        while !self.can_process_block(block.height) {
+            tokio::time::sleep(self.backoff()).await;
+        }
+        self.charge(decrypt_attempts(block));
+    }
+
+    /// Charge the budget for scanning a block that needed `decrypt_attempts` trial decryptions.
+    fn charge(&mut self, decrypt_attempts: usize) {
+        let per_block = self.budget.max_decrypt_attempts_per_block.max(1);
+        let blocks = decrypt_attempts.div_ceil(per_block).max(1);
+        self.tokens -= blocks as f64;
+    }
+
+    /// How long to wait before the budget allows another block to be scanned.
+    pub fn backoff(&mut self) -> Duration {
+        self.backoff_at(Instant::now())
+    }
+
+    fn backoff_at(&mut self, now: Instant) -> Duration {
+        self.refill(now);
+        if self.tokens >= 1.0 {
+            return Duration::ZERO;
+        }
+        Duration::from_secs_f64((1.0 - self.tokens) / self.budget.max_blocks_per_second)
+    }
+}
+
+/// The number of trial decryptions scanning `block` needs.
+pub(crate) fn decrypt_attempts(block: &CompactBlock) -> usize {
+    block
+        .state_payloads
+        .iter()
+        .filter(|payload| !matches!(payload, StatePayload::RolledUp { .. }))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use penumbra_asset::{Value, STAKING_TOKEN_ASSET_ID};
+    use penumbra_keys::keys::{Bip44Path, SeedPhrase, SpendKey};
+    use penumbra_sct::CommitmentSource;
+    use penumbra_shielded_pool::Note;
+    use rand_core::OsRng;
+
+    use super::*;
+
+    const BUDGET: ScanBudget = ScanBudget {
+        max_decrypt_attempts_per_block: 1_000,
+        max_blocks_per_second: 10.0,
+    };
+
+    #[test]
+    fn oversized_blocks_trigger_rate_limiting_until_the_backoff_passes() {
+        let start = Instant::now();
+        let mut scheduler = ScanScheduler::new_at(BUDGET, start).unwrap();
+
+        // A block of 10,000 clues costs as much as ten ordinary blocks, draining the bucket.
+        assert!(scheduler.can_process_block_at(1, start));
+        scheduler.charge(10_000);
+        assert!(!scheduler.can_process_block_at(2, start));
+
+        let backoff = scheduler.backoff_at(start);
+        assert!((backoff.as_secs_f64() - 0.1).abs() < 1e-9);
+        assert!(!scheduler.can_process_block_at(2, start + Duration::from_millis(50)));
+
+        // Once the backoff has passed, scanning recovers.
+        let later = start + Duration::from_millis(101);
+        assert!(scheduler.can_process_block_at(2, later));
+        assert_eq!(scheduler.backoff_at(later), Duration::ZERO);
+    }
+
+    #[test]
+    fn ordinary_blocks_are_limited_to_the_block_rate() {
+        let start = Instant::now();
+        let mut scheduler = ScanScheduler::new_at(BUDGET, start).unwrap();
+        for height in 0..10 {
+            assert!(scheduler.can_process_block_at(height, start));
+            scheduler.charge(10);
+        }
+        assert!(!scheduler.can_process_block_at(10, start));
+        assert!(scheduler.can_process_block_at(10, start + Duration::from_millis(101)));
+    }
+
+    #[test]
+    fn blocks_are_charged_for_their_trial_decryptions() {
+        let fvk = SpendKey::from_seed_phrase_bip44(SeedPhrase::generate(OsRng), &Bip44Path::new(0))
+            .full_viewing_key()
+            .clone();
+        let (address, _) = fvk.incoming().payment_address(0u32.into());
+        let value = Value {
+            amount: 1u64.into(),
+            asset_id: *STAKING_TOKEN_ASSET_ID,
+        };
+        let note = || StatePayload::Note {
+            source: CommitmentSource::transaction(),
+            note: Box::new(Note::generate(&mut OsRng, &address, value).payload()),
+        };
+        let rolled_up = || StatePayload::RolledUp {
+            source: CommitmentSource::transaction(),
+            commitment: Note::generate(&mut OsRng, &address, value).commit(),
+        };
+        // Rolled-up commitments don't need trial decryption, so they're free.
+        let block = CompactBlock {
+            height: 1,
+            state_payloads: (0..20).map(|_| note()).chain([rolled_up()]).collect(),
+            ..Default::default()
+        };
+        assert_eq!(decrypt_attempts(&block), 20);
+
+        // Twenty decryptions at two per block costs ten blocks, a whole second's budget.
+        let start = Instant::now();
+        let mut scheduler = ScanScheduler::new_at(
+            ScanBudget {
+                max_decrypt_attempts_per_block: 2,
+                max_blocks_per_second: 10.0,
+            },
+            start,
+        )
+        .unwrap();
+        assert!(scheduler.can_process_block_at(1, start));
+        scheduler.charge(decrypt_attempts(&block));
+        assert!(!scheduler.can_process_block_at(2, start));
+        assert!((scheduler.backoff_at(start).as_secs_f64() - 0.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn budgets_that_never_refill_are_rejected() {
+        for max_blocks_per_second in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let budget = ScanBudget {
+                max_blocks_per_second,
+                ..BUDGET
+            };
+            assert!(budget.check().is_err(), "{max_blocks_per_second}");
+            assert!(ScanScheduler::new(budget).is_err());
+        }
+        assert!(BUDGET.check().is_ok());
+    }
+}
@@ -2,6 +2,7 @@ use std::{
     collections::{BTreeMap, BTreeSet},
     pin::Pin,
     sync::{Arc, Mutex},
+    time::Duration,
 };
 
 use anyhow::{anyhow, Context};
@@ -21,6 +22,7 @@ use tonic::{async_trait, transport::Channel, Request, Response, Status};
 use tracing::{instrument, Instrument};
 use url::Url;
 
+use penumbra_app::params::AppParameters;
 use penumbra_asset::{asset, asset::Metadata, Value};
 use penumbra_dex::{
     lp::{
@@ -60,7 +62,12 @@ use penumbra_transaction::{
     AuthorizationData, Transaction, TransactionPerspective, TransactionPlan, WitnessData,
 };
 
-use crate::{worker::Worker, Planner, Storage};
+use crate::{
+    dry_run::{DryRunError, DryRunSnapshot},
+    query_cache::QueryCache,
+    worker::Worker,
+    Planner, ScanBudget, Storage,
+};
 
 /// A [`futures::Stream`] of broadcast transaction responses.
 ///
@@ -89,6 +96,15 @@ pub struct ViewServer {
     node: Url,
     /// Used to watch for changes to the sync height.
     sync_height_rx: watch::Receiver<u64>,
+    /// Cached results for the `assets` query, keyed by the pattern of denoms asked for, or `None`
+    /// for every asset.
+    assets_cache: QueryCache<Option<String>, Vec<Metadata>>,
+    /// Cached results for the `app_parameters` query.
+    app_params_cache: QueryCache<(), AppParameters>,
+    /// The cached chain state that `dry_run_transaction` checks against.
+    dry_run_snapshot_cache: QueryCache<(), DryRunSnapshot>,
+    /// Used to change the worker's scan budget.
+    scan_budget_tx: Arc<watch::Sender<Option<ScanBudget>>>,
 }
 
 impl ViewServer {
@@ -131,8 +147,9 @@ impl ViewServer {
             .with_context(|| "could not connect to grpc server")
             .tap_err(|error| tracing::error!(?error, "could not connect to grpc server"))?;
 
+        let (scan_budget_tx, scan_budget_rx) = watch::channel(None);
         let (worker, state_commitment_tree, error_slot, sync_height_rx) =
-            Worker::new(storage.clone(), channel)
+            Worker::new(storage.clone(), channel, scan_budget_rx)
                 .instrument(span.clone())
                 .tap(|_| tracing::trace!("constructing view server worker"))
                 .await?
@@ -147,9 +164,46 @@ impl ViewServer {
             sync_height_rx,
             state_commitment_tree,
             node,
+            assets_cache: QueryCache::new(Duration::ZERO),
+            app_params_cache: QueryCache::new(Duration::ZERO),
+            dry_run_snapshot_cache: QueryCache::new(Duration::ZERO),
+            scan_budget_tx: Arc::new(scan_budget_tx),
         })
     }
 
+    /// Caches the results of frequently-repeated queries for `ttl`.
+    ///
+    /// This applies to the `assets` and `app_parameters` endpoints, which would otherwise
+    /// hit the database on every request; `app_parameters` is how the view service serves the
+    /// chain parameters. The view service doesn't list trading pairs, so there's nothing to
+    /// cache for them. By default, a zero TTL is used, which disables caching.
+    pub fn with_query_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.assets_cache = QueryCache::new(ttl);
+        self.app_params_cache = QueryCache::new(ttl);
+        self
+    }
+
+    /// Reuses the chain state that `dry_run_transaction` checks against for `ttl`, rather than
+    /// reading it from the database on every call.
+    ///
+    /// Within the TTL, dry runs may check expiry heights against a sync height up to `ttl` old.
+    /// By default, a zero TTL is used, which disables caching.
+    pub fn with_dry_run_snapshot_ttl(mut self, ttl: Duration) -> Self {
+        self.dry_run_snapshot_cache = QueryCache::new(ttl);
+        self
+    }
+
+    /// Limits how fast the worker trial-decrypts blocks, so that a flood of payloads can't
+    /// monopolize the machine.
+    ///
+    /// This applies to every clone of the view server, since they share a worker. By default,
+    /// there's no limit. Fails if the budget fails [`ScanBudget::check`].
+    pub fn with_scan_budget(self, budget: ScanBudget) -> anyhow::Result<Self> {
+        budget.check()?;
+        self.scan_budget_tx.send_replace(Some(budget));
+        Ok(self)
+    }
+
     /// Checks if the view server worker has encountered an error.
     ///
     /// This function returns a gRPC [`tonic::Status`] containing the view server worker error if
@@ -292,6 +346,52 @@ impl ViewServer {
             }.boxed()
     }
 
+    /// Check `transaction` as far as the view service can without broadcasting it.
+    ///
+    /// Errors in the check itself are returned as a [`tonic::Status`], and failures of the
+    /// transaction as a [`DryRunError`].
+    #[instrument(skip(self, transaction), fields(id = %transaction.id()))]
+    async fn dry_run(
+        &self,
+        transaction: &Transaction,
+    ) -> Result<Result<(), DryRunError>, tonic::Status> {
+        use penumbra_app::AppActionHandler;
+
+        let snapshot = self
+            .dry_run_snapshot_cache
+            .get_or_fetch((), self.dry_run_snapshot())
+            .await
+            .map_err(|e| tonic::Status::unavailable(format!("error getting chain state: {e:#}")))?;
+
+        let mut spent = Vec::new();
+        for nullifier in transaction.spent_nullifiers() {
+            if self
+                .storage
+                .nullifier_status(nullifier, false)
+                .await
+                .map_err(|e| tonic::Status::internal(format!("error querying storage: {e:#}")))?
+            {
+                spent.push(nullifier);
+            }
+        }
+        if let Err(error) = snapshot.check(transaction, |nullifier| spent.contains(nullifier)) {
+            return Ok(Err(error));
+        }
+
+        // Check proofs and signatures last, since they're the most expensive to check.
+        Ok(transaction
+            .check_stateless(())
+            .await
+            .map_err(DryRunError::Stateless))
+    }
+
+    async fn dry_run_snapshot(&self) -> anyhow::Result<DryRunSnapshot> {
+        Ok(DryRunSnapshot {
+            sync_height: self.storage.last_sync_height().await?.unwrap_or(0),
+            chain_id: self.storage.app_params().await?.chain_id,
+        })
+    }
+
     #[instrument(level = "trace", skip(self))]
     async fn tendermint_proxy_client(
         &self,
@@ -509,6 +609,31 @@ impl ViewService for ViewServer {
         Ok(tonic::Response::new(stream))
     }
 
+    #[instrument(skip_all, level = "trace")]
+    async fn dry_run_transaction(
+        &self,
+        request: tonic::Request<pb::DryRunTransactionRequest>,
+    ) -> Result<tonic::Response<pb::DryRunTransactionResponse>, tonic::Status> {
+        use penumbra_transaction::gas::GasCost as _;
+
+        self.check_worker().await?;
+
+        let transaction = Transaction::decode(request.into_inner().tx_bytes.as_slice())
+            .map_err(|e| e.context("could not decode transaction"))
+            .map_err(|e| tonic::Status::invalid_argument(format!("{:#}", e)))?;
+
+        let result = self.dry_run(&transaction).await?;
+        if let Err(error) = &result {
+            tracing::debug!(%error, "transaction failed dry run");
+        }
+
+        Ok(tonic::Response::new(pb::DryRunTransactionResponse {
+            success: result.is_ok(),
+            gas_used: Some(transaction.gas_cost().into()),
+            error_message: result.err().map(|e| e.to_string()).unwrap_or_default(),
+        }))
+    }
+
     #[instrument(skip_all, level = "trace")]
     async fn transaction_planner(
         &self,
@@ -1397,8 +1522,8 @@ impl ViewService for ViewServer {
 
         // Fetch assets from storage.
         let assets = if !filtered {
-            self.storage
-                .all_assets()
+            self.assets_cache
+                .get_or_fetch(None, self.storage.all_assets())
                 .await
                 .map_err(|e| tonic::Status::unavailable(format!("error fetching assets: {e}")))?
         } else {
@@ -1417,8 +1542,11 @@ impl ViewService for ViewServer {
             ] {
                 if *include {
                     assets.extend(
-                        self.storage
-                            .assets_matching(pattern.to_string())
+                        self.assets_cache
+                            .get_or_fetch(
+                                Some(pattern.to_string()),
+                                self.storage.assets_matching(pattern.to_string()),
+                            )
                             .await
                             .map_err(|e| {
                                 tonic::Status::unavailable(format!("error fetching assets: {e}"))
@@ -1650,10 +1778,11 @@ impl ViewService for ViewServer {
     ) -> Result<tonic::Response<pb::AppParametersResponse>, tonic::Status> {
         self.check_worker().await?;
 
-        let parameters =
-            self.storage.app_params().await.map_err(|e| {
-                tonic::Status::unavailable(format!("error getting app params: {e}"))
-            })?;
+        let parameters = self
+            .app_params_cache
+            .get_or_fetch((), self.storage.app_params())
+            .await
+            .map_err(|e| tonic::Status::unavailable(format!("error getting app params: {e}")))?;
 
         let response = AppParametersResponse {
             parameters: Some(parameters.into()),
@@ -34,7 +34,7 @@ use tracing::instrument;
 
 use crate::{
     sync::{scan_block, FilteredBlock},
-    Storage,
+    ScanBudget, ScanScheduler, Storage,
 };
 
 // The maximum size of a compact block, in bytes (12MB).
@@ -48,6 +48,10 @@ pub struct Worker {
     sync_height_tx: watch::Sender<u64>,
     /// Tonic channel used to create GRPC clients.
     channel: Channel,
+    /// Used to watch for changes to the scan budget.
+    scan_budget_rx: watch::Receiver<Option<ScanBudget>>,
+    /// Paces block scanning, if there is a scan budget.
+    scan_scheduler: Option<ScanScheduler>,
 }
 
 impl Worker {
@@ -57,10 +61,13 @@ impl Worker {
     /// - a shared, in-memory SCT instance;
     /// - a shared error slot;
     /// - a channel for notifying the client of sync progress.
+    ///
+    /// Blocks are scanned within the budget last sent on `scan_budget_rx`, if any.
     #[instrument(skip_all)]
     pub async fn new(
         storage: Storage,
         channel: Channel,
+        mut scan_budget_rx: watch::Receiver<Option<ScanBudget>>,
     ) -> Result<
         (
             Self,
@@ -86,6 +93,10 @@ impl Worker {
             watch::channel(storage.last_sync_height().await?.unwrap_or(0));
         // Mark the current height as seen, since it's not new.
         sync_height_rx.borrow_and_update();
+        let scan_scheduler = scan_budget_rx
+            .borrow_and_update()
+            .map(ScanScheduler::new)
+            .transpose()?;
 
         Ok((
             Self {
@@ -95,6 +106,8 @@ impl Worker {
                 error_slot: error_slot.clone(),
                 sync_height_tx,
                 channel,
+                scan_budget_rx,
+                scan_scheduler,
             },
             sct,
             error_slot,
@@ -233,6 +246,11 @@ impl Worker {
             }
             expected_height += 1;
 
+            // Wait for the scan budget before taking the SCT lock, so that queries aren't blocked.
+            if block.requires_scanning() {
+                self.wait_for_scan_budget(&block).await?;
+            }
+
             // Lock the SCT only while processing this block.
             let mut sct_guard = self.sct.write().await;
 
@@ -429,6 +447,22 @@ impl Worker {
         Ok(())
     }
 
+    /// Wait until the scan budget, if any, allows `block` to be scanned.
+    async fn wait_for_scan_budget(&mut self, block: &CompactBlock) -> anyhow::Result<()> {
+        // If the view server has gone, the budget can't change any more.
+        if self.scan_budget_rx.has_changed().unwrap_or(false) {
+            self.scan_scheduler = self
+                .scan_budget_rx
+                .borrow_and_update()
+                .map(ScanScheduler::new)
+                .transpose()?;
+        }
+        if let Some(scheduler) = &mut self.scan_scheduler {
+            scheduler.wait_to_scan(block).await;
+        }
+        Ok(())
+    }
+
     pub async fn run(mut self) -> anyhow::Result<()> {
         loop {
             // Do a single sync run, recording any errors.
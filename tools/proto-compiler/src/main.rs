@@ -114,8 +114,11 @@ fn main() -> anyhow::Result<()> {
                 // Also included in the cnidarium crate directly.
                 "../../proto/penumbra/penumbra/cnidarium/v1/cnidarium.proto",
                 "../../proto/penumbra/penumbra/tools/summoning/v1/summoning.proto",
+                "../../proto/penumbra/penumbra/util/cometindex/v1/cometindex.proto",
+                "../../proto/penumbra/penumbra/util/error/v1/error.proto",
                 "../../proto/penumbra/penumbra/util/tendermint_proxy/v1/tendermint_proxy.proto",
                 "../../proto/penumbra/penumbra/view/v1/view.proto",
+                "../../proto/rust-vendored/google/rpc/status.proto",
                 "../../proto/rust-vendored/tendermint/abci/types.proto",
                 "../../proto/rust-vendored/tendermint/types/validator.proto",
                 "../../proto/rust-vendored/tendermint/p2p/types.proto",
@@ -160,6 +163,9 @@ fn main() -> anyhow::Result<()> {
             ".penumbra.util.tendermint_proxy.v1.ABCIQueryResponse".to_owned(),
             ".penumbra.util.tendermint_proxy.v1.GetBlockByHeightResponse".to_owned(),
             ".penumbra.util.tendermint_proxy.v1.GetStatusResponse".to_owned(),
+            // These embed `tendermint.abci.Event`, for the same reason.
+            ".penumbra.util.cometindex.v1.ContextualizedEvent".to_owned(),
+            ".penumbra.util.cometindex.v1.EventResponse".to_owned(),
         ])
         .build(&[".penumbra"])?;
 